@@ -64,11 +64,54 @@ mod packed_msm_benches {
         }
     }
 
+    fn per_output_msm_computation(c: &mut Criterion) {
+        init_backend();
+
+        let mut rng = OsRng;
+        let num_commits = 1024;
+        let num_rows = 1024;
+        let element_num_bytes = 32;
+
+        let mut res = vec![RistrettoPoint::default(); num_commits];
+
+        let generators: Vec<RistrettoPoint> = (0..num_rows)
+            .map(|_| RistrettoPoint::random(&mut rng))
+            .collect();
+
+        let handle = MsmHandle::new(&generators);
+
+        let outputs: Vec<Vec<u8>> = (0..num_commits)
+            .map(|_| {
+                (0..num_rows * element_num_bytes)
+                    .map(|_| rng.gen::<u8>())
+                    .collect()
+            })
+            .collect();
+        let scalars =
+            ScalarMatrix::from_outputs(element_num_bytes as u32, outputs.iter().map(Vec::as_slice));
+
+        let label = format!(
+            "Column-major scalars - {} commits, {} rows, {} bytes",
+            num_commits, num_rows, element_num_bytes
+        );
+        c.bench_function(&label, |b| {
+            b.iter(|| handle.msm(&mut res, element_num_bytes as u32, scalars.as_bytes()))
+        });
+
+        let label = format!(
+            "Per-output scalars - {} commits, {} rows, {} bytes",
+            num_commits, num_rows, element_num_bytes
+        );
+        c.bench_function(&label, |b| {
+            b.iter(|| handle.msm_from_outputs(&mut res, element_num_bytes as u32, &outputs))
+        });
+    }
+
     criterion_group! {
         name = blitzar_packed_msm_commitments;
         // Lower the sample size to run the benchmarks faster
         config = Criterion::default().sample_size(15);
-        targets = packed_msm_commitment_computation
+        targets = packed_msm_commitment_computation, per_output_msm_computation
     }
 }
 
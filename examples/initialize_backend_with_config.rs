@@ -21,10 +21,9 @@ fn main() {
     /////////////////////////////////////////////
     // Initialize the backend with a custom configuration
     /////////////////////////////////////////////
-    let num_precomputed_generators: u64 = 7;
-    init_backend_with_config(BackendConfig {
-        num_precomputed_generators,
-    });
+    let mut config = BackendConfig::default();
+    config.num_precomputed_generators = 7;
+    init_backend_with_config(config);
 
     /////////////////////////////////////////////
     // Define the data vectors that will be used in the computation
@@ -0,0 +1,216 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! programmatic benchmarks of the commitment computation
+//!
+//! This module is only available with the `bench` feature. It builds random workloads
+//! of a configurable shape and times them, so that external performance harnesses do
+//! not need to duplicate the setup of the benchmarks of this crate.
+
+use crate::{
+    compute::{
+        generate_generators, init_backend, Bls12381G1, Bn254G1, CommitmentScheme, Curve25519,
+        DeriveGenerator, ElementP2, Grumpkin,
+    },
+    sequence::Sequence,
+};
+use ark_std::rand::{rngs::StdRng, RngCore, SeedableRng};
+use std::time::{Duration, Instant};
+
+/// Label used to derive the generators of the workloads.
+const GENERATORS_LABEL: &[u8] = b"blitzar-bench";
+
+/// Curve over which a [Workload] computes commitments.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BenchCurve {
+    /// `curve25519`, with compressed ristretto outputs.
+    Curve25519,
+    /// `bls12-381` `G1`, with compressed outputs.
+    Bls12381G1,
+    /// `bn254` `G1`, with uncompressed outputs.
+    Bn254G1,
+    /// `grumpkin`, with uncompressed outputs.
+    Grumpkin,
+}
+
+/// Shape of a random commitment workload.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Workload {
+    /// Curve of the commitments.
+    pub curve: BenchCurve,
+    /// Number of commitments computed by each run.
+    pub num_outputs: usize,
+    /// Number of elements of each committed sequence.
+    pub length: usize,
+    /// Number of bytes of each element, between `1` and `32`.
+    pub element_size: usize,
+    /// Seed of the random data, so that workloads can be reproduced.
+    pub seed: u64,
+}
+
+/// Timings of the runs of a [Workload].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BenchStats {
+    /// Number of timed runs.
+    pub iterations: usize,
+    /// Total time of the timed runs.
+    pub total: Duration,
+    /// Fastest run.
+    pub min: Duration,
+    /// Slowest run.
+    pub max: Duration,
+    /// Average run time.
+    pub mean: Duration,
+    /// Median run time.
+    pub median: Duration,
+}
+
+impl Workload {
+    /// Creates a workload with a seed of `0`.
+    pub fn new(curve: BenchCurve, num_outputs: usize, length: usize, element_size: usize) -> Self {
+        Self {
+            curve,
+            num_outputs,
+            length,
+            element_size,
+            seed: 0,
+        }
+    }
+
+    /// Returns the random data committed by each run, one byte vector per output.
+    pub fn data(&self) -> Vec<Vec<u8>> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        (0..self.num_outputs)
+            .map(|_| {
+                let mut bytes = vec![0_u8; self.length * self.element_size];
+                rng.fill_bytes(&mut bytes);
+                bytes
+            })
+            .collect()
+    }
+
+    /// Runs the workload once untimed to warm up the backend, then `iterations` timed
+    /// times.
+    ///
+    /// Generating the data and the generators is not included in the timings.
+    ///
+    /// # Panics
+    ///
+    /// If `iterations` is zero or `element_size` is not between `1` and `32`.
+    pub fn run(&self, iterations: usize) -> BenchStats {
+        assert!(iterations > 0, "at least one iteration is required");
+        init_backend();
+
+        let data = self.data();
+        let sequences: Vec<Sequence> = data
+            .iter()
+            .map(|bytes| Sequence::from_byte_slice(bytes, self.element_size, false))
+            .collect();
+        let times = match self.curve {
+            BenchCurve::Curve25519 => {
+                let generators = generate_generators(GENERATORS_LABEL, self.length);
+                time::<Curve25519>(&sequences, &generators, iterations)
+            }
+            BenchCurve::Bls12381G1 => {
+                let generators =
+                    sw_generators::<ElementP2<ark_bls12_381::g1::Config>, _>(self.length);
+                time::<Bls12381G1>(&sequences, &generators, iterations)
+            }
+            BenchCurve::Bn254G1 => {
+                let generators = sw_generators::<ElementP2<ark_bn254::g1::Config>, _>(self.length);
+                time::<Bn254G1>(&sequences, &generators, iterations)
+            }
+            BenchCurve::Grumpkin => {
+                let generators =
+                    sw_generators::<ElementP2<ark_grumpkin::GrumpkinConfig>, _>(self.length);
+                time::<Grumpkin>(&sequences, &generators, iterations)
+            }
+        };
+        BenchStats::from_times(times)
+    }
+}
+
+impl BenchStats {
+    fn from_times(mut times: Vec<Duration>) -> Self {
+        times.sort_unstable();
+        let iterations = times.len();
+        let total: Duration = times.iter().sum();
+        Self {
+            iterations,
+            total,
+            min: times[0],
+            max: times[iterations - 1],
+            mean: total / iterations as u32,
+            median: times[iterations / 2],
+        }
+    }
+}
+
+fn sw_generators<T: DeriveGenerator + Send, A: From<T>>(n: usize) -> Vec<A> {
+    generate_generators::<T>(GENERATORS_LABEL, n)
+        .into_iter()
+        .map(Into::into)
+        .collect()
+}
+
+fn time<C: CommitmentScheme>(
+    data: &[Sequence],
+    generators: &[C::Generator],
+    iterations: usize,
+) -> Vec<Duration> {
+    C::compute_commitments_with_generators(data, generators);
+    (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            C::compute_commitments_with_generators(data, generators);
+            start.elapsed()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn we_can_run_a_workload_on_every_curve() {
+        for curve in [
+            BenchCurve::Curve25519,
+            BenchCurve::Bls12381G1,
+            BenchCurve::Bn254G1,
+            BenchCurve::Grumpkin,
+        ] {
+            let stats = Workload::new(curve, 2, 3, 4).run(3);
+            assert_eq!(stats.iterations, 3);
+            assert!(stats.min <= stats.median && stats.median <= stats.max);
+        }
+    }
+
+    #[test]
+    fn workloads_with_the_same_seed_have_the_same_data() {
+        let workload = Workload::new(BenchCurve::Curve25519, 3, 5, 2);
+        let data = workload.data();
+        assert_eq!(data.len(), 3);
+        assert!(data.iter().all(|bytes| bytes.len() == 10));
+        assert_eq!(data, workload.data());
+        assert_ne!(
+            data,
+            Workload {
+                seed: 1,
+                ..workload
+            }
+            .data()
+        );
+    }
+}
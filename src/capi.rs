@@ -0,0 +1,460 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! stable C ABI for the commitment and MSM entry points
+//!
+//! The functions of this module are exported unmangled with the `blitzar_` prefix, so
+//! that other languages can call the high-level API of this crate, including its input
+//! validation, without re-wrapping `blitzar-sys`. A shared library is built with
+//!
+//! ```text
+//! cargo rustc --release --features capi --crate-type cdylib
+//! ```
+//!
+//! and a header can be generated from this module with `cbindgen`.
+//!
+//! Every fallible function returns a [BlitzarStatus] instead of panicking: invalid
+//! inputs are reported as [BlitzarStatus::InvalidInput], and a panic inside the crate is
+//! caught and reported as [BlitzarStatus::Panic]. Points are exchanged in their 32-byte
+//! compressed encoding. Additions to this interface increase [BLITZAR_CAPI_VERSION];
+//! existing functions and types are never changed.
+
+use crate::{
+    compute::{
+        try_compute_curve25519_commitments, try_compute_curve25519_commitments_with_generators,
+        MsmHandle, ScalarWidth,
+    },
+    error::Error,
+    sequence::Sequence,
+};
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Version of the C interface, returned by [blitzar_capi_version].
+pub const BLITZAR_CAPI_VERSION: u32 = 1;
+
+/// Status returned by the fallible functions of the C interface
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlitzarStatus {
+    /// The call succeeded.
+    Ok = 0,
+    /// A pointer, length, sequence descriptor or point is invalid.
+    InvalidInput = 1,
+    /// The backend cannot be initialized or fails.
+    Backend = 2,
+    /// The call panicked.
+    Panic = 3,
+}
+
+impl From<Error> for BlitzarStatus {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::Backend(_) => BlitzarStatus::Backend,
+            _ => BlitzarStatus::InvalidInput,
+        }
+    }
+}
+
+/// Descriptor of a sequence of `n` elements of `element_nbytes` bytes each
+///
+/// This has the same meaning as [Sequence]: the elements are little-endian integers,
+/// read as two's complement when `is_signed` is non-zero.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct BlitzarSequenceDescriptor {
+    /// The number of bytes of each element
+    pub element_nbytes: u8,
+    /// The number of elements
+    pub n: u64,
+    /// The elements, which may be null when `n` is zero
+    pub data: *const u8,
+    /// Whether the elements are signed
+    pub is_signed: i32,
+}
+
+/// Handle to compute curve25519 MSMs with pre-specified generators
+///
+/// The handle is opaque to C: it is created by [blitzar_curve25519_msm_handle_new] and
+/// must be released with [blitzar_curve25519_msm_handle_free].
+pub struct BlitzarCurve25519MsmHandle(MsmHandle<RistrettoPoint>);
+
+/// Runs `f`, converting a panic into [BlitzarStatus::Panic].
+fn guard(f: impl FnOnce() -> Result<(), BlitzarStatus>) -> BlitzarStatus {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => BlitzarStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => BlitzarStatus::Panic,
+    }
+}
+
+/// Returns the `len` elements at `ptr`, which may be null when `len` is zero.
+///
+/// # Safety
+///
+/// Unless `len` is zero, `ptr` must be valid for reads of `len` elements.
+unsafe fn slice_from<'a, T>(ptr: *const T, len: usize) -> Result<&'a [T], BlitzarStatus> {
+    match (len, ptr.is_null()) {
+        (0, _) => Ok(&[]),
+        (_, true) => Err(BlitzarStatus::InvalidInput),
+        (_, false) => Ok(std::slice::from_raw_parts(ptr, len)),
+    }
+}
+
+/// Returns the `len` elements at `ptr`, which may be null when `len` is zero.
+///
+/// # Safety
+///
+/// Unless `len` is zero, `ptr` must be valid for writes of `len` elements.
+unsafe fn slice_from_mut<'a, T>(ptr: *mut T, len: usize) -> Result<&'a mut [T], BlitzarStatus> {
+    match (len, ptr.is_null()) {
+        (0, _) => Ok(&mut []),
+        (_, true) => Err(BlitzarStatus::InvalidInput),
+        (_, false) => Ok(std::slice::from_raw_parts_mut(ptr, len)),
+    }
+}
+
+/// Converts the descriptors into sequences.
+///
+/// # Safety
+///
+/// The data of every descriptor must be valid for reads of `n * element_nbytes` bytes.
+unsafe fn sequences_from<'a>(
+    descriptors: &[BlitzarSequenceDescriptor],
+) -> Result<Vec<Sequence<'a>>, BlitzarStatus> {
+    descriptors
+        .iter()
+        .map(|d| {
+            let num_bytes = usize::try_from(d.n)
+                .ok()
+                .and_then(|n| n.checked_mul(d.element_nbytes as usize))
+                .ok_or(BlitzarStatus::InvalidInput)?;
+            let data = slice_from(d.data, num_bytes)?;
+            Sequence::try_from_byte_slice_with_size(
+                data,
+                d.element_nbytes as usize,
+                d.is_signed != 0,
+            )
+            .map_err(|_| BlitzarStatus::InvalidInput)
+        })
+        .collect()
+}
+
+/// Decompresses `num_generators` points of 32 bytes each.
+///
+/// # Safety
+///
+/// Unless `num_generators` is zero, `generators` must be valid for reads of
+/// `32 * num_generators` bytes.
+unsafe fn generators_from(
+    generators: *const u8,
+    num_generators: u64,
+) -> Result<Vec<RistrettoPoint>, BlitzarStatus> {
+    let num_bytes = usize::try_from(num_generators)
+        .ok()
+        .and_then(|n| n.checked_mul(32))
+        .ok_or(BlitzarStatus::InvalidInput)?;
+    slice_from(generators, num_bytes)?
+        .chunks_exact(32)
+        .map(|bytes| {
+            CompressedRistretto::from_slice(bytes)
+                .ok()
+                .and_then(|point| point.decompress())
+                .ok_or(BlitzarStatus::InvalidInput)
+        })
+        .collect()
+}
+
+/// Returns [BLITZAR_CAPI_VERSION].
+#[no_mangle]
+pub extern "C" fn blitzar_capi_version() -> u32 {
+    BLITZAR_CAPI_VERSION
+}
+
+/// Computes the curve25519 commitments of `num_sequences` sequences using the
+/// backend's generators starting at `offset_generators`.
+///
+/// See [crate::compute::compute_curve25519_commitments]. The commitments are written to
+/// `commitments` in their 32-byte compressed encoding.
+///
+/// # Safety
+///
+/// `commitments` must be valid for writes of `32 * num_sequences` bytes, `descriptors`
+/// must be valid for reads of `num_sequences` descriptors, and the data of every
+/// descriptor must be valid for reads of `n * element_nbytes` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn blitzar_compute_curve25519_commitments(
+    commitments: *mut u8,
+    descriptors: *const BlitzarSequenceDescriptor,
+    num_sequences: u32,
+    offset_generators: u64,
+) -> BlitzarStatus {
+    guard(|| {
+        let num_sequences = num_sequences as usize;
+        let commitments = slice_from_mut(commitments as *mut CompressedRistretto, num_sequences)?;
+        let data = sequences_from(slice_from(descriptors, num_sequences)?)?;
+        Ok(try_compute_curve25519_commitments(
+            commitments,
+            &data,
+            offset_generators,
+        )?)
+    })
+}
+
+/// Computes the curve25519 commitments of `num_sequences` sequences using the
+/// `num_generators` given generators.
+///
+/// See [crate::compute::compute_curve25519_commitments_with_generators]. The generators
+/// are read and the commitments are written in their 32-byte compressed encoding.
+///
+/// # Safety
+///
+/// In addition to the requirements of [blitzar_compute_curve25519_commitments],
+/// `generators` must be valid for reads of `32 * num_generators` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn blitzar_compute_curve25519_commitments_with_generators(
+    commitments: *mut u8,
+    descriptors: *const BlitzarSequenceDescriptor,
+    num_sequences: u32,
+    generators: *const u8,
+    num_generators: u64,
+) -> BlitzarStatus {
+    guard(|| {
+        let num_sequences = num_sequences as usize;
+        let commitments = slice_from_mut(commitments as *mut CompressedRistretto, num_sequences)?;
+        let data = sequences_from(slice_from(descriptors, num_sequences)?)?;
+        let generators = generators_from(generators, num_generators)?;
+        Ok(try_compute_curve25519_commitments_with_generators(
+            commitments,
+            &data,
+            &generators,
+        )?)
+    })
+}
+
+/// Creates a handle from `num_generators` generators in their 32-byte compressed
+/// encoding.
+///
+/// Returns null if a generator is not a valid point or the handle cannot be created.
+///
+/// # Safety
+///
+/// `generators` must be valid for reads of `32 * num_generators` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn blitzar_curve25519_msm_handle_new(
+    generators: *const u8,
+    num_generators: u64,
+) -> *mut BlitzarCurve25519MsmHandle {
+    let mut handle = None;
+    guard(|| {
+        let generators = generators_from(generators, num_generators)?;
+        crate::compute::ensure_initialized().map_err(|_| BlitzarStatus::Backend)?;
+        handle = Some(MsmHandle::new(&generators));
+        Ok(())
+    });
+    handle.map_or(std::ptr::null_mut(), |handle| {
+        Box::into_raw(Box::new(BlitzarCurve25519MsmHandle(handle)))
+    })
+}
+
+/// Releases a handle created by [blitzar_curve25519_msm_handle_new].
+///
+/// Passing null does nothing.
+///
+/// # Safety
+///
+/// `handle` must be null or a handle that was not released yet.
+#[no_mangle]
+pub unsafe extern "C" fn blitzar_curve25519_msm_handle_free(
+    handle: *mut BlitzarCurve25519MsmHandle,
+) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Computes `num_outputs` MSMs with the generators of `handle`.
+///
+/// `scalars` holds `scalars_len` bytes laid out as in [MsmHandle::msm], and the outputs
+/// are written to `res` in their 32-byte compressed encoding.
+///
+/// Returns [BlitzarStatus::InvalidInput] in the cases where [MsmHandle::try_msm] returns
+/// an error, or if `element_num_bytes` is not 1, 2, 4, 8, 16 or 32.
+///
+/// # Safety
+///
+/// `handle` must be a handle that was not released, `res` must be valid for writes of
+/// `32 * num_outputs` bytes, and `scalars` must be valid for reads of `scalars_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn blitzar_curve25519_msm(
+    handle: *const BlitzarCurve25519MsmHandle,
+    res: *mut u8,
+    num_outputs: u32,
+    element_num_bytes: u32,
+    scalars: *const u8,
+    scalars_len: u64,
+) -> BlitzarStatus {
+    guard(|| {
+        let handle = handle.as_ref().ok_or(BlitzarStatus::InvalidInput)?;
+        let res = slice_from_mut(res as *mut CompressedRistretto, num_outputs as usize)?;
+        let scalars_len = usize::try_from(scalars_len).map_err(|_| BlitzarStatus::InvalidInput)?;
+        let scalars = slice_from(scalars, scalars_len)?;
+        let width =
+            ScalarWidth::try_from(element_num_bytes).map_err(|_| BlitzarStatus::InvalidInput)?;
+        let mut points = vec![RistrettoPoint::default(); res.len()];
+        handle
+            .0
+            .try_msm(&mut points, width, scalars)
+            .map_err(|_| BlitzarStatus::InvalidInput)?;
+        res.iter_mut()
+            .zip(points)
+            .for_each(|(res, point)| *res = point.compress());
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compute::{compute_curve25519_commitments, get_curve25519_generators};
+    use curve25519_dalek::scalar::Scalar;
+
+    fn descriptor(data: &[u64]) -> BlitzarSequenceDescriptor {
+        BlitzarSequenceDescriptor {
+            element_nbytes: 8,
+            n: data.len() as u64,
+            data: data.as_ptr() as *const u8,
+            is_signed: 0,
+        }
+    }
+
+    #[test]
+    fn we_can_get_the_version_of_the_c_interface() {
+        assert_eq!(blitzar_capi_version(), BLITZAR_CAPI_VERSION);
+    }
+
+    #[test]
+    fn we_can_compute_commitments_through_the_c_interface() {
+        let data: Vec<u64> = vec![1, 2, 3];
+        let mut expected = [CompressedRistretto::default()];
+        compute_curve25519_commitments(&mut expected, &[(&data).into()], 5);
+
+        let mut commitments = [0_u8; 32];
+        let status = unsafe {
+            blitzar_compute_curve25519_commitments(
+                commitments.as_mut_ptr(),
+                &descriptor(&data),
+                1,
+                5,
+            )
+        };
+        assert_eq!(status, BlitzarStatus::Ok);
+        assert_eq!(commitments, expected[0].to_bytes());
+    }
+
+    #[test]
+    fn we_can_compute_commitments_with_generators_through_the_c_interface() {
+        let data: Vec<u64> = vec![1, 2];
+        let mut generators = vec![RistrettoPoint::default(); 2];
+        get_curve25519_generators(&mut generators, 0);
+        let compressed: Vec<u8> = generators
+            .iter()
+            .flat_map(|g| g.compress().to_bytes())
+            .collect();
+
+        let mut commitments = [0_u8; 32];
+        let status = unsafe {
+            blitzar_compute_curve25519_commitments_with_generators(
+                commitments.as_mut_ptr(),
+                &descriptor(&data),
+                1,
+                compressed.as_ptr(),
+                2,
+            )
+        };
+        assert_eq!(status, BlitzarStatus::Ok);
+        let expected = generators[0] + Scalar::from(2_u64) * generators[1];
+        assert_eq!(commitments, expected.compress().to_bytes());
+    }
+
+    #[test]
+    fn we_cannot_compute_commitments_with_invalid_inputs_through_the_c_interface() {
+        let data: Vec<u64> = vec![1, 2];
+        let mut commitments = [0_u8; 32];
+
+        let mut invalid = descriptor(&data);
+        invalid.element_nbytes = 0;
+        let status = unsafe {
+            blitzar_compute_curve25519_commitments(commitments.as_mut_ptr(), &invalid, 1, 0)
+        };
+        assert_eq!(status, BlitzarStatus::InvalidInput);
+
+        let status = unsafe {
+            blitzar_compute_curve25519_commitments(std::ptr::null_mut(), &descriptor(&data), 1, 0)
+        };
+        assert_eq!(status, BlitzarStatus::InvalidInput);
+
+        let not_a_point = [0xff_u8; 32];
+        let status = unsafe {
+            blitzar_compute_curve25519_commitments_with_generators(
+                commitments.as_mut_ptr(),
+                &descriptor(&data[..1]),
+                1,
+                not_a_point.as_ptr(),
+                1,
+            )
+        };
+        assert_eq!(status, BlitzarStatus::InvalidInput);
+    }
+
+    #[test]
+    fn we_can_compute_msms_with_a_handle_through_the_c_interface() {
+        let mut generators = vec![RistrettoPoint::default(); 2];
+        get_curve25519_generators(&mut generators, 0);
+        let compressed: Vec<u8> = generators
+            .iter()
+            .flat_map(|g| g.compress().to_bytes())
+            .collect();
+
+        unsafe {
+            let handle = blitzar_curve25519_msm_handle_new(compressed.as_ptr(), 2);
+            assert!(!handle.is_null());
+
+            // g[0] + 2 * g[1]
+            let scalars: Vec<u8> = vec![1, 2];
+            let mut res = [0_u8; 32];
+            let status =
+                blitzar_curve25519_msm(handle, res.as_mut_ptr(), 1, 1, scalars.as_ptr(), 2);
+            assert_eq!(status, BlitzarStatus::Ok);
+            let expected = generators[0] + generators[1] + generators[1];
+            assert_eq!(res, expected.compress().to_bytes());
+
+            // an MSM longer than the number of generators is rejected
+            let scalars: Vec<u8> = vec![1, 2, 3];
+            let status =
+                blitzar_curve25519_msm(handle, res.as_mut_ptr(), 1, 1, scalars.as_ptr(), 3);
+            assert_eq!(status, BlitzarStatus::InvalidInput);
+
+            blitzar_curve25519_msm_handle_free(handle);
+        }
+    }
+
+    #[test]
+    fn we_cannot_create_a_handle_from_invalid_generators_through_the_c_interface() {
+        let not_a_point = [0xff_u8; 32];
+        let handle = unsafe { blitzar_curve25519_msm_handle_new(not_a_point.as_ptr(), 1) };
+        assert!(handle.is_null());
+        unsafe { blitzar_curve25519_msm_handle_free(handle) };
+    }
+}
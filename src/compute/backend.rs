@@ -11,19 +11,147 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
-use std::sync::Once;
+use super::error::BackendError;
+use std::sync::OnceLock;
 
 /// Struct to hold configuration values about the chosen backend.
+///
+/// Start from [BackendConfig::default] and set the fields to change, since more fields
+/// may be added in the future.
+#[non_exhaustive]
 pub struct BackendConfig {
     /// The total number of precomputed values to be generated.
     pub num_precomputed_generators: u64,
+
+    /// The maximum number of bytes of inputs and outputs that a single commitment
+    /// computation may transfer to the device, or `None` for no limit.
+    ///
+    /// The `compute_curve25519_commitments` and `compute_*_commitments_with_generators`
+    /// functions of every curve split larger computations into chunks of rows. When even a
+    /// single chunk would exceed the limit, their `try_` variants return
+    /// [BackendError::MemoryLimitExceeded] and the other variants panic. An MSM with a
+    /// [super::MsmHandle], including the `*_with_handle` commitment functions, cannot be
+    /// split, so [super::MsmHandle::try_msm] returns the error and the other methods panic.
+    /// The backend also allocates scratch memory, so the limit should leave some headroom
+    /// below the memory of the device.
+    pub max_device_memory_bytes: Option<u64>,
+
+    /// The largest total number of elements committed on the host instead of the backend,
+    /// or `0` to always call the backend.
+    ///
+    /// For tiny batches, the launch overhead of the backend exceeds the work, so the
+    /// `compute_*_commitments_with_generators` functions compute the commitments of at
+    /// most this many elements with the `curve25519-dalek` and `arkworks` MSMs instead.
+    /// The results are identical. `compute_curve25519_commitments`, which uses the
+    /// generators of the backend, always calls the backend.
+    pub small_batch_threshold: usize,
 }
 
-// holds the state of the backend initialization (0 for success, non-zero otherwise)
-static mut INIT_STATE: i32 = 0;
+/// The kind of backend the crate was built with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BackendKind {
+    /// The serial CPU backend, enabled by the `cpu` feature.
+    Cpu,
+    /// The CUDA GPU backend, enabled by the `gpu` feature.
+    Gpu,
+}
 
-// static variable used to assure that the backend initialization is triggered only once
-static INIT: Once = Once::new();
+/// Runtime information about the backend, as returned by [backend_info].
+///
+/// `blitzar-sys` does not report device properties or memory usage, so only the
+/// values known to this crate are available.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BackendInfo {
+    /// The backend selected at build time.
+    pub backend: BackendKind,
+    /// Whether the backend was initialized successfully.
+    pub initialized: bool,
+    /// The number of precomputed generators the backend was initialized with, or zero
+    /// if it is not initialized.
+    pub num_precomputed_generators: u64,
+    /// The device memory limit the backend was initialized with, if any.
+    pub max_device_memory_bytes: Option<u64>,
+    /// The small batch threshold the backend was initialized with, or zero if it is not
+    /// initialized.
+    pub small_batch_threshold: usize,
+}
+
+/// State of an initialized backend.
+struct Backend {
+    num_precomputed_generators: u64,
+    max_device_memory_bytes: Option<u64>,
+    small_batch_threshold: usize,
+}
+
+// holds the result of the backend initialization, which is triggered only once
+static BACKEND: OnceLock<Result<Backend, BackendError>> = OnceLock::new();
+
+/// The number of generators precomputed by [init_backend].
+const DEFAULT_NUM_PRECOMPUTED_GENERATORS: u64 = 20;
+
+/// Environment variable overriding [BackendConfig::num_precomputed_generators].
+pub const NUM_PRECOMPUTED_GENERATORS_ENV: &str = "BLITZAR_NUM_PRECOMPUTED_GENERATORS";
+
+/// Environment variable setting [BackendConfig::max_device_memory_bytes].
+pub const MAX_DEVICE_MEMORY_BYTES_ENV: &str = "BLITZAR_MAX_DEVICE_MEMORY_BYTES";
+
+/// Environment variable setting [BackendConfig::small_batch_threshold].
+pub const SMALL_BATCH_THRESHOLD_ENV: &str = "BLITZAR_SMALL_BATCH_THRESHOLD";
+
+/// Parses the value of the environment variable `name`.
+fn parse_var<T: std::str::FromStr>(name: &'static str, value: &str) -> Result<T, BackendError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| BackendError::InvalidEnvironmentVariable {
+            name,
+            value: value.to_string(),
+        })
+}
+
+/// Builds the configuration from the environment variables returned by `var`, falling
+/// back to the defaults for unset variables.
+fn parse_config(
+    var: impl Fn(&'static str) -> Option<String>,
+) -> Result<BackendConfig, BackendError> {
+    let parse = |name| var(name).map(|value| parse_var(name, &value)).transpose();
+    let default = BackendConfig::default();
+    Ok(BackendConfig {
+        num_precomputed_generators: parse(NUM_PRECOMPUTED_GENERATORS_ENV)?
+            .unwrap_or(default.num_precomputed_generators),
+        max_device_memory_bytes: parse(MAX_DEVICE_MEMORY_BYTES_ENV)?
+            .or(default.max_device_memory_bytes),
+        small_batch_threshold: parse(SMALL_BATCH_THRESHOLD_ENV)?
+            .map_or(default.small_batch_threshold, |n: u64| n as usize),
+    })
+}
+
+impl Default for BackendConfig {
+    fn default() -> Self {
+        Self {
+            num_precomputed_generators: DEFAULT_NUM_PRECOMPUTED_GENERATORS,
+            max_device_memory_bytes: None,
+            small_batch_threshold: 0,
+        }
+    }
+}
+
+impl BackendConfig {
+    /// Reads the configuration from the environment.
+    ///
+    /// The following variables are read, and unset variables keep their default:
+    ///
+    /// | Variable | Field | Default |
+    /// |----------|-------|---------|
+    /// | `BLITZAR_NUM_PRECOMPUTED_GENERATORS` | `num_precomputed_generators` | `20` |
+    /// | `BLITZAR_MAX_DEVICE_MEMORY_BYTES` | `max_device_memory_bytes` | no limit |
+    /// | `BLITZAR_SMALL_BATCH_THRESHOLD` | `small_batch_threshold` | `0` |
+    ///
+    /// The backend does not expose other settings, such as the device to use.
+    pub fn from_env() -> Result<Self, BackendError> {
+        parse_config(|name| std::env::var(name).ok())
+    }
+}
 
 /// verify which feature backend was passed to the build
 fn get_backend() -> i32 {
@@ -36,6 +164,38 @@ fn get_backend() -> i32 {
     }
 }
 
+fn initialize(config: BackendConfig) -> &'static Result<Backend, BackendError> {
+    BACKEND.get_or_init(|| {
+        // initializes the backend using the lower-level rust sys crate
+        let sxt_config = blitzar_sys::sxt_config {
+            backend: get_backend(),
+            num_precomputed_generators: config.num_precomputed_generators,
+        };
+        match unsafe { blitzar_sys::sxt_init(&sxt_config) } {
+            0 => Ok(Backend {
+                num_precomputed_generators: config.num_precomputed_generators,
+                max_device_memory_bytes: config.max_device_memory_bytes,
+                small_batch_threshold: config.small_batch_threshold,
+            }),
+            code => Err(BackendError::InitializationFailed(code)),
+        }
+    })
+}
+
+/// Initializes the backend with the configuration read by [BackendConfig::from_env],
+/// unless it is already initialized, and returns the result of the initialization.
+///
+/// Unlike [init_backend], this does not panic if the initialization failed.
+pub fn ensure_initialized() -> Result<(), BackendError> {
+    if let Some(res) = BACKEND.get() {
+        return res.as_ref().map(|_| ()).map_err(Clone::clone);
+    }
+    match initialize(BackendConfig::from_env()?) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(err.clone()),
+    }
+}
+
 #[doc = include_str!("../../docs/commitments/init_backend.md")]
 ///
 /// # Example - Initializing the Backend
@@ -48,25 +208,23 @@ fn get_backend() -> i32 {
 #[doc = include_str!("../../examples/initialize_backend.rs")]
 /// ```
 pub fn init_backend() {
-    unsafe {
-        let num_precomputed_generators: u64 = 20;
-
-        INIT.call_once(|| {
-            let backend = get_backend();
-
-            // initializes the backend using the lower-level rust sys crate
-            let config: blitzar_sys::sxt_config = blitzar_sys::sxt_config {
-                backend,
-                num_precomputed_generators,
-            };
-
-            INIT_STATE = blitzar_sys::sxt_init(&config);
-        });
+    if ensure_initialized().is_err() {
+        panic!("Error during backend initialization");
+    }
+}
 
-        if INIT_STATE != 0 {
-            panic!("Error during backend initialization");
-        }
-    };
+/// Initializes the backend with the configuration read from the `BLITZAR_*` environment
+/// variables listed in [BackendConfig::from_env].
+///
+/// The lazy initialization done by the compute functions reads the same variables, so
+/// this only needs to be called to initialize the backend eagerly.
+///
+/// # Panics
+///
+/// If a variable cannot be parsed or if the backend initialization fails.
+pub fn init_backend_from_env() {
+    let config = BackendConfig::from_env().unwrap_or_else(|err| panic!("{err}"));
+    init_backend_with_config(config);
 }
 
 #[doc = include_str!("../../docs/commitments/init_backend_with_config.md")]
@@ -84,21 +242,93 @@ pub fn init_backend() {
 #[doc = include_str!("../../examples/initialize_backend_with_config.rs")]
 /// ```
 pub fn init_backend_with_config(config: BackendConfig) {
-    unsafe {
-        INIT.call_once(|| {
-            let backend = get_backend();
+    if initialize(config).is_err() {
+        panic!("Error during backend initialization");
+    }
+}
 
-            // initializes the backend using the lower-level rust sys crate
-            let config: blitzar_sys::sxt_config = blitzar_sys::sxt_config {
-                backend,
-                num_precomputed_generators: config.num_precomputed_generators,
-            };
+/// Returns information about the backend.
+///
+/// This does not initialize the backend.
+pub fn backend_info() -> BackendInfo {
+    let backend = BACKEND.get().and_then(|res| res.as_ref().ok());
+    BackendInfo {
+        backend: if cfg!(feature = "cpu") {
+            BackendKind::Cpu
+        } else {
+            BackendKind::Gpu
+        },
+        initialized: backend.is_some(),
+        num_precomputed_generators: backend.map_or(0, |b| b.num_precomputed_generators),
+        max_device_memory_bytes: backend.and_then(|b| b.max_device_memory_bytes),
+        small_batch_threshold: backend.map_or(0, |b| b.small_batch_threshold),
+    }
+}
+
+/// Returns the device memory limit of the initialized backend, if any.
+pub(crate) fn max_device_memory_bytes() -> Option<u64> {
+    BACKEND
+        .get()
+        .and_then(|res| res.as_ref().ok())
+        .and_then(|b| b.max_device_memory_bytes)
+}
 
-            INIT_STATE = blitzar_sys::sxt_init(&config);
-        });
+/// Returns the small batch threshold of the initialized backend, or zero.
+pub(crate) fn small_batch_threshold() -> usize {
+    BACKEND
+        .get()
+        .and_then(|res| res.as_ref().ok())
+        .map_or(0, |b| b.small_batch_threshold)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        if INIT_STATE != 0 {
-            panic!("Error during backend initialization");
+    fn env<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&'static str) -> Option<String> + 'a {
+        |name| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
         }
-    };
+    }
+
+    #[test]
+    fn we_can_parse_the_backend_configuration_from_the_environment() {
+        let config = parse_config(env(&[])).unwrap();
+        assert_eq!(
+            config.num_precomputed_generators,
+            DEFAULT_NUM_PRECOMPUTED_GENERATORS
+        );
+        assert_eq!(config.max_device_memory_bytes, None);
+        assert_eq!(config.small_batch_threshold, 0);
+        let config = parse_config(env(&[
+            (NUM_PRECOMPUTED_GENERATORS_ENV, " 1024 "),
+            (MAX_DEVICE_MEMORY_BYTES_ENV, "4096"),
+            (SMALL_BATCH_THRESHOLD_ENV, "256"),
+        ]))
+        .unwrap();
+        assert_eq!(config.num_precomputed_generators, 1024);
+        assert_eq!(config.max_device_memory_bytes, Some(4096));
+        assert_eq!(config.small_batch_threshold, 256);
+    }
+
+    #[test]
+    fn we_cannot_parse_an_invalid_backend_configuration() {
+        assert_eq!(
+            parse_config(env(&[(NUM_PRECOMPUTED_GENERATORS_ENV, "many")])).err(),
+            Some(BackendError::InvalidEnvironmentVariable {
+                name: NUM_PRECOMPUTED_GENERATORS_ENV,
+                value: "many".to_string(),
+            })
+        );
+        assert!(parse_config(env(&[(NUM_PRECOMPUTED_GENERATORS_ENV, "-1")])).is_err());
+        assert_eq!(
+            parse_config(env(&[(MAX_DEVICE_MEMORY_BYTES_ENV, "1GB")])).err(),
+            Some(BackendError::InvalidEnvironmentVariable {
+                name: MAX_DEVICE_MEMORY_BYTES_ENV,
+                value: "1GB".to_string(),
+            })
+        );
+    }
 }
@@ -0,0 +1,58 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+#[cfg(feature = "bls12_381")]
+use super::CompressedG1Bls12_381;
+#[cfg(feature = "bls12_381")]
+use ark_bls12_381::G1Affine;
+#[cfg(feature = "bls12_381")]
+use ark_serialize::SerializationError;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use rayon::prelude::*;
+
+// The backend has no kernels for point compression, so these run on the host with rayon.
+
+/// Compresses `points` in parallel.
+pub fn batch_compress_ristretto(points: &[RistrettoPoint]) -> Vec<CompressedRistretto> {
+    points.par_iter().map(RistrettoPoint::compress).collect()
+}
+
+/// Decompresses `points` in parallel.
+///
+/// Returns `None` if any of the points is not a valid ristretto encoding.
+pub fn batch_decompress_ristretto(points: &[CompressedRistretto]) -> Option<Vec<RistrettoPoint>> {
+    points
+        .par_iter()
+        .map(CompressedRistretto::decompress)
+        .collect()
+}
+
+/// Compresses `bls12-381` `G1` `points` in parallel.
+#[cfg(feature = "bls12_381")]
+pub fn batch_compress_bls12_381_g1(points: &[G1Affine]) -> Vec<CompressedG1Bls12_381> {
+    points.par_iter().map(|point| (*point).into()).collect()
+}
+
+/// Decompresses `bls12-381` `G1` `points` in parallel, with the checks of
+/// [CompressedG1Bls12_381::decompress].
+///
+/// Returns an error if any of the points is invalid.
+#[cfg(feature = "bls12_381")]
+pub fn batch_decompress_bls12_381_g1(
+    points: &[CompressedG1Bls12_381],
+) -> Result<Vec<G1Affine>, SerializationError> {
+    points
+        .par_iter()
+        .map(CompressedG1Bls12_381::decompress)
+        .collect()
+}
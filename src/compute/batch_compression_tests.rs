@@ -0,0 +1,52 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+#[cfg(feature = "bls12_381")]
+use ark_bls12_381::G1Affine;
+#[cfg(feature = "bls12_381")]
+use ark_std::UniformRand;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use rand_core::OsRng;
+
+#[test]
+fn we_can_compress_and_decompress_ristretto_points_in_batches() {
+    let points: Vec<RistrettoPoint> = (0..10)
+        .map(|_| RistrettoPoint::random(&mut OsRng))
+        .collect();
+    let compressed = batch_compress_ristretto(&points);
+    assert_eq!(
+        compressed,
+        points
+            .iter()
+            .map(RistrettoPoint::compress)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(batch_decompress_ristretto(&compressed), Some(points));
+
+    let invalid = [CompressedRistretto([0xff; 32])];
+    assert_eq!(batch_decompress_ristretto(&invalid), None);
+}
+
+#[test]
+#[cfg(feature = "bls12_381")]
+fn we_can_compress_and_decompress_bls12_381_points_in_batches() {
+    let mut rng = ark_std::test_rng();
+    let points: Vec<G1Affine> = (0..10).map(|_| G1Affine::rand(&mut rng)).collect();
+    let compressed = batch_compress_bls12_381_g1(&points);
+    assert_eq!(compressed[3], CompressedG1Bls12_381::from(points[3]));
+    assert_eq!(batch_decompress_bls12_381_g1(&compressed).unwrap(), points);
+
+    let invalid = [compressed[0], CompressedG1Bls12_381([0xff; 48])];
+    assert!(batch_decompress_bls12_381_g1(&invalid).is_err());
+}
@@ -0,0 +1,33 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! commitments and MSMs on the `G1` group of bls12-381
+//!
+//! The functions of this module are the `*_bls12_381_g1_*` functions of [crate::compute]
+//! without the curve in their names.
+
+pub use super::{
+    batch_compress_bls12_381_g1 as batch_compress,
+    batch_decompress_bls12_381_g1 as batch_decompress,
+    batch_scalar_mul_bls12_381_g1 as batch_scalar_mul,
+    compute_bls12_381_g1_commitments_with_generators as compute_commitments_with_generators,
+    compute_bls12_381_g1_commitments_with_generators_uninit as compute_commitments_with_generators_uninit,
+    compute_bls12_381_g1_commitments_with_handle as compute_commitments_with_handle,
+    compute_bls12_381_g1_compressed_commitments_with_generators as compute_compressed_commitments_with_generators,
+    try_compute_bls12_381_g1_commitments_with_generators as try_compute_commitments_with_generators,
+    Bls12381G1 as Scheme, CompressedG1Bls12_381 as CompressedG1,
+};
+
+/// Handle to compute MSMs with pre-specified bls12-381 `G1` generators
+pub type MsmHandle = super::MsmHandle<super::ElementP2<ark_bls12_381::g1::Config>>;
@@ -0,0 +1,31 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! commitments and MSMs on the `G1` group of bn254
+//!
+//! The functions of this module are the `*_bn254_g1_*` functions of [crate::compute]
+//! without the curve in their names. The commitments are uncompressed affine points.
+
+pub use super::{
+    batch_scalar_mul_bn254_g1 as batch_scalar_mul,
+    compute_bn254_g1_uncompressed_commitments_with_generators as compute_commitments_with_generators,
+    compute_bn254_g1_uncompressed_commitments_with_generators_uninit as compute_commitments_with_generators_uninit,
+    compute_bn254_g1_uncompressed_commitments_with_handle as compute_commitments_with_handle,
+    rerandomize_bn254_g1_uncompressed_commitments as rerandomize_commitments,
+    try_compute_bn254_g1_uncompressed_commitments_with_generators as try_compute_commitments_with_generators,
+    Bn254G1 as Scheme,
+};
+
+/// Handle to compute MSMs with pre-specified bn254 `G1` generators
+pub type MsmHandle = super::MsmHandle<super::ElementP2<ark_bn254::g1::Config>>;
@@ -0,0 +1,224 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::commitments::update_curve25519_commitments;
+use crate::{
+    error::{Error, InvalidInputError},
+    sequence::Sequence,
+};
+use curve25519_dalek::ristretto::CompressedRistretto;
+use memmap2::Mmap;
+use std::{
+    fs::File,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Token used to stop a chunked computation from another thread.
+///
+/// Clones share the same state, so one clone can be handed to the computation while
+/// another one is kept to cancel it.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Creates a token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests the cancellation of the computations using this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true if [CancellationToken::cancel] was called on any clone of the token.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Computes the `curve25519` commitment of a single column given as raw bytes,
+/// submitting at most `chunk_num_elements` rows to the backend at a time.
+///
+/// `data` holds the column elements contiguously, each one encoded with
+/// `element_size` little-endian bytes, and is interpreted as signed values if
+/// `is_signed` is true. The result is the same as calling
+/// `compute_curve25519_commitments` with a single sequence over `data` and the same
+/// `offset_generators`: the chunk starting at row `i` is committed using the
+/// generators starting at `offset_generators + i`, and the partial commitments are
+/// added together.
+///
+/// Since `data` is only borrowed, it can be a view of a memory-mapped file
+/// (e.g. `&mmap[..]` for a `memmap2::Mmap`), in which case only the pages of the
+/// chunk being committed need to be resident.
+///
+/// # Panics
+///
+/// In the same cases as [compute_curve25519_commitment_chunked_cancellable] returns an
+/// [Error::InvalidInput] error.
+pub fn compute_curve25519_commitment_chunked(
+    data: &[u8],
+    element_size: usize,
+    is_signed: bool,
+    offset_generators: u64,
+    chunk_num_elements: usize,
+) -> CompressedRistretto {
+    compute_curve25519_commitment_chunked_with_progress(
+        data,
+        element_size,
+        is_signed,
+        offset_generators,
+        chunk_num_elements,
+        |_| {},
+    )
+}
+
+/// Progress of a chunked commitment, reported after each chunk.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ChunkProgress {
+    /// Number of chunks committed so far.
+    pub chunks_completed: usize,
+    /// Total number of chunks.
+    pub num_chunks: usize,
+    /// Number of rows committed so far.
+    pub rows_completed: usize,
+    /// Total number of rows.
+    pub num_rows: usize,
+}
+
+impl ChunkProgress {
+    /// Returns the fraction of rows committed so far, between `0` and `1`.
+    pub fn fraction(&self) -> f64 {
+        if self.num_rows == 0 {
+            return 1.0;
+        }
+        self.rows_completed as f64 / self.num_rows as f64
+    }
+}
+
+/// Same as [compute_curve25519_commitment_chunked], calling `progress` after each chunk
+/// has been committed.
+///
+/// The callback runs on the calling thread between chunk submissions, so it can report
+/// liveness or an ETA of long commitments, but it delays the next chunk for as long as
+/// it runs.
+pub fn compute_curve25519_commitment_chunked_with_progress(
+    data: &[u8],
+    element_size: usize,
+    is_signed: bool,
+    offset_generators: u64,
+    chunk_num_elements: usize,
+    progress: impl FnMut(ChunkProgress),
+) -> CompressedRistretto {
+    compute_curve25519_commitment_chunked_cancellable(
+        data,
+        element_size,
+        is_signed,
+        offset_generators,
+        chunk_num_elements,
+        &CancellationToken::new(),
+        progress,
+    )
+    .unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Same as [compute_curve25519_commitment_chunked_with_progress], stopping early once
+/// `cancel` is cancelled.
+///
+/// The token is checked before each chunk is submitted to the backend, so a chunk that
+/// is already being committed runs to completion.
+///
+/// # Errors
+///
+/// [Error::Cancelled] if the token was cancelled before the last chunk was submitted, or
+/// [Error::InvalidInput] if `chunk_num_elements` or `element_size` is zero, if
+/// `data.len()` is not a multiple of `element_size`, or if `element_size` is not
+/// supported by [Sequence].
+pub fn compute_curve25519_commitment_chunked_cancellable(
+    data: &[u8],
+    element_size: usize,
+    is_signed: bool,
+    offset_generators: u64,
+    chunk_num_elements: usize,
+    cancel: &CancellationToken,
+    mut progress: impl FnMut(ChunkProgress),
+) -> Result<CompressedRistretto, Error> {
+    if chunk_num_elements == 0 {
+        return Err(InvalidInputError::ZeroChunkSize.into());
+    }
+    Sequence::try_from_byte_slice_with_size(data, element_size, is_signed)?;
+
+    let num_rows = data.len() / element_size;
+    let num_chunks = num_rows.div_ceil(chunk_num_elements);
+    let mut commitment = [CompressedRistretto::default()];
+    for (chunk_index, chunk) in data.chunks(chunk_num_elements * element_size).enumerate() {
+        if cancel.is_cancelled() {
+            return Err(Error::Cancelled);
+        }
+        let sequence = Sequence::from_byte_slice(chunk, element_size, is_signed);
+        let chunk_offset = (chunk_index * chunk_num_elements) as u64;
+        update_curve25519_commitments(
+            &mut commitment,
+            &[sequence],
+            offset_generators + chunk_offset,
+        );
+        progress(ChunkProgress {
+            chunks_completed: chunk_index + 1,
+            num_chunks,
+            rows_completed: chunk_offset as usize + sequence.len(),
+            num_rows,
+        });
+    }
+    Ok(commitment[0])
+}
+
+/// Computes the `curve25519` commitment of a single column stored in a file.
+///
+/// The file is memory-mapped and committed with
+/// [compute_curve25519_commitment_chunked], so at most `chunk_num_elements` rows are
+/// submitted to the backend at a time and the file is never copied into an
+/// intermediate buffer.
+///
+/// # Errors
+///
+/// [Error::Io] if the file cannot be opened or mapped, or [Error::InvalidInput] in the
+/// same cases as [compute_curve25519_commitment_chunked_cancellable], with the contents
+/// of the file as `data`.
+pub fn compute_curve25519_commitment_from_file<P: AsRef<Path>>(
+    path: P,
+    element_size: usize,
+    is_signed: bool,
+    offset_generators: u64,
+    chunk_num_elements: usize,
+) -> Result<CompressedRistretto, Error> {
+    let file = File::open(path)?;
+
+    // Safety: the mapping is only read while the function runs. As with any
+    // memory-mapped file, the caller must ensure the file is not truncated
+    // or modified concurrently.
+    let mmap = unsafe { Mmap::map(&file)? };
+
+    compute_curve25519_commitment_chunked_cancellable(
+        &mmap,
+        element_size,
+        is_signed,
+        offset_generators,
+        chunk_num_elements,
+        &CancellationToken::new(),
+        |_| {},
+    )
+}
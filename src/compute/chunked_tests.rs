@@ -0,0 +1,216 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{
+    error::{Error, InvalidInputError},
+    sequence::SequenceError,
+};
+use curve25519_dalek::ristretto::CompressedRistretto;
+use tempfile::TempDir;
+
+fn as_bytes(data: &[i64]) -> Vec<u8> {
+    data.iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+#[test]
+fn chunked_commitments_match_a_single_commitment() {
+    let data: Vec<i64> = vec![2000, -7500, 5000, 1500, 0, 3, -1];
+    let bytes = as_bytes(&data);
+
+    for offset_generators in [0_u64, 5] {
+        let mut expected = vec![CompressedRistretto::default(); 1];
+        compute_curve25519_commitments(&mut expected, &[(&data).into()], offset_generators);
+
+        for chunk_num_elements in [1, 2, 3, 7, 100] {
+            let commitment = compute_curve25519_commitment_chunked(
+                &bytes,
+                8,
+                true,
+                offset_generators,
+                chunk_num_elements,
+            );
+            assert_eq!(commitment, expected[0]);
+        }
+    }
+}
+
+#[test]
+fn chunked_commitments_of_empty_data_are_the_identity() {
+    let commitment = compute_curve25519_commitment_chunked(&[], 8, false, 0, 4);
+    assert_eq!(commitment, CompressedRistretto::default());
+}
+
+#[test]
+fn we_can_compute_a_commitment_from_a_file() {
+    let data: Vec<i64> = vec![2000, -7500, 5000, 1500, 0, 3, -1];
+    let mut expected = vec![CompressedRistretto::default(); 1];
+    compute_curve25519_commitments(&mut expected, &[(&data).into()], 0);
+
+    let tmp_dir = TempDir::new().unwrap();
+    let path = tmp_dir.path().join("column");
+    std::fs::write(&path, as_bytes(&data)).unwrap();
+
+    let commitment = compute_curve25519_commitment_from_file(&path, 8, true, 0, 3).unwrap();
+    assert_eq!(commitment, expected[0]);
+}
+
+#[test]
+fn we_cannot_compute_a_commitment_from_a_file_with_a_partial_element() {
+    let tmp_dir = TempDir::new().unwrap();
+    let path = tmp_dir.path().join("column");
+    std::fs::write(&path, [1_u8; 9]).unwrap();
+
+    let err = compute_curve25519_commitment_from_file(&path, 8, false, 0, 3).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::InvalidInput(InvalidInputError::Sequence(
+            SequenceError::LengthNotMultipleOfElementSize { .. }
+        ))
+    ));
+
+    let err =
+        compute_curve25519_commitment_from_file(tmp_dir.path().join("missing"), 8, false, 0, 3)
+            .unwrap_err();
+    assert!(matches!(err, Error::Io(_)));
+}
+
+#[test]
+fn we_cannot_compute_a_chunked_commitment_with_a_zero_element_size() {
+    let tmp_dir = TempDir::new().unwrap();
+    let path = tmp_dir.path().join("column");
+    std::fs::write(&path, [1_u8; 8]).unwrap();
+    let err = compute_curve25519_commitment_from_file(&path, 0, false, 0, 3).unwrap_err();
+    assert!(matches!(err, Error::InvalidInput(_)));
+    let err = compute_curve25519_commitment_from_file(&path, 8, false, 0, 0).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::InvalidInput(InvalidInputError::ZeroChunkSize)
+    ));
+
+    let res = compute_curve25519_commitment_chunked_cancellable(
+        &[1_u8; 8],
+        0,
+        false,
+        0,
+        3,
+        &CancellationToken::new(),
+        |_| {},
+    );
+    assert!(matches!(res, Err(Error::InvalidInput(_))));
+}
+
+#[test]
+fn we_cannot_compute_a_chunked_commitment_of_a_partial_element_or_with_empty_chunks() {
+    let res = compute_curve25519_commitment_chunked_cancellable(
+        &[1_u8; 9],
+        8,
+        false,
+        0,
+        3,
+        &CancellationToken::new(),
+        |_| {},
+    );
+    assert!(matches!(
+        res,
+        Err(Error::InvalidInput(InvalidInputError::Sequence(
+            SequenceError::LengthNotMultipleOfElementSize {
+                num_bytes: 9,
+                element_size: 8
+            }
+        )))
+    ));
+
+    let res = compute_curve25519_commitment_chunked_cancellable(
+        &[1_u8; 8],
+        8,
+        false,
+        0,
+        0,
+        &CancellationToken::new(),
+        |_| {},
+    );
+    assert!(matches!(
+        res,
+        Err(Error::InvalidInput(InvalidInputError::ZeroChunkSize))
+    ));
+}
+
+#[test]
+#[should_panic(expected = "Element size must be at least 1 byte")]
+fn chunked_commitments_panic_on_a_zero_element_size() {
+    compute_curve25519_commitment_chunked(&[1_u8; 8], 0, false, 0, 3);
+}
+
+#[test]
+fn we_can_report_the_progress_of_a_chunked_commitment() {
+    let data: Vec<i64> = (0..10).map(|x| x - 4).collect();
+    let bytes = as_bytes(&data);
+
+    let mut reports = Vec::new();
+    let commitment =
+        compute_curve25519_commitment_chunked_with_progress(&bytes, 8, true, 2, 4, |progress| {
+            reports.push(progress)
+        });
+    assert_eq!(
+        commitment,
+        compute_curve25519_commitment_chunked(&bytes, 8, true, 2, 4)
+    );
+    let rows: Vec<usize> = reports.iter().map(|p| p.rows_completed).collect();
+    assert_eq!(rows, [4, 8, 10]);
+    assert!(reports
+        .iter()
+        .enumerate()
+        .all(|(i, p)| p.chunks_completed == i + 1 && p.num_chunks == 3 && p.num_rows == 10));
+    assert_eq!(reports[2].fraction(), 1.0);
+    assert_eq!(reports[0].fraction(), 0.4);
+
+    let mut num_reports = 0;
+    compute_curve25519_commitment_chunked_with_progress(&[], 8, true, 0, 4, |_| num_reports += 1);
+    assert_eq!(num_reports, 0);
+}
+
+#[test]
+fn we_can_cancel_a_chunked_commitment_between_chunks() {
+    let data: Vec<i64> = (0..10).collect();
+    let bytes = as_bytes(&data);
+
+    let cancel = CancellationToken::new();
+    let res =
+        compute_curve25519_commitment_chunked_cancellable(&bytes, 8, true, 0, 3, &cancel, |_| {});
+    assert_eq!(
+        res.unwrap(),
+        compute_curve25519_commitment_chunked(&bytes, 8, true, 0, 3)
+    );
+
+    let token = cancel.clone();
+    let mut rows = Vec::new();
+    let res = compute_curve25519_commitment_chunked_cancellable(
+        &bytes,
+        8,
+        true,
+        0,
+        3,
+        &cancel,
+        |progress| {
+            rows.push(progress.rows_completed);
+            if progress.chunks_completed == 2 {
+                token.cancel();
+            }
+        },
+    );
+    assert!(matches!(res, Err(Error::Cancelled)));
+    assert_eq!(rows, [3, 6]);
+    assert!(cancel.is_cancelled());
+}
@@ -0,0 +1,112 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::compute_curve25519_commitments;
+use crate::sequence::Sequence;
+use curve25519_dalek::ristretto::CompressedRistretto;
+use rayon::prelude::*;
+use sha2::{Digest, Sha512};
+use std::collections::HashMap;
+
+/// Content-addressed key of a commitment.
+type CacheKey = [u8; 64];
+
+/// Caches commitments by the content of the committed data.
+///
+/// Entries are keyed by a hash of the curve, the generator offset, and the sequence
+/// layout and bytes, so recommitting an unchanged column only costs hashing it. The cache
+/// is never invalidated implicitly: its lifetime is controlled by the caller, who can
+/// drop it or call [CommitmentCache::clear] at any time.
+#[derive(Clone, Debug, Default)]
+pub struct CommitmentCache {
+    curve25519: HashMap<CacheKey, CompressedRistretto>,
+}
+
+impl CommitmentCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of cached commitments.
+    pub fn len(&self) -> usize {
+        self.curve25519.len()
+    }
+
+    /// Returns `true` if the cache holds no commitments.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes every cached commitment.
+    pub fn clear(&mut self) {
+        self.curve25519.clear();
+    }
+
+    /// Same as [compute_curve25519_commitments], but reuses the cached commitments of
+    /// sequences that were already committed with the same `offset_generators`.
+    ///
+    /// The sequences missing from the cache are committed in a single backend call and
+    /// then added to the cache.
+    pub fn compute_curve25519_commitments(
+        &mut self,
+        commitments: &mut [CompressedRistretto],
+        data: &[Sequence],
+        offset_generators: u64,
+    ) {
+        let keys: Vec<CacheKey> = data
+            .par_iter()
+            .map(|sequence| cache_key(b"curve25519", sequence, offset_generators))
+            .collect();
+
+        let mut missing = Vec::new();
+        for (i, key) in keys.iter().enumerate() {
+            match self.curve25519.get(key) {
+                Some(commitment) => commitments[i] = *commitment,
+                None => missing.push(i),
+            }
+        }
+        if missing.is_empty() {
+            return;
+        }
+
+        let missing_data: Vec<Sequence> = missing.iter().map(|&i| data[i]).collect();
+        let mut missing_commitments = vec![CompressedRistretto::default(); missing.len()];
+        compute_curve25519_commitments(&mut missing_commitments, &missing_data, offset_generators);
+        for (i, commitment) in missing.into_iter().zip(missing_commitments) {
+            commitments[i] = commitment;
+            self.curve25519.insert(keys[i], commitment);
+        }
+    }
+}
+
+/// Hashes everything a commitment depends on.
+fn cache_key(curve: &[u8], sequence: &Sequence, offset_generators: u64) -> CacheKey {
+    let mut hasher = Sha512::new();
+    hasher.update((curve.len() as u64).to_le_bytes());
+    hasher.update(curve);
+    hasher.update(offset_generators.to_le_bytes());
+    hasher.update((sequence.element_size() as u64).to_le_bytes());
+    hasher.update([sequence.is_signed() as u8]);
+    hasher.update(sequence.data_slice());
+    if let Some(validity) = sequence.validity() {
+        // only the bits of actual elements are hashed
+        let len = sequence.len();
+        let mut validity = validity[..len.div_ceil(8)].to_vec();
+        if len % 8 != 0 {
+            validity[len / 8] &= (1_u8 << (len % 8)) - 1;
+        }
+        hasher.update(validity);
+    }
+    hasher.finalize().into()
+}
@@ -0,0 +1,56 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+use crate::sequence::Sequence;
+use curve25519_dalek::ristretto::CompressedRistretto;
+
+#[test]
+fn we_can_compute_commitments_through_the_cache() {
+    let data_a: Vec<u64> = vec![2, 3, 1, 5];
+    let data_b: Vec<i32> = vec![-3, 1];
+    let data_c: Vec<u8> = vec![2, 3, 1, 5];
+    let data: Vec<Sequence> = vec![(&data_a).into(), (&data_b).into(), (&data_c).into()];
+
+    let mut expected = vec![CompressedRistretto::default(); 3];
+    compute_curve25519_commitments(&mut expected, &data, 4);
+
+    let mut cache = CommitmentCache::new();
+    let mut commitments = vec![CompressedRistretto::default(); 3];
+    cache.compute_curve25519_commitments(&mut commitments[..2], &data[..2], 4);
+    assert_eq!(cache.len(), 2);
+    cache.compute_curve25519_commitments(&mut commitments, &data, 4);
+    assert_eq!(commitments, expected);
+    assert_eq!(cache.len(), 3);
+}
+
+#[test]
+fn the_cache_distinguishes_offsets_and_layouts() {
+    let data_a: Vec<u64> = vec![2, 3];
+    let data_b: Vec<i64> = vec![2, 3];
+    let mut cache = CommitmentCache::new();
+    let mut commitments = vec![CompressedRistretto::default(); 1];
+
+    cache.compute_curve25519_commitments(&mut commitments, &[(&data_a).into()], 0);
+    cache.compute_curve25519_commitments(&mut commitments, &[(&data_a).into()], 1);
+    cache.compute_curve25519_commitments(&mut commitments, &[(&data_b).into()], 0);
+    assert_eq!(cache.len(), 3);
+
+    let mut expected = vec![CompressedRistretto::default(); 1];
+    compute_curve25519_commitments(&mut expected, &[(&data_a).into()], 1);
+    cache.compute_curve25519_commitments(&mut commitments, &[(&data_a).into()], 1);
+    assert_eq!(commitments, expected);
+
+    cache.clear();
+    assert!(cache.is_empty());
+}
@@ -0,0 +1,348 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+#[cfg(feature = "bn254")]
+use super::commitments::compute_bn254_g1_uncompressed_commitments_with_generators;
+use super::commitments::compute_curve25519_commitments_with_generators;
+#[cfg(feature = "grumpkin")]
+use super::commitments::compute_grumpkin_uncompressed_commitments_with_generators;
+#[cfg(feature = "bls12_381")]
+use super::{compute_bls12_381_g1_compressed_commitments_with_generators, CompressedG1Bls12_381};
+use crate::sequence::Sequence;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+
+/// A Pedersen commitment scheme over one of the supported curves.
+///
+/// This allows downstream code to be generic over the curve, e.g.
+///
+/// ```no_run
+/// use blitzar::compute::CommitmentScheme;
+/// use blitzar::sequence::Sequence;
+///
+/// fn commit<C: CommitmentScheme>(data: &[Sequence], generators: &[C::Generator]) -> Vec<C::Output> {
+///     C::compute_commitments_with_generators(data, generators)
+/// }
+/// ```
+pub trait CommitmentScheme {
+    /// The type of the generators.
+    type Generator;
+
+    /// The type of the commitments.
+    type Output;
+
+    /// Computes the commitments of `data` with the given generators, returning one
+    /// commitment for each sequence.
+    ///
+    /// See the corresponding `compute_*_commitments_with_generators` function of each
+    /// implementation for details.
+    fn compute_commitments_with_generators(
+        data: &[Sequence],
+        generators: &[Self::Generator],
+    ) -> Vec<Self::Output>;
+
+    /// Same as [CommitmentScheme::compute_commitments_with_generators], writing the
+    /// commitments to `commitments` instead of a new vector.
+    ///
+    /// # Panics
+    ///
+    /// If `commitments.len()` is different from `data.len()`, or in the same cases as
+    /// [CommitmentScheme::compute_commitments_with_generators].
+    fn compute_commitments_with_generators_into(
+        commitments: &mut [Self::Output],
+        data: &[Sequence],
+        generators: &[Self::Generator],
+    ) {
+        assert_eq!(
+            commitments.len(),
+            data.len(),
+            "commitments must hold one commitment for each sequence"
+        );
+        commitments
+            .iter_mut()
+            .zip(Self::compute_commitments_with_generators(data, generators))
+            .for_each(|(c, commitment)| *c = commitment);
+    }
+
+    /// Same as [CommitmentScheme::compute_commitments_with_generators], with the sequences
+    /// produced by an iterator, e.g. an adapter over the columns of a table.
+    ///
+    /// The sequences are collected once, so the caller does not need to build its own
+    /// vector of sequences.
+    fn compute_commitments_with_generators_from_iter<'a>(
+        data: impl IntoIterator<Item = Sequence<'a>>,
+        generators: &[Self::Generator],
+    ) -> Vec<Self::Output> {
+        let data: Vec<Sequence> = data.into_iter().collect();
+        Self::compute_commitments_with_generators(&data, generators)
+    }
+}
+
+/// Commitments over `curve25519`, compressed as ristretto points.
+pub struct Curve25519;
+
+impl CommitmentScheme for Curve25519 {
+    type Generator = RistrettoPoint;
+    type Output = CompressedRistretto;
+
+    fn compute_commitments_with_generators(
+        data: &[Sequence],
+        generators: &[Self::Generator],
+    ) -> Vec<Self::Output> {
+        let mut commitments = vec![CompressedRistretto::default(); data.len()];
+        compute_curve25519_commitments_with_generators(&mut commitments, data, generators);
+        commitments
+    }
+
+    fn compute_commitments_with_generators_into(
+        commitments: &mut [Self::Output],
+        data: &[Sequence],
+        generators: &[Self::Generator],
+    ) {
+        compute_curve25519_commitments_with_generators(commitments, data, generators);
+    }
+}
+
+/// Commitments over `bls12-381` `G1`, compressed to 48 bytes.
+#[cfg(feature = "bls12_381")]
+pub struct Bls12381G1;
+
+#[cfg(feature = "bls12_381")]
+impl CommitmentScheme for Bls12381G1 {
+    type Generator = ark_bls12_381::G1Affine;
+    type Output = CompressedG1Bls12_381;
+
+    fn compute_commitments_with_generators(
+        data: &[Sequence],
+        generators: &[Self::Generator],
+    ) -> Vec<Self::Output> {
+        let mut commitments = vec![CompressedG1Bls12_381::default(); data.len()];
+        compute_bls12_381_g1_compressed_commitments_with_generators(
+            &mut commitments,
+            data,
+            generators,
+        );
+        commitments
+    }
+
+    fn compute_commitments_with_generators_into(
+        commitments: &mut [Self::Output],
+        data: &[Sequence],
+        generators: &[Self::Generator],
+    ) {
+        compute_bls12_381_g1_compressed_commitments_with_generators(commitments, data, generators);
+    }
+}
+
+/// Commitments over `bn254` `G1`, as uncompressed affine points.
+#[cfg(feature = "bn254")]
+pub struct Bn254G1;
+
+#[cfg(feature = "bn254")]
+impl CommitmentScheme for Bn254G1 {
+    type Generator = ark_bn254::G1Affine;
+    type Output = ark_bn254::G1Affine;
+
+    fn compute_commitments_with_generators(
+        data: &[Sequence],
+        generators: &[Self::Generator],
+    ) -> Vec<Self::Output> {
+        let mut commitments = vec![ark_bn254::G1Affine::default(); data.len()];
+        compute_bn254_g1_uncompressed_commitments_with_generators(
+            &mut commitments,
+            data,
+            generators,
+        );
+        commitments
+    }
+
+    fn compute_commitments_with_generators_into(
+        commitments: &mut [Self::Output],
+        data: &[Sequence],
+        generators: &[Self::Generator],
+    ) {
+        compute_bn254_g1_uncompressed_commitments_with_generators(commitments, data, generators);
+    }
+}
+
+/// Commitments over `grumpkin`, as uncompressed affine points.
+#[cfg(feature = "grumpkin")]
+pub struct Grumpkin;
+
+#[cfg(feature = "grumpkin")]
+impl CommitmentScheme for Grumpkin {
+    type Generator = ark_grumpkin::Affine;
+    type Output = ark_grumpkin::Affine;
+
+    fn compute_commitments_with_generators(
+        data: &[Sequence],
+        generators: &[Self::Generator],
+    ) -> Vec<Self::Output> {
+        let mut commitments = vec![ark_grumpkin::Affine::default(); data.len()];
+        compute_grumpkin_uncompressed_commitments_with_generators(
+            &mut commitments,
+            data,
+            generators,
+        );
+        commitments
+    }
+
+    fn compute_commitments_with_generators_into(
+        commitments: &mut [Self::Output],
+        data: &[Sequence],
+        generators: &[Self::Generator],
+    ) {
+        compute_grumpkin_uncompressed_commitments_with_generators(commitments, data, generators);
+    }
+}
+
+/// Computes the Pedersen commitments of `data` with the given generators, using the
+/// commitment scheme `C`.
+///
+/// This covers the compressed and uncompressed encodings of every curve with one generic
+/// function, and panics in the same cases as the `compute_*_commitments_with_generators`
+/// function of `C`.
+///
+/// # Example
+///
+/// ```no_run
+/// use blitzar::compute::{compute_commitments_with_generators, CommitmentScheme};
+/// use blitzar::sequence::Sequence;
+///
+/// fn commit<C: CommitmentScheme>(
+///     commitments: &mut [C::Output],
+///     data: &[Sequence],
+///     generators: &[C::Generator],
+/// ) {
+///     compute_commitments_with_generators::<C>(commitments, data, generators);
+/// }
+/// ```
+pub fn compute_commitments_with_generators<C: CommitmentScheme>(
+    commitments: &mut [C::Output],
+    data: &[Sequence],
+    generators: &[C::Generator],
+) {
+    C::compute_commitments_with_generators_into(commitments, data, generators);
+}
+
+#[cfg(all(test, feature = "bls12_381", feature = "bn254", feature = "grumpkin"))]
+mod tests {
+    use super::*;
+    use crate::reference;
+    use ark_std::UniformRand;
+
+    fn commit<C: CommitmentScheme>(
+        data: &[Sequence],
+        generators: &[C::Generator],
+    ) -> Vec<C::Output> {
+        C::compute_commitments_with_generators(data, generators)
+    }
+
+    #[test]
+    fn we_can_compute_commitments_generically_over_the_curve() {
+        let data_a: Vec<u64> = vec![2, 3, 1, 5];
+        let data_b: Vec<i32> = vec![-3, 1];
+        let data: Vec<Sequence> = vec![(&data_a).into(), (&data_b).into()];
+        let mut rng = ark_std::test_rng();
+
+        let generators: Vec<RistrettoPoint> = (0..4)
+            .map(|_| RistrettoPoint::random(&mut rand_core::OsRng))
+            .collect();
+        let mut expected = vec![CompressedRistretto::default(); 2];
+        reference::compute_curve25519_commitments_with_generators(
+            &mut expected,
+            &data,
+            &generators,
+        );
+        assert_eq!(commit::<Curve25519>(&data, &generators), expected);
+
+        let generators: Vec<ark_bls12_381::G1Affine> = (0..4)
+            .map(|_| ark_bls12_381::G1Affine::rand(&mut rng))
+            .collect();
+        let mut expected = vec![[0_u8; 48]; 2];
+        reference::compute_bls12_381_g1_commitments_with_generators(
+            &mut expected,
+            &data,
+            &generators,
+        );
+        let expected: Vec<CompressedG1Bls12_381> = expected.into_iter().map(Into::into).collect();
+        assert_eq!(commit::<Bls12381G1>(&data, &generators), expected);
+
+        let generators: Vec<ark_bn254::G1Affine> = (0..4)
+            .map(|_| ark_bn254::G1Affine::rand(&mut rng))
+            .collect();
+        let mut expected = vec![ark_bn254::G1Affine::default(); 2];
+        reference::compute_bn254_g1_uncompressed_commitments_with_generators(
+            &mut expected,
+            &data,
+            &generators,
+        );
+        assert_eq!(commit::<Bn254G1>(&data, &generators), expected);
+
+        let generators: Vec<ark_grumpkin::Affine> = (0..4)
+            .map(|_| ark_grumpkin::Affine::rand(&mut rng))
+            .collect();
+        let mut expected = vec![ark_grumpkin::Affine::default(); 2];
+        reference::compute_grumpkin_uncompressed_commitments_with_generators(
+            &mut expected,
+            &data,
+            &generators,
+        );
+        assert_eq!(commit::<Grumpkin>(&data, &generators), expected);
+    }
+
+    #[test]
+    fn we_can_compute_commitments_of_an_iterator_of_sequences() {
+        let columns: Vec<Vec<u32>> = vec![vec![1, 2, 3], vec![4], vec![]];
+        let data: Vec<Sequence> = columns.iter().map(Sequence::from).collect();
+        let mut rng = ark_std::test_rng();
+        let generators: Vec<ark_bn254::G1Affine> = (0..3)
+            .map(|_| ark_bn254::G1Affine::rand(&mut rng))
+            .collect();
+
+        assert_eq!(
+            Bn254G1::compute_commitments_with_generators_from_iter(
+                columns.iter().map(Sequence::from),
+                &generators
+            ),
+            Bn254G1::compute_commitments_with_generators(&data, &generators)
+        );
+    }
+
+    #[test]
+    fn we_can_compute_commitments_into_a_slice_generically_over_the_curve() {
+        let data_a: Vec<u64> = vec![2, 3, 1];
+        let data_b: Vec<i16> = vec![-3, 1];
+        let data = [Sequence::from(&data_a), Sequence::from(&data_b)];
+        let mut rng = ark_std::test_rng();
+
+        let generators: Vec<ark_bls12_381::G1Affine> = (0..3)
+            .map(|_| ark_bls12_381::G1Affine::rand(&mut rng))
+            .collect();
+        let mut commitments = [CompressedG1Bls12_381::default(); 2];
+        compute_commitments_with_generators::<Bls12381G1>(&mut commitments, &data, &generators);
+        assert_eq!(
+            commitments.to_vec(),
+            Bls12381G1::compute_commitments_with_generators(&data, &generators)
+        );
+
+        let generators: Vec<RistrettoPoint> = (0..3)
+            .map(|_| RistrettoPoint::random(&mut rand_core::OsRng))
+            .collect();
+        let mut commitments = [CompressedRistretto::default(); 2];
+        compute_commitments_with_generators::<Curve25519>(&mut commitments, &data, &generators);
+        assert_eq!(
+            commitments.to_vec(),
+            Curve25519::compute_commitments_with_generators(&data, &generators)
+        );
+    }
+}
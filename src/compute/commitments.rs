@@ -12,12 +12,43 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::backend::init_backend;
-use crate::sequence::Sequence;
+use super::{
+    backend::{ensure_initialized, init_backend, max_device_memory_bytes, small_batch_threshold},
+    delta_update::get_row_generators,
+    memory_limit::{row_chunks, rows_per_chunk},
+    packing::pack_sequences,
+    validity::NullCorrection,
+    CurveId, MsmHandle,
+};
+#[cfg(any(feature = "bls12_381", feature = "bn254", feature = "grumpkin"))]
+use super::{error::BackendError, ElementP2};
+use crate::{
+    error::{
+        backend_len, check_generators, check_length, try_backend_len, Error, InvalidInputError,
+    },
+    reference,
+    sequence::Sequence,
+};
+#[cfg(feature = "bls12_381")]
 use ark_bls12_381::G1Affine;
+#[cfg(feature = "bn254")]
 use ark_bn254::G1Affine as bn254_g1_affine;
+#[cfg(any(feature = "bls12_381", feature = "bn254", feature = "grumpkin"))]
+use ark_ec::short_weierstrass::{Affine, Projective, SWCurveConfig};
+#[cfg(any(feature = "bls12_381", feature = "bn254", feature = "grumpkin"))]
+use ark_ec::CurveGroup;
+#[cfg(feature = "bn254")]
+use ark_ff::PrimeField;
+#[cfg(feature = "grumpkin")]
 use ark_grumpkin::Affine as grumpkin_affine;
-use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+#[cfg(feature = "bls12_381")]
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+};
+use rayon::prelude::*;
+use std::mem::MaybeUninit;
 
 #[doc = include_str!("../../docs/commitments/compute_curve25519_commitments.md")]
 ///
@@ -40,8 +71,114 @@ pub fn compute_curve25519_commitments(
     data: &[Sequence],
     offset_generators: u64,
 ) {
+    // SAFETY: the uninit variant only writes initialized commitments
+    let commitments = unsafe { as_uninit_mut(&mut commitments[..data.len()]) };
+    compute_curve25519_commitments_uninit(commitments, data, offset_generators);
+}
+
+/// Computes the Pedersen commitments of `data` into uninitialized memory.
+///
+/// This is the same as [compute_curve25519_commitments], except that `commitments`
+/// does not need to be initialized beforehand, which avoids zeroing large output
+/// buffers. Every element of `commitments` is initialized on return.
+///
+/// # Panics
+///
+/// If `commitments.len()` is different from `data.len()`, or in the same cases as
+/// [compute_curve25519_commitments].
+pub fn compute_curve25519_commitments_uninit<'a>(
+    commitments: &'a mut [MaybeUninit<CompressedRistretto>],
+    data: &[Sequence],
+    offset_generators: u64,
+) -> &'a mut [CompressedRistretto] {
+    assert_eq!(
+        commitments.len(),
+        data.len(),
+        "commitments must hold one element for each sequence"
+    );
+    if fill_identity_if_empty(commitments, data, CompressedRistretto::default()) {
+        return unsafe { assume_init_mut(commitments) };
+    }
     init_backend();
 
+    if let Some(num_rows) = rows_per_chunk(data, 32, 32, max_device_memory_bytes())
+        .unwrap_or_else(|err| panic!("{err}"))
+    {
+        let commitments = fill(commitments, CompressedRistretto::default());
+        compute_curve25519_commitments_in_chunks(
+            commitments,
+            data,
+            num_rows,
+            |partial, chunk, start| unsafe {
+                compute_curve25519_commitments_impl(
+                    // SAFETY: the backend only writes initialized commitments
+                    as_uninit_mut(partial),
+                    chunk,
+                    offset_generators + start as u64,
+                )
+            },
+        );
+        return commitments;
+    }
+
+    unsafe {
+        compute_curve25519_commitments_impl(commitments, data, offset_generators);
+    }
+
+    // SAFETY: the backend writes one commitment for each sequence
+    unsafe { assume_init_mut(commitments) }
+}
+
+/// Computes the Pedersen commitments of the sequences produced by `data`.
+///
+/// This is the same as [compute_curve25519_commitments], except that the sequences can
+/// be produced lazily, e.g. by an adapter over the columns of a table, and that the
+/// commitments are returned. The sequences are collected once before calling the backend.
+///
+/// # Panics
+///
+/// In the same cases as [compute_curve25519_commitments].
+pub fn compute_curve25519_commitments_from_iter<'a>(
+    data: impl IntoIterator<Item = Sequence<'a>>,
+    offset_generators: u64,
+) -> Vec<CompressedRistretto> {
+    let data: Vec<Sequence> = data.into_iter().collect();
+    let mut commitments = vec![CompressedRistretto::default(); data.len()];
+    compute_curve25519_commitments(&mut commitments, &data, offset_generators);
+    commitments
+}
+
+/// Same as [compute_curve25519_commitments], without checking that the backend is initialized.
+///
+/// This skips the initialization check done by every call to the checked function,
+/// which can matter in tight loops over tiny inputs.
+///
+/// # Safety
+///
+/// The backend must have been initialized successfully, e.g. with [init_backend] or
+/// [crate::compute::ensure_initialized].
+pub unsafe fn compute_curve25519_commitments_unchecked(
+    commitments: &mut [CompressedRistretto],
+    data: &[Sequence],
+    offset_generators: u64,
+) {
+    // SAFETY: the identity fill and the backend only write initialized commitments
+    let commitments = as_uninit_mut(&mut commitments[..data.len()]);
+    if !fill_identity_if_empty(commitments, data, CompressedRistretto::default()) {
+        compute_curve25519_commitments_impl(commitments, data, offset_generators);
+    }
+}
+
+/// Computes the commitments without initializing the backend.
+///
+/// # Safety
+///
+/// The backend must be initialized.
+unsafe fn compute_curve25519_commitments_impl(
+    commitments: &mut [MaybeUninit<CompressedRistretto>],
+    data: &[Sequence],
+    offset_generators: u64,
+) {
     let sxt_descriptors: Vec<blitzar_sys::sxt_sequence_descriptor> =
         data.iter().map(Into::into).collect();
 
@@ -51,11 +188,136 @@ pub fn compute_curve25519_commitments(
     unsafe {
         blitzar_sys::sxt_curve25519_compute_pedersen_commitments(
             sxt_ristretto255_compressed,
-            sxt_descriptors.len() as u32,
+            backend_len("data", sxt_descriptors.len()),
             sxt_descriptors.as_ptr(),
             offset_generators,
         );
     }
+
+    if let Some(correction) = NullCorrection::new(data) {
+        let generators = get_row_generators(&correction.rows, offset_generators);
+        let mut nulls = vec![CompressedRistretto::default(); data.len()];
+        compute_curve25519_commitments_with_generators_impl(
+            // SAFETY: the backend only writes initialized commitments
+            as_uninit_mut(&mut nulls),
+            &correction.sequences(),
+            &generators,
+        );
+        subtract_curve25519_commitments(assume_init_mut(commitments), &nulls);
+    }
+}
+
+/// Checks the inputs of a commitment computation without calling the backend.
+///
+/// Returns the error that the fallible functions report for `num_commitments` outputs,
+/// the sequences `data` and `num_generators` generators, so that untrusted input can be
+/// rejected before calling one of the panicking functions. Pass `None` as
+/// `num_generators` for the functions that derive their generators from an offset.
+///
+/// # Example
+///
+/// ```
+/// use blitzar::{compute::check_inputs, sequence::Sequence};
+///
+/// let data: Vec<u32> = vec![1, 2, 3];
+/// let sequences = [Sequence::from(&data)];
+/// assert!(check_inputs(1, &sequences, Some(3)).is_ok());
+/// assert!(check_inputs(1, &sequences, Some(2)).is_err());
+/// ```
+pub fn check_inputs(
+    num_commitments: usize,
+    data: &[Sequence],
+    num_generators: Option<usize>,
+) -> Result<(), InvalidInputError> {
+    check_length("commitments", data.len(), num_commitments)?;
+    try_backend_len("data", data.len())?;
+    crate::sequence::validate(data)?;
+    match num_generators {
+        Some(num_generators) => check_generators(data, num_generators),
+        None => Ok(()),
+    }
+}
+
+/// Computes the commitments of `data` in chunks of `num_rows` rows and adds them up.
+///
+/// `compute` is called with the commitments to fill, the sequences of a chunk and the
+/// index of its first row.
+fn compute_curve25519_commitments_in_chunks(
+    commitments: &mut [CompressedRistretto],
+    data: &[Sequence],
+    num_rows: usize,
+    mut compute: impl FnMut(&mut [CompressedRistretto], &[Sequence], usize),
+) {
+    let mut sums = vec![RistrettoPoint::default(); data.len()];
+    let mut partial = vec![CompressedRistretto::default(); data.len()];
+    for (start, chunk) in row_chunks(data, num_rows) {
+        compute(&mut partial, &chunk, start);
+        sums.iter_mut().zip(&partial).for_each(|(sum, c)| {
+            *sum += c
+                .decompress()
+                .expect("the backend returns valid ristretto points")
+        });
+    }
+    commitments
+        .iter_mut()
+        .zip(sums)
+        .for_each(|(c, sum)| *c = sum.compress());
+}
+
+/// Computes the short Weierstrass commitments of `data` in chunks of `num_rows` rows and
+/// adds them up.
+///
+/// `compute` is called with the commitments to fill, the sequences of a chunk and the
+/// index of its first row.
+#[cfg(any(feature = "bls12_381", feature = "bn254", feature = "grumpkin"))]
+fn compute_sw_commitments_in_chunks<C: SWCurveConfig>(
+    commitments: &mut [Affine<C>],
+    data: &[Sequence],
+    num_rows: usize,
+    mut compute: impl FnMut(&mut [Affine<C>], &[Sequence], usize),
+) {
+    let mut sums = vec![Projective::<C>::default(); data.len()];
+    let mut partial = vec![Affine::<C>::identity(); data.len()];
+    for (start, chunk) in row_chunks(data, num_rows) {
+        compute(&mut partial, &chunk, start);
+        sums.iter_mut().zip(&partial).for_each(|(sum, c)| *sum += c);
+    }
+    commitments.copy_from_slice(&Projective::normalize_batch(&sums));
+}
+
+/// Fallible version of [compute_curve25519_commitments].
+///
+/// Returns an error instead of panicking if `commitments.len()` is different from
+/// `data.len()`, if a sequence is invalid, or if the backend cannot be initialized.
+///
+/// Computations exceeding [BackendConfig::max_device_memory_bytes](super::BackendConfig::max_device_memory_bytes),
+/// or with sequences longer than `u32::MAX` rows, are split into chunks of rows, and an
+/// error is returned if a chunk still exceeds the memory limit.
+pub fn try_compute_curve25519_commitments(
+    commitments: &mut [CompressedRistretto],
+    data: &[Sequence],
+    offset_generators: u64,
+) -> Result<(), Error> {
+    check_inputs(commitments.len(), data, None)?;
+    ensure_initialized()?;
+    match rows_per_chunk(data, 32, 32, max_device_memory_bytes())? {
+        None => unsafe {
+            compute_curve25519_commitments_unchecked(commitments, data, offset_generators)
+        },
+        Some(num_rows) => compute_curve25519_commitments_in_chunks(
+            commitments,
+            data,
+            num_rows,
+            |partial, chunk, start| unsafe {
+                compute_curve25519_commitments_unchecked(
+                    partial,
+                    chunk,
+                    offset_generators + start as u64,
+                )
+            },
+        ),
+    }
+    Ok(())
 }
 
 #[doc = include_str!("../../docs/commitments/compute_curve25519_commitments_with_generators.md")]
@@ -74,18 +336,107 @@ pub fn compute_curve25519_commitments_with_generators(
     data: &[Sequence],
     generators: &[RistrettoPoint],
 ) {
+    // SAFETY: the uninit variant only writes initialized commitments
+    let commitments = unsafe { as_uninit_mut(&mut commitments[..data.len()]) };
+    compute_curve25519_commitments_with_generators_uninit(commitments, data, generators);
+}
+
+/// Computes the Pedersen commitments of `data` with the given generators into
+/// uninitialized memory.
+///
+/// This is the same as [compute_curve25519_commitments_with_generators], except that
+/// `commitments` does not need to be initialized beforehand. Every element of
+/// `commitments` is initialized on return.
+///
+/// # Panics
+///
+/// If `commitments.len()` is different from `data.len()`, or in the same cases as
+/// [compute_curve25519_commitments_with_generators].
+pub fn compute_curve25519_commitments_with_generators_uninit<'a>(
+    commitments: &'a mut [MaybeUninit<CompressedRistretto>],
+    data: &[Sequence],
+    generators: &[RistrettoPoint],
+) -> &'a mut [CompressedRistretto] {
+    assert_eq!(
+        commitments.len(),
+        data.len(),
+        "commitments must hold one element for each sequence"
+    );
+    if fill_identity_if_empty(commitments, data, CompressedRistretto::default()) {
+        return unsafe { assume_init_mut(commitments) };
+    }
     init_backend();
 
-    let sxt_descriptors: Vec<blitzar_sys::sxt_sequence_descriptor> = data
-        .iter()
-        .map(|s| {
-            assert!(
-                s.len() <= generators.len(),
-                "generators has a length smaller than the longest sequence in the input data"
-            );
-            s.into()
-        })
-        .collect();
+    if is_small_batch(data) {
+        check_generators(data, generators.len()).unwrap_or_else(|err| panic!("{err}"));
+        let commitments = fill(commitments, CompressedRistretto::default());
+        reference::compute_curve25519_commitments_with_generators(commitments, data, generators);
+        return commitments;
+    }
+
+    if let Some(num_rows) = rows_per_chunk(data, 32, 32, max_device_memory_bytes())
+        .unwrap_or_else(|err| panic!("{err}"))
+    {
+        check_generators(data, generators.len()).unwrap_or_else(|err| panic!("{err}"));
+        let commitments = fill(commitments, CompressedRistretto::default());
+        compute_curve25519_commitments_in_chunks(
+            commitments,
+            data,
+            num_rows,
+            |partial, chunk, start| unsafe {
+                compute_curve25519_commitments_with_generators_impl(
+                    // SAFETY: the backend only writes initialized commitments
+                    as_uninit_mut(partial),
+                    chunk,
+                    &generators[start..],
+                )
+            },
+        );
+        return commitments;
+    }
+
+    unsafe {
+        compute_curve25519_commitments_with_generators_impl(commitments, data, generators);
+    }
+
+    // SAFETY: the backend writes one commitment for each sequence
+    unsafe { assume_init_mut(commitments) }
+}
+
+/// Same as [compute_curve25519_commitments_with_generators], without checking that the backend is initialized.
+///
+/// This skips the initialization check done by every call to the checked function,
+/// which can matter in tight loops over tiny inputs.
+///
+/// # Safety
+///
+/// The backend must have been initialized successfully, e.g. with [init_backend] or
+/// [crate::compute::ensure_initialized].
+pub unsafe fn compute_curve25519_commitments_with_generators_unchecked(
+    commitments: &mut [CompressedRistretto],
+    data: &[Sequence],
+    generators: &[RistrettoPoint],
+) {
+    // SAFETY: the identity fill and the backend only write initialized commitments
+    let commitments = as_uninit_mut(&mut commitments[..data.len()]);
+    if !fill_identity_if_empty(commitments, data, CompressedRistretto::default()) {
+        compute_curve25519_commitments_with_generators_impl(commitments, data, generators);
+    }
+}
+
+/// Computes the commitments without initializing the backend.
+///
+/// # Safety
+///
+/// The backend must be initialized.
+unsafe fn compute_curve25519_commitments_with_generators_impl(
+    commitments: &mut [MaybeUninit<CompressedRistretto>],
+    data: &[Sequence],
+    generators: &[RistrettoPoint],
+) {
+    check_generators(data, generators.len()).unwrap_or_else(|err| panic!("{err}"));
+    let sxt_descriptors: Vec<blitzar_sys::sxt_sequence_descriptor> =
+        data.iter().map(|s| s.into()).collect();
 
     let sxt_ristretto_generators = generators.as_ptr() as *const blitzar_sys::sxt_ristretto255;
 
@@ -95,11 +446,58 @@ pub fn compute_curve25519_commitments_with_generators(
     unsafe {
         blitzar_sys::sxt_curve25519_compute_pedersen_commitments_with_generators(
             sxt_ristretto255_compressed,
-            sxt_descriptors.len() as u32,
+            backend_len("data", sxt_descriptors.len()),
             sxt_descriptors.as_ptr(),
             sxt_ristretto_generators,
         );
     }
+
+    if let Some(correction) = NullCorrection::new(data) {
+        let mut nulls = vec![CompressedRistretto::default(); data.len()];
+        compute_curve25519_commitments_with_generators_impl(
+            // SAFETY: the backend only writes initialized commitments
+            as_uninit_mut(&mut nulls),
+            &correction.sequences(),
+            &correction.gather(generators),
+        );
+        subtract_curve25519_commitments(assume_init_mut(commitments), &nulls);
+    }
+}
+
+/// Fallible version of [compute_curve25519_commitments_with_generators].
+///
+/// Returns an error instead of panicking if `commitments.len()` is different from
+/// `data.len()`, if a sequence is invalid or longer than `generators`, or if the backend
+/// cannot be initialized.
+///
+/// Computations exceeding [BackendConfig::max_device_memory_bytes](super::BackendConfig::max_device_memory_bytes),
+/// or with sequences longer than `u32::MAX` rows, are split into chunks of rows, and an
+/// error is returned if a chunk still exceeds the memory limit.
+pub fn try_compute_curve25519_commitments_with_generators(
+    commitments: &mut [CompressedRistretto],
+    data: &[Sequence],
+    generators: &[RistrettoPoint],
+) -> Result<(), Error> {
+    check_inputs(commitments.len(), data, Some(generators.len()))?;
+    ensure_initialized()?;
+    match rows_per_chunk(data, 32, 32, max_device_memory_bytes())? {
+        None => unsafe {
+            compute_curve25519_commitments_with_generators_unchecked(commitments, data, generators)
+        },
+        Some(num_rows) => compute_curve25519_commitments_in_chunks(
+            commitments,
+            data,
+            num_rows,
+            |partial, chunk, start| unsafe {
+                compute_curve25519_commitments_with_generators_unchecked(
+                    partial,
+                    chunk,
+                    &generators[start..],
+                )
+            },
+        ),
+    }
+    Ok(())
 }
 
 #[doc = include_str!("../../docs/commitments/compute_bls12_381_g1_commitments_with_generators.md")]
@@ -108,77 +506,334 @@ pub fn compute_curve25519_commitments_with_generators(
 ///```no_run
 #[doc = include_str!("../../examples/pass_bls12_381_g1_generators_to_commitment.rs")]
 ///```
+#[cfg(feature = "bls12_381")]
 pub fn compute_bls12_381_g1_commitments_with_generators(
     commitments: &mut [[u8; 48]],
     data: &[Sequence],
     generators: &[G1Affine],
 ) {
+    // SAFETY: the uninit variant only writes initialized commitments
+    let commitments = unsafe { as_uninit_mut(&mut commitments[..data.len()]) };
+    compute_bls12_381_g1_commitments_with_generators_uninit(commitments, data, generators);
+}
+
+/// Computes the `bls12-381` `G1` commitments of `data` into uninitialized memory.
+///
+/// This is the same as [compute_bls12_381_g1_commitments_with_generators], except that
+/// `commitments` does not need to be initialized beforehand. Every element of
+/// `commitments` is initialized on return.
+///
+/// # Panics
+///
+/// If `commitments.len()` is different from `data.len()`, or in the same cases as
+/// [compute_bls12_381_g1_commitments_with_generators].
+#[cfg(feature = "bls12_381")]
+pub fn compute_bls12_381_g1_commitments_with_generators_uninit<'a>(
+    commitments: &'a mut [MaybeUninit<[u8; 48]>],
+    data: &[Sequence],
+    generators: &[G1Affine],
+) -> &'a mut [[u8; 48]] {
+    assert_eq!(
+        commitments.len(),
+        data.len(),
+        "commitments must hold one element for each sequence"
+    );
+    if fill_identity_if_empty(commitments, data, bls12_381_g1_compressed_identity()) {
+        return unsafe { assume_init_mut(commitments) };
+    }
     init_backend();
 
-    let sxt_descriptors: Vec<blitzar_sys::sxt_sequence_descriptor> = data
-        .iter()
-        .map(|s| {
-            assert!(
-                s.len() <= generators.len(),
-                "generators has a length smaller than the longest sequence in the input data"
+    check_generators(data, generators.len()).unwrap_or_else(|err| panic!("{err}"));
+    if is_small_batch(data) {
+        let commitments = fill(commitments, bls12_381_g1_compressed_identity());
+        reference::compute_bls12_381_g1_commitments_with_generators(commitments, data, generators);
+        return commitments;
+    }
+    let num_rows = bls12_381_g1_rows_per_chunk(data).unwrap_or_else(|err| panic!("{err}"));
+    unsafe {
+        compute_bls12_381_g1_commitments_with_generators_impl(
+            commitments,
+            data,
+            generators,
+            num_rows,
+        );
+    }
+
+    // SAFETY: the backend writes one commitment for each sequence
+    unsafe { assume_init_mut(commitments) }
+}
+
+/// Returns the number of rows of the chunks of a `bls12-381` `G1` commitment
+/// computation, as given by [rows_per_chunk].
+#[cfg(feature = "bls12_381")]
+fn bls12_381_g1_rows_per_chunk(data: &[Sequence]) -> Result<Option<usize>, BackendError> {
+    rows_per_chunk(
+        data,
+        std::mem::size_of::<blitzar_sys::sxt_bls12_381_g1>(),
+        std::mem::size_of::<blitzar_sys::sxt_bls12_381_g1_compressed>(),
+        max_device_memory_bytes(),
+    )
+}
+
+/// Computes the commitments without initializing the backend, in chunks of `num_rows`
+/// rows if given.
+///
+/// # Safety
+///
+/// The backend must be initialized.
+#[cfg(feature = "bls12_381")]
+pub(crate) unsafe fn compute_bls12_381_g1_commitments_with_generators_impl(
+    commitments: &mut [MaybeUninit<[u8; 48]>],
+    data: &[Sequence],
+    generators: &[G1Affine],
+    num_rows: Option<usize>,
+) {
+    if let Some(num_rows) = num_rows {
+        let mut points = vec![G1Affine::identity(); data.len()];
+        compute_sw_commitments_in_chunks(&mut points, data, num_rows, |partial, chunk, start| {
+            let mut bytes = vec![[0_u8; 48]; chunk.len()];
+            compute_bls12_381_g1_commitments_with_generators_impl(
+                // SAFETY: the backend only writes initialized commitments
+                as_uninit_mut(&mut bytes),
+                chunk,
+                &generators[start..],
+                None,
             );
-            s.into()
-        })
-        .collect();
+            partial
+                .par_iter_mut()
+                .zip(bytes)
+                .for_each(|(point, bytes)| {
+                    *point = G1Affine::deserialize_compressed_unchecked(&bytes[..])
+                        .expect("the backend returns valid points")
+                });
+        });
+        commitments
+            .par_iter_mut()
+            .zip(points)
+            .for_each(|(commitment, point)| {
+                let mut bytes = [0_u8; 48];
+                point
+                    .serialize_compressed(&mut bytes[..])
+                    .expect("a compressed bls12-381 G1 point is 48 bytes");
+                commitment.write(bytes);
+            });
+        return;
+    }
+
+    let sxt_descriptors: Vec<blitzar_sys::sxt_sequence_descriptor> =
+        data.iter().map(|s| s.into()).collect();
 
     let sxt_bls12_381_g1_generators = generators.as_ptr() as *const blitzar_sys::sxt_bls12_381_g1;
 
     let sxt_bls12_381_g1_compressed =
         commitments.as_mut_ptr() as *mut blitzar_sys::sxt_bls12_381_g1_compressed;
 
-    unsafe {
-        blitzar_sys::sxt_bls12_381_g1_compute_pedersen_commitments_with_generators(
-            sxt_bls12_381_g1_compressed,
-            sxt_descriptors.len() as u32,
-            sxt_descriptors.as_ptr(),
-            sxt_bls12_381_g1_generators,
+    blitzar_sys::sxt_bls12_381_g1_compute_pedersen_commitments_with_generators(
+        sxt_bls12_381_g1_compressed,
+        backend_len("data", sxt_descriptors.len()),
+        sxt_descriptors.as_ptr(),
+        sxt_bls12_381_g1_generators,
+    );
+
+    if let Some(correction) = NullCorrection::new(data) {
+        let commitments = assume_init_mut(commitments);
+        let mut nulls = vec![[0_u8; 48]; data.len()];
+        compute_bls12_381_g1_commitments_with_generators_impl(
+            // SAFETY: the backend only writes initialized commitments
+            as_uninit_mut(&mut nulls),
+            &correction.sequences(),
+            &correction.gather(generators),
+            None,
         );
+        commitments
+            .par_iter_mut()
+            .zip(nulls)
+            .for_each(|(commitment, null)| {
+                let point = G1Affine::deserialize_compressed_unchecked(&commitment[..])
+                    .expect("the backend returns valid points")
+                    - G1Affine::deserialize_compressed_unchecked(&null[..])
+                        .expect("the backend returns valid points");
+                point
+                    .into_affine()
+                    .serialize_compressed(&mut commitment[..])
+                    .expect("a compressed bls12-381 G1 point is 48 bytes")
+            });
     }
 }
 
+/// Fallible version of [compute_bls12_381_g1_commitments_with_generators].
+///
+/// Returns an error instead of panicking if `commitments.len()` is different from
+/// `data.len()`, if a sequence is invalid or longer than `generators`, or if the backend
+/// cannot be initialized.
+///
+/// Computations exceeding [BackendConfig::max_device_memory_bytes](super::BackendConfig::max_device_memory_bytes),
+/// or with sequences longer than `u32::MAX` rows, are split into chunks of rows, and an
+/// error is returned if a chunk still exceeds the memory limit.
+#[cfg(feature = "bls12_381")]
+pub fn try_compute_bls12_381_g1_commitments_with_generators(
+    commitments: &mut [[u8; 48]],
+    data: &[Sequence],
+    generators: &[G1Affine],
+) -> Result<(), Error> {
+    check_inputs(commitments.len(), data, Some(generators.len()))?;
+    ensure_initialized()?;
+    bls12_381_g1_rows_per_chunk(data)?;
+    compute_bls12_381_g1_commitments_with_generators(commitments, data, generators);
+    Ok(())
+}
+
 #[doc = include_str!("../../docs/commitments/compute_bn254_g1_commitments_with_generators.md")]
 ///
 /// # Example - Pass generators to Commitment Computation
 ///```no_run
 #[doc = include_str!("../../examples/pass_bn254_g1_generators_to_commitment.rs")]
 ///```
+#[cfg(feature = "bn254")]
 pub fn compute_bn254_g1_uncompressed_commitments_with_generators(
     commitments: &mut [bn254_g1_affine],
     data: &[Sequence],
     generators: &[bn254_g1_affine],
 ) {
+    // SAFETY: the uninit variant only writes initialized commitments
+    let commitments = unsafe { as_uninit_mut(&mut commitments[..data.len()]) };
+    compute_bn254_g1_uncompressed_commitments_with_generators_uninit(commitments, data, generators);
+}
+
+/// Computes the `bn254` `G1` commitments of `data` into uninitialized memory.
+///
+/// This is the same as [compute_bn254_g1_uncompressed_commitments_with_generators],
+/// except that `commitments` does not need to be initialized beforehand. Every element
+/// of `commitments` is initialized on return.
+///
+/// # Panics
+///
+/// If `commitments.len()` is different from `data.len()`, or in the same cases as
+/// [compute_bn254_g1_uncompressed_commitments_with_generators].
+#[cfg(feature = "bn254")]
+pub fn compute_bn254_g1_uncompressed_commitments_with_generators_uninit<'a>(
+    commitments: &'a mut [MaybeUninit<bn254_g1_affine>],
+    data: &[Sequence],
+    generators: &[bn254_g1_affine],
+) -> &'a mut [bn254_g1_affine] {
+    assert_eq!(
+        commitments.len(),
+        data.len(),
+        "commitments must hold one element for each sequence"
+    );
+    if fill_identity_if_empty(commitments, data, bn254_g1_affine::identity()) {
+        return unsafe { assume_init_mut(commitments) };
+    }
     init_backend();
 
-    let sxt_descriptors: Vec<blitzar_sys::sxt_sequence_descriptor> = data
-        .iter()
-        .map(|s| {
-            assert!(
-                s.len() <= generators.len(),
-                "generators has a length smaller than the longest sequence in the input data"
-            );
-            s.into()
-        })
-        .collect();
+    check_generators(data, generators.len()).unwrap_or_else(|err| panic!("{err}"));
+    if is_small_batch(data) {
+        let commitments = fill(commitments, bn254_g1_affine::identity());
+        reference::compute_bn254_g1_uncompressed_commitments_with_generators(
+            commitments,
+            data,
+            generators,
+        );
+        return commitments;
+    }
+    let num_rows = bn254_g1_rows_per_chunk(data).unwrap_or_else(|err| panic!("{err}"));
+    unsafe {
+        compute_bn254_g1_uncompressed_commitments_with_generators_impl(
+            commitments,
+            data,
+            generators,
+            num_rows,
+        );
+    }
+
+    // SAFETY: the backend writes one commitment for each sequence
+    unsafe { assume_init_mut(commitments) }
+}
+
+/// Returns the number of rows of the chunks of a `bn254` `G1` commitment computation,
+/// as given by [rows_per_chunk].
+#[cfg(feature = "bn254")]
+fn bn254_g1_rows_per_chunk(data: &[Sequence]) -> Result<Option<usize>, BackendError> {
+    let point_size = std::mem::size_of::<blitzar_sys::sxt_bn254_g1>();
+    rows_per_chunk(data, point_size, point_size, max_device_memory_bytes())
+}
+
+/// Computes the commitments without initializing the backend, in chunks of `num_rows`
+/// rows if given.
+///
+/// # Safety
+///
+/// The backend must be initialized.
+#[cfg(feature = "bn254")]
+pub(crate) unsafe fn compute_bn254_g1_uncompressed_commitments_with_generators_impl(
+    commitments: &mut [MaybeUninit<bn254_g1_affine>],
+    data: &[Sequence],
+    generators: &[bn254_g1_affine],
+    num_rows: Option<usize>,
+) {
+    if let Some(num_rows) = num_rows {
+        let commitments = fill(commitments, bn254_g1_affine::identity());
+        compute_sw_commitments_in_chunks(commitments, data, num_rows, |partial, chunk, start| {
+            compute_bn254_g1_uncompressed_commitments_with_generators_impl(
+                // SAFETY: the backend only writes initialized commitments
+                as_uninit_mut(partial),
+                chunk,
+                &generators[start..],
+                None,
+            )
+        });
+        return;
+    }
+
+    let sxt_descriptors: Vec<blitzar_sys::sxt_sequence_descriptor> =
+        data.iter().map(|s| s.into()).collect();
 
     let sxt_bn254_g1_generators = generators.as_ptr() as *const blitzar_sys::sxt_bn254_g1;
 
     let sxt_bn254_g1_uncompressed = commitments.as_mut_ptr() as *mut blitzar_sys::sxt_bn254_g1;
 
-    unsafe {
-        blitzar_sys::sxt_bn254_g1_uncompressed_compute_pedersen_commitments_with_generators(
-            sxt_bn254_g1_uncompressed,
-            sxt_descriptors.len() as u32,
-            sxt_descriptors.as_ptr(),
-            sxt_bn254_g1_generators,
+    blitzar_sys::sxt_bn254_g1_uncompressed_compute_pedersen_commitments_with_generators(
+        sxt_bn254_g1_uncompressed,
+        backend_len("data", sxt_descriptors.len()),
+        sxt_descriptors.as_ptr(),
+        sxt_bn254_g1_generators,
+    );
+
+    if let Some(correction) = NullCorrection::new(data) {
+        let mut nulls = vec![bn254_g1_affine::default(); data.len()];
+        compute_bn254_g1_uncompressed_commitments_with_generators_impl(
+            // SAFETY: the backend only writes initialized commitments
+            as_uninit_mut(&mut nulls),
+            &correction.sequences(),
+            &correction.gather(generators),
+            None,
         );
+        subtract_sw_commitments(assume_init_mut(commitments), &nulls);
     }
 }
 
+/// Fallible version of [compute_bn254_g1_uncompressed_commitments_with_generators].
+///
+/// Returns an error instead of panicking if `commitments.len()` is different from
+/// `data.len()`, if a sequence is invalid or longer than `generators`, or if the backend
+/// cannot be initialized.
+///
+/// Computations exceeding [BackendConfig::max_device_memory_bytes](super::BackendConfig::max_device_memory_bytes),
+/// or with sequences longer than `u32::MAX` rows, are split into chunks of rows, and an
+/// error is returned if a chunk still exceeds the memory limit.
+#[cfg(feature = "bn254")]
+pub fn try_compute_bn254_g1_uncompressed_commitments_with_generators(
+    commitments: &mut [bn254_g1_affine],
+    data: &[Sequence],
+    generators: &[bn254_g1_affine],
+) -> Result<(), Error> {
+    check_inputs(commitments.len(), data, Some(generators.len()))?;
+    ensure_initialized()?;
+    bn254_g1_rows_per_chunk(data)?;
+    compute_bn254_g1_uncompressed_commitments_with_generators(commitments, data, generators);
+    Ok(())
+}
+
 #[doc = include_str!("../../docs/commitments/update_curve25519_commitments.md")]
 ///
 /// # Example - Update Commitments with Dense and Dalek Scalars
@@ -210,40 +865,473 @@ pub fn update_curve25519_commitments(
         });
 }
 
+/// Rerandomizes curve25519 commitments in place.
+///
+/// Each commitment is replaced by `C_i' = C_i + r_i * H`, where `r_i` is the
+/// `i`-th entry of `blinding_factors` and `H` is the blinding generator `h`. The
+/// products `r_i * H` are all computed in a single backend call.
+///
+/// # Panics
+///
+/// If `commitments` and `blinding_factors` have different lengths, or if a commitment
+/// is not a valid ristretto point.
+pub fn rerandomize_curve25519_commitments(
+    commitments: &mut [CompressedRistretto],
+    blinding_factors: &[Scalar],
+    h: &RistrettoPoint,
+) {
+    assert_eq!(commitments.len(), blinding_factors.len());
+    let data: Vec<Sequence> = blinding_factors
+        .iter()
+        .map(|r| std::slice::from_ref(r).into())
+        .collect();
+    let mut blindings = vec![CompressedRistretto::default(); data.len()];
+    compute_curve25519_commitments_with_generators(&mut blindings, &data, std::slice::from_ref(h));
+
+    commitments
+        .par_iter_mut()
+        .zip(blindings)
+        .for_each(|(c, blinding)| {
+            *c = (c.decompress().unwrap_or_else(|| {
+                panic!(
+                    "invalid ristretto point decompression on rerandomize_curve25519_commitments"
+                )
+            }) + blinding.decompress().unwrap_or_else(|| {
+                panic!(
+                    "invalid ristretto point decompression on rerandomize_curve25519_commitments"
+                )
+            }))
+            .compress()
+        });
+}
+
+/// Rerandomizes bn254 `G1` commitments in place.
+///
+/// Each commitment is replaced by `C_i' = C_i + r_i * H`, where `r_i` is the
+/// `i`-th entry of `blinding_factors` and `H` is the blinding generator `h`. The
+/// products `r_i * H` are all computed in a single backend call.
+///
+/// # Panics
+///
+/// If `commitments` and `blinding_factors` have different lengths.
+#[cfg(feature = "bn254")]
+pub fn rerandomize_bn254_g1_uncompressed_commitments(
+    commitments: &mut [bn254_g1_affine],
+    blinding_factors: &[ark_bn254::Fr],
+    h: &bn254_g1_affine,
+) {
+    assert_eq!(commitments.len(), blinding_factors.len());
+    let blinding_factors: Vec<[u64; 4]> =
+        blinding_factors.iter().map(|r| r.into_bigint().0).collect();
+    let data: Vec<Sequence> = blinding_factors
+        .iter()
+        .map(|r| std::slice::from_ref(r).into())
+        .collect();
+    let mut blindings = vec![bn254_g1_affine::default(); data.len()];
+    compute_bn254_g1_uncompressed_commitments_with_generators(
+        &mut blindings,
+        &data,
+        std::slice::from_ref(h),
+    );
+
+    commitments
+        .par_iter_mut()
+        .zip(blindings)
+        .for_each(|(c, blinding)| *c = (*c + blinding).into_affine());
+}
+
 #[doc = include_str!("../../docs/commitments/compute_grumpkin_commitments_with_generators.md")]
 ///
 /// # Example - Pass generators to Commitment Computation
 ///```no_run
 #[doc = include_str!("../../examples/pass_grumpkin_generators_to_commitment.rs")]
 ///```
+#[cfg(feature = "grumpkin")]
 pub fn compute_grumpkin_uncompressed_commitments_with_generators(
     commitments: &mut [grumpkin_affine],
     data: &[Sequence],
     generators: &[grumpkin_affine],
 ) {
+    // SAFETY: the uninit variant only writes initialized commitments
+    let commitments = unsafe { as_uninit_mut(&mut commitments[..data.len()]) };
+    compute_grumpkin_uncompressed_commitments_with_generators_uninit(commitments, data, generators);
+}
+
+/// Computes the `grumpkin` commitments of `data` into uninitialized memory.
+///
+/// This is the same as [compute_grumpkin_uncompressed_commitments_with_generators],
+/// except that `commitments` does not need to be initialized beforehand. Every element
+/// of `commitments` is initialized on return.
+///
+/// # Panics
+///
+/// If `commitments.len()` is different from `data.len()`, or in the same cases as
+/// [compute_grumpkin_uncompressed_commitments_with_generators].
+#[cfg(feature = "grumpkin")]
+pub fn compute_grumpkin_uncompressed_commitments_with_generators_uninit<'a>(
+    commitments: &'a mut [MaybeUninit<grumpkin_affine>],
+    data: &[Sequence],
+    generators: &[grumpkin_affine],
+) -> &'a mut [grumpkin_affine] {
+    assert_eq!(
+        commitments.len(),
+        data.len(),
+        "commitments must hold one element for each sequence"
+    );
+    if fill_identity_if_empty(commitments, data, grumpkin_affine::identity()) {
+        return unsafe { assume_init_mut(commitments) };
+    }
     init_backend();
 
-    let sxt_descriptors: Vec<blitzar_sys::sxt_sequence_descriptor> = data
-        .iter()
-        .map(|s| {
-            assert!(
-                s.len() <= generators.len(),
-                "generators has a length smaller than the longest sequence in the input data"
-            );
-            s.into()
-        })
-        .collect();
+    check_generators(data, generators.len()).unwrap_or_else(|err| panic!("{err}"));
+    if is_small_batch(data) {
+        let commitments = fill(commitments, grumpkin_affine::identity());
+        reference::compute_grumpkin_uncompressed_commitments_with_generators(
+            commitments,
+            data,
+            generators,
+        );
+        return commitments;
+    }
+    let num_rows = grumpkin_rows_per_chunk(data).unwrap_or_else(|err| panic!("{err}"));
+    unsafe {
+        compute_grumpkin_uncompressed_commitments_with_generators_impl(
+            commitments,
+            data,
+            generators,
+            num_rows,
+        );
+    }
+
+    // SAFETY: the backend writes one commitment for each sequence
+    unsafe { assume_init_mut(commitments) }
+}
+
+/// Returns the number of rows of the chunks of a `grumpkin` commitment computation, as
+/// given by [rows_per_chunk].
+#[cfg(feature = "grumpkin")]
+fn grumpkin_rows_per_chunk(data: &[Sequence]) -> Result<Option<usize>, BackendError> {
+    let point_size = std::mem::size_of::<blitzar_sys::sxt_grumpkin>();
+    rows_per_chunk(data, point_size, point_size, max_device_memory_bytes())
+}
+
+/// Computes the commitments without initializing the backend, in chunks of `num_rows`
+/// rows if given.
+///
+/// # Safety
+///
+/// The backend must be initialized.
+#[cfg(feature = "grumpkin")]
+pub(crate) unsafe fn compute_grumpkin_uncompressed_commitments_with_generators_impl(
+    commitments: &mut [MaybeUninit<grumpkin_affine>],
+    data: &[Sequence],
+    generators: &[grumpkin_affine],
+    num_rows: Option<usize>,
+) {
+    if let Some(num_rows) = num_rows {
+        let commitments = fill(commitments, grumpkin_affine::identity());
+        compute_sw_commitments_in_chunks(commitments, data, num_rows, |partial, chunk, start| {
+            compute_grumpkin_uncompressed_commitments_with_generators_impl(
+                // SAFETY: the backend only writes initialized commitments
+                as_uninit_mut(partial),
+                chunk,
+                &generators[start..],
+                None,
+            )
+        });
+        return;
+    }
+
+    let sxt_descriptors: Vec<blitzar_sys::sxt_sequence_descriptor> =
+        data.iter().map(|s| s.into()).collect();
 
     let sxt_grumpkin_generators = generators.as_ptr() as *const blitzar_sys::sxt_grumpkin;
 
     let sxt_grumpkin_uncompressed = commitments.as_mut_ptr() as *mut blitzar_sys::sxt_grumpkin;
 
-    unsafe {
-        blitzar_sys::sxt_grumpkin_uncompressed_compute_pedersen_commitments_with_generators(
-            sxt_grumpkin_uncompressed,
-            sxt_descriptors.len() as u32,
-            sxt_descriptors.as_ptr(),
-            sxt_grumpkin_generators,
+    blitzar_sys::sxt_grumpkin_uncompressed_compute_pedersen_commitments_with_generators(
+        sxt_grumpkin_uncompressed,
+        backend_len("data", sxt_descriptors.len()),
+        sxt_descriptors.as_ptr(),
+        sxt_grumpkin_generators,
+    );
+
+    if let Some(correction) = NullCorrection::new(data) {
+        let mut nulls = vec![grumpkin_affine::default(); data.len()];
+        compute_grumpkin_uncompressed_commitments_with_generators_impl(
+            // SAFETY: the backend only writes initialized commitments
+            as_uninit_mut(&mut nulls),
+            &correction.sequences(),
+            &correction.gather(generators),
+            None,
         );
+        subtract_sw_commitments(assume_init_mut(commitments), &nulls);
+    }
+}
+
+/// Fallible version of [compute_grumpkin_uncompressed_commitments_with_generators].
+///
+/// Returns an error instead of panicking if `commitments.len()` is different from
+/// `data.len()`, if a sequence is invalid or longer than `generators`, or if the backend
+/// cannot be initialized.
+///
+/// Computations exceeding [BackendConfig::max_device_memory_bytes](super::BackendConfig::max_device_memory_bytes),
+/// or with sequences longer than `u32::MAX` rows, are split into chunks of rows, and an
+/// error is returned if a chunk still exceeds the memory limit.
+#[cfg(feature = "grumpkin")]
+pub fn try_compute_grumpkin_uncompressed_commitments_with_generators(
+    commitments: &mut [grumpkin_affine],
+    data: &[Sequence],
+    generators: &[grumpkin_affine],
+) -> Result<(), Error> {
+    check_inputs(commitments.len(), data, Some(generators.len()))?;
+    ensure_initialized()?;
+    grumpkin_rows_per_chunk(data)?;
+    compute_grumpkin_uncompressed_commitments_with_generators(commitments, data, generators);
+    Ok(())
+}
+
+/// Computes the commitments of `data` using the generators of `handle`.
+///
+/// The sequences are packed into a single `packed_msm` call. The results of signed
+/// sequences are then corrected with [CurveId::sub_shifted].
+pub(crate) fn compute_commitments_with_handle<T: CurveId + Clone + Default>(
+    data: &[Sequence],
+    handle: &MsmHandle<T>,
+) -> Vec<T> {
+    check_generators(data, handle.num_generators()).unwrap_or_else(|err| panic!("{err}"));
+    let longest_sequence = data.iter().map(Sequence::len).max().unwrap_or(0);
+
+    let packed = pack_sequences(data);
+    let mut res = vec![T::default(); packed.output_bit_table.len()];
+    if longest_sequence > 0 {
+        handle.packed_msm(&mut res, &packed.output_bit_table, &packed.scalars);
+    }
+
+    for (j, sign_output) in packed.sign_outputs.iter().enumerate() {
+        if let Some(k) = sign_output {
+            res[j] = T::sub_shifted(&res[j], &res[*k], 8 * data[j].element_size() as u32);
+        }
+    }
+    res.truncate(data.len());
+    res
+}
+
+/// Computes the Pedersen commitments of `data` using the `curve25519` generators
+/// already uploaded to `handle`.
+///
+/// The `j`-th commitment is computed as
+///
+/// ```text
+/// commitments[j] = data[j][0] * g[0] + data[j][1] * g[1] + ... + data[j][n_j - 1] * g[n_j - 1]
+/// ```
+///
+/// where `g` are the generators used to create `handle`, so the result is the same as
+/// calling `compute_curve25519_commitments_with_generators` with those generators,
+/// without transferring them again. Sequences may have different lengths, element
+/// sizes and signedness.
+///
+/// # Asserts
+///
+/// If `commitments.len()` is different from `data.len()`, if the longest sequence is
+/// longer than the number of generators of `handle`, or if the packed scalars exceed
+/// [BackendConfig::max_device_memory_bytes](super::BackendConfig::max_device_memory_bytes),
+/// since an MSM with a handle is not split into chunks of rows.
+pub fn compute_curve25519_commitments_with_handle(
+    commitments: &mut [CompressedRistretto],
+    data: &[Sequence],
+    handle: &MsmHandle<RistrettoPoint>,
+) {
+    assert_eq!(commitments.len(), data.len());
+
+    let res = compute_commitments_with_handle(data, handle);
+
+    commitments
+        .par_iter_mut()
+        .zip(res)
+        .for_each(|(commitment, res)| *commitment = res.compress());
+}
+
+/// Computes the Pedersen commitments of `data` using the `bls12-381` `G1` generators
+/// already uploaded to `handle`.
+///
+/// The result is the same as calling `compute_bls12_381_g1_commitments_with_generators`
+/// with the generators used to create `handle`. See
+/// [compute_curve25519_commitments_with_handle] for details.
+///
+/// # Asserts
+///
+/// If `commitments.len()` is different from `data.len()`, if the longest sequence is
+/// longer than the number of generators of `handle`, or if the packed scalars exceed
+/// [BackendConfig::max_device_memory_bytes](super::BackendConfig::max_device_memory_bytes),
+/// since an MSM with a handle is not split into chunks of rows.
+#[cfg(feature = "bls12_381")]
+pub fn compute_bls12_381_g1_commitments_with_handle(
+    commitments: &mut [[u8; 48]],
+    data: &[Sequence],
+    handle: &MsmHandle<ElementP2<ark_bls12_381::g1::Config>>,
+) {
+    assert_eq!(commitments.len(), data.len());
+
+    let res = compute_commitments_with_handle(data, handle);
+
+    commitments
+        .par_iter_mut()
+        .zip(res)
+        .for_each(|(commitment, res)| {
+            G1Affine::from(res)
+                .serialize_compressed(&mut commitment[..])
+                .expect("a compressed bls12-381 G1 point is 48 bytes")
+        });
+}
+
+/// Computes the Pedersen commitments of `data` using the `bn254` `G1` generators
+/// already uploaded to `handle`.
+///
+/// The result is the same as calling
+/// `compute_bn254_g1_uncompressed_commitments_with_generators` with the generators used
+/// to create `handle`. See [compute_curve25519_commitments_with_handle] for details.
+///
+/// # Asserts
+///
+/// If `commitments.len()` is different from `data.len()`, if the longest sequence is
+/// longer than the number of generators of `handle`, or if the packed scalars exceed
+/// [BackendConfig::max_device_memory_bytes](super::BackendConfig::max_device_memory_bytes),
+/// since an MSM with a handle is not split into chunks of rows.
+#[cfg(feature = "bn254")]
+pub fn compute_bn254_g1_uncompressed_commitments_with_handle(
+    commitments: &mut [bn254_g1_affine],
+    data: &[Sequence],
+    handle: &MsmHandle<ElementP2<ark_bn254::g1::Config>>,
+) {
+    assert_eq!(commitments.len(), data.len());
+
+    let res = compute_commitments_with_handle(data, handle);
+
+    commitments
+        .par_iter_mut()
+        .zip(res)
+        .for_each(|(commitment, res)| *commitment = res.into());
+}
+
+/// Computes the Pedersen commitments of `data` using the `grumpkin` generators
+/// already uploaded to `handle`.
+///
+/// The result is the same as calling
+/// `compute_grumpkin_uncompressed_commitments_with_generators` with the generators used
+/// to create `handle`. See [compute_curve25519_commitments_with_handle] for details.
+///
+/// # Asserts
+///
+/// If `commitments.len()` is different from `data.len()`, if the longest sequence is
+/// longer than the number of generators of `handle`, or if the packed scalars exceed
+/// [BackendConfig::max_device_memory_bytes](super::BackendConfig::max_device_memory_bytes),
+/// since an MSM with a handle is not split into chunks of rows.
+#[cfg(feature = "grumpkin")]
+pub fn compute_grumpkin_uncompressed_commitments_with_handle(
+    commitments: &mut [grumpkin_affine],
+    data: &[Sequence],
+    handle: &MsmHandle<ElementP2<ark_grumpkin::GrumpkinConfig>>,
+) {
+    assert_eq!(commitments.len(), data.len());
+
+    let res = compute_commitments_with_handle(data, handle);
+
+    commitments
+        .par_iter_mut()
+        .zip(res)
+        .for_each(|(commitment, res)| *commitment = res.into());
+}
+
+/// Subtracts the commitments of the null elements from `commitments`.
+fn subtract_curve25519_commitments(
+    commitments: &mut [CompressedRistretto],
+    nulls: &[CompressedRistretto],
+) {
+    commitments
+        .par_iter_mut()
+        .zip(nulls)
+        .for_each(|(commitment, null)| {
+            *commitment = (commitment
+                .decompress()
+                .expect("the backend returns valid points")
+                - null.decompress().expect("the backend returns valid points"))
+            .compress()
+        });
+}
+
+/// Subtracts the commitments of the null elements from `commitments`.
+#[cfg(any(feature = "bn254", feature = "grumpkin"))]
+fn subtract_sw_commitments<C: SWCurveConfig>(commitments: &mut [Affine<C>], nulls: &[Affine<C>]) {
+    commitments
+        .par_iter_mut()
+        .zip(nulls)
+        .for_each(|(commitment, null)| *commitment = (*commitment - *null).into_affine());
+}
+
+/// Writes `identity` to every commitment if no sequence of `data` has any element.
+///
+/// Returns `true` in that case, so that callers can skip the backend entirely.
+fn fill_identity_if_empty<T: Copy>(
+    commitments: &mut [MaybeUninit<T>],
+    data: &[Sequence],
+    identity: T,
+) -> bool {
+    if !data.iter().all(Sequence::is_empty) {
+        return false;
     }
+    commitments.iter_mut().for_each(|c| {
+        c.write(identity);
+    });
+    true
+}
+
+/// Returns true if `data` has at most [BackendConfig::small_batch_threshold](super::BackendConfig::small_batch_threshold)
+/// elements, so that the commitments are computed on the host.
+fn is_small_batch(data: &[Sequence]) -> bool {
+    is_small_batch_with_threshold(data, small_batch_threshold())
+}
+
+/// Returns true if `data` has at most `threshold` elements and `threshold` is not zero.
+pub(crate) fn is_small_batch_with_threshold(data: &[Sequence], threshold: usize) -> bool {
+    threshold > 0 && data.iter().map(Sequence::len).sum::<usize>() <= threshold
+}
+
+/// Initializes every element of `commitments` to `value`.
+fn fill<T: Copy>(commitments: &mut [MaybeUninit<T>], value: T) -> &mut [T] {
+    commitments.iter_mut().for_each(|c| {
+        c.write(value);
+    });
+    // SAFETY: every element was just initialized
+    unsafe { assume_init_mut(commitments) }
+}
+
+/// Returns the compressed encoding of the identity of `bls12-381` `G1`.
+#[cfg(feature = "bls12_381")]
+fn bls12_381_g1_compressed_identity() -> [u8; 48] {
+    let mut bytes = [0_u8; 48];
+    G1Affine::identity()
+        .serialize_compressed(&mut bytes[..])
+        .expect("the identity fits in 48 bytes");
+    bytes
+}
+
+/// Views an initialized slice as possibly uninitialized memory.
+///
+/// # Safety
+///
+/// The returned slice must only be written with initialized values. Writing
+/// `MaybeUninit::uninit()` through it would leave `slice` holding uninitialized `T`s
+/// once the borrow ends.
+pub(crate) unsafe fn as_uninit_mut<T: Copy>(slice: &mut [T]) -> &mut [MaybeUninit<T>] {
+    // MaybeUninit<T> has the same layout as T, and T: Copy has no drop glue, so
+    // overwriting elements through the returned slice cannot leak or double drop
+    &mut *(slice as *mut [T] as *mut [MaybeUninit<T>])
+}
+
+/// # Safety
+///
+/// Every element of `slice` must be initialized.
+pub(crate) unsafe fn assume_init_mut<T>(slice: &mut [MaybeUninit<T>]) -> &mut [T] {
+    &mut *(slice as *mut [MaybeUninit<T>] as *mut [T])
 }
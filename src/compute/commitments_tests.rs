@@ -12,14 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::*;
+use super::{commitments::is_small_batch_with_threshold, *};
+#[cfg(all(feature = "bls12_381", feature = "bn254", feature = "grumpkin"))]
+use crate::reference;
+use crate::sequence::Sequence;
+#[cfg(feature = "bls12_381")]
 use ark_bls12_381::{Fr, G1Affine, G1Projective};
+#[cfg(feature = "bn254")]
 use ark_bn254::{Fr as bn254_fr, G1Affine as bn254_g1_affine, G1Projective as bn254_g1_projective};
+#[cfg(any(feature = "bls12_381", feature = "bn254", feature = "grumpkin"))]
 use ark_ec::{CurveGroup, VariableBaseMSM};
+#[cfg(feature = "grumpkin")]
 use ark_grumpkin::{
     Affine as grumpkin_affine, Fr as grumpkin_fr, Projective as grumpkin_projective,
 };
+#[cfg(feature = "bls12_381")]
 use ark_serialize::CanonicalSerialize;
+#[cfg(any(feature = "bls12_381", feature = "bn254", feature = "grumpkin"))]
 use ark_std::UniformRand;
 use curve25519_dalek::{
     ristretto::{CompressedRistretto, RistrettoPoint},
@@ -472,6 +481,7 @@ fn sending_generators_to_gpu_produces_correct_commitment_results() {
 }
 
 #[test]
+#[cfg(feature = "bls12_381")]
 fn sending_generators_to_gpu_produces_correct_bls12_381_g1_commitment_results() {
     // generate input table
     let data: Vec<u64> = vec![2, 3, 1, 5, 4, 7, 6, 8, 9, 10];
@@ -513,6 +523,51 @@ fn sending_generators_to_gpu_produces_correct_bls12_381_g1_commitment_results()
 }
 
 #[test]
+#[cfg(feature = "bls12_381")]
+fn bls12_381_g1_commitments_with_generators_support_ragged_sequences() {
+    // generate input table with columns of different lengths and element sizes
+    let data_a: Vec<u64> = vec![2, 3, 1, 5, 4, 7, 6, 8, 9, 10];
+    let data_b: Vec<u8> = vec![3, 1];
+    let data_c: Vec<u32> = vec![7, 0, 0, 9, 1];
+
+    // randomly obtain the generator points
+    let mut rng = ark_std::test_rng();
+    let generator_points: Vec<G1Affine> = (0..data_a.len())
+        .map(|_| G1Affine::rand(&mut rng))
+        .collect();
+
+    // initialize commitments
+    let mut commitments = vec![[0_u8; 48]; 3];
+
+    // compute commitments in Blitzar
+    compute_bls12_381_g1_commitments_with_generators(
+        &mut commitments,
+        &[(&data_a).into(), (&data_b).into(), (&data_c).into()],
+        &generator_points,
+    );
+
+    // compute msms in Arkworks
+    let scalar_data: Vec<Vec<Fr>> = vec![
+        data_a.iter().map(|d| Fr::from(*d)).collect(),
+        data_b.iter().map(|d| Fr::from(*d)).collect(),
+        data_c.iter().map(|d| Fr::from(*d)).collect(),
+    ];
+    for (commitment, scalars) in commitments.iter().zip(&scalar_data) {
+        let ark_commitment =
+            G1Projective::msm(&generator_points[..scalars.len()], scalars).unwrap();
+
+        let mut compressed_bytes = Vec::new();
+        ark_commitment
+            .serialize_compressed(&mut compressed_bytes)
+            .unwrap();
+
+        // verify results
+        assert_eq!(&commitment[..], compressed_bytes.as_slice());
+    }
+}
+
+#[test]
+#[cfg(feature = "bn254")]
 fn sending_generators_to_gpu_produces_correct_bn254_g1_commitment_results() {
     // generate input table
     let data: Vec<u64> = vec![2, 3, 1, 5, 4, 7, 6, 8, 9, 10];
@@ -548,6 +603,7 @@ fn sending_generators_to_gpu_produces_correct_bn254_g1_commitment_results() {
 }
 
 #[test]
+#[cfg(feature = "grumpkin")]
 fn sending_generators_to_gpu_produces_correct_grumpkin_commitment_results() {
     // generate input table
     let data: Vec<u64> = vec![2, 3, 1, 5, 4, 7, 6, 8, 9, 10];
@@ -644,3 +700,764 @@ fn commit_to_signed_slice_and_its_negatives_gives_the_zero_commit() {
             == commitments[2].decompress().unwrap()
     );
 }
+
+#[test]
+fn curve25519_commitments_with_handle_match_commitments_with_generators() {
+    let data_a: Vec<u64> = vec![2, 3, 1, 5, 4, 7, 6, 8, 9, 10];
+    let data_b: Vec<i32> = vec![-3, 1, i32::MIN, 7];
+    let data_c: Vec<u8> = vec![255, 0, 17];
+    let data_d: Vec<i16> = vec![];
+    let data: Vec<Sequence> = vec![
+        (&data_a).into(),
+        (&data_b).into(),
+        (&data_c).into(),
+        (&data_d).into(),
+    ];
+
+    let mut rng = OsRng;
+    let generators: Vec<RistrettoPoint> =
+        (0..16).map(|_| RistrettoPoint::random(&mut rng)).collect();
+    let handle = MsmHandle::new(&generators);
+
+    let mut commitments = vec![CompressedRistretto::default(); data.len()];
+    compute_curve25519_commitments_with_handle(&mut commitments, &data, &handle);
+
+    let mut expected = vec![CompressedRistretto::default(); data.len()];
+    compute_curve25519_commitments_with_generators(&mut expected, &data, &generators);
+
+    assert_eq!(commitments, expected);
+    assert_eq!(commitments[3], CompressedRistretto::default());
+}
+
+#[test]
+#[cfg(feature = "bls12_381")]
+fn bls12_381_g1_commitments_with_handle_match_commitments_with_generators() {
+    let data_a: Vec<u64> = vec![2, 3, 1, 5, 4, 7, 6, 8, 9, 10];
+    let data_b: Vec<i64> = vec![-3, 1, i64::MIN, 7];
+    let data_c: Vec<u8> = vec![255, 0, 17];
+    let data: Vec<Sequence> = vec![(&data_a).into(), (&data_b).into(), (&data_c).into()];
+
+    let mut rng = ark_std::test_rng();
+    let generator_points: Vec<G1Affine> = (0..16).map(|_| G1Affine::rand(&mut rng)).collect();
+    let handle = MsmHandle::new_with_affine(&generator_points);
+
+    let mut commitments = vec![[0_u8; 48]; data.len()];
+    compute_bls12_381_g1_commitments_with_handle(&mut commitments, &data, &handle);
+
+    let mut expected = vec![[0_u8; 48]; data.len()];
+    compute_bls12_381_g1_commitments_with_generators(&mut expected, &data, &generator_points);
+
+    assert_eq!(commitments, expected);
+}
+
+#[test]
+#[cfg(feature = "bn254")]
+fn bn254_g1_commitments_with_handle_match_commitments_with_generators() {
+    let data_a: Vec<u64> = vec![2, 3, 1, 5, 4, 7, 6, 8, 9, 10];
+    let data_b: Vec<i32> = vec![-3, 1, i32::MIN, 7];
+    let data: Vec<Sequence> = vec![(&data_a).into(), (&data_b).into()];
+
+    let mut rng = ark_std::test_rng();
+    let generator_points: Vec<bn254_g1_affine> =
+        (0..16).map(|_| bn254_g1_affine::rand(&mut rng)).collect();
+    let handle = MsmHandle::new_with_affine(&generator_points);
+
+    let mut commitments = vec![bn254_g1_affine::default(); data.len()];
+    compute_bn254_g1_uncompressed_commitments_with_handle(&mut commitments, &data, &handle);
+
+    let mut expected = vec![bn254_g1_affine::default(); data.len()];
+    compute_bn254_g1_uncompressed_commitments_with_generators(
+        &mut expected,
+        &data,
+        &generator_points,
+    );
+
+    assert_eq!(commitments, expected);
+}
+
+#[test]
+#[cfg(feature = "grumpkin")]
+fn grumpkin_commitments_with_handle_match_commitments_with_generators() {
+    let data_a: Vec<u64> = vec![2, 3, 1, 5, 4, 7, 6, 8, 9, 10];
+    let data_b: Vec<i16> = vec![-3, 1, i16::MIN, 7];
+    let data: Vec<Sequence> = vec![(&data_a).into(), (&data_b).into()];
+
+    let mut rng = ark_std::test_rng();
+    let generator_points: Vec<grumpkin_affine> =
+        (0..16).map(|_| grumpkin_affine::rand(&mut rng)).collect();
+    let handle = MsmHandle::new_with_affine(&generator_points);
+
+    let mut commitments = vec![grumpkin_affine::default(); data.len()];
+    compute_grumpkin_uncompressed_commitments_with_handle(&mut commitments, &data, &handle);
+
+    let mut expected = vec![grumpkin_affine::default(); data.len()];
+    compute_grumpkin_uncompressed_commitments_with_generators(
+        &mut expected,
+        &data,
+        &generator_points,
+    );
+
+    assert_eq!(commitments, expected);
+}
+
+#[test]
+fn commitments_are_identical_across_repeated_runs() {
+    let data_a: Vec<u64> = vec![2, 3, 1, 5, 4, 7, 6, 8, 9, 10];
+    let data_b: Vec<i32> = vec![-3, 1, i32::MIN, 7];
+    let data: Vec<Sequence> = vec![(&data_a).into(), (&data_b).into()];
+
+    let mut first = vec![CompressedRistretto::default(); data.len()];
+    compute_curve25519_commitments(&mut first, &data, 0);
+
+    for _ in 0..4 {
+        let mut commitments = vec![CompressedRistretto::default(); data.len()];
+        compute_curve25519_commitments(&mut commitments, &data, 0);
+        assert_eq!(commitments, first);
+    }
+}
+
+#[test]
+fn the_backend_is_reported_as_initialized_after_computing_commitments() {
+    let data: Vec<u32> = vec![1, 2, 3];
+    let mut commitments = vec![CompressedRistretto::default(); 1];
+    compute_curve25519_commitments(&mut commitments, &[(&data).into()], 0);
+
+    let info = backend_info();
+    assert!(info.initialized);
+    let expected_backend = if cfg!(feature = "cpu") {
+        BackendKind::Cpu
+    } else {
+        BackendKind::Gpu
+    };
+    assert_eq!(info.backend, expected_backend);
+}
+
+#[test]
+#[cfg(feature = "bn254")]
+fn we_can_compute_commitments_into_uninitialized_memory() {
+    let data_a: Vec<u64> = vec![2, 3, 1, 5];
+    let data_b: Vec<i32> = vec![-3, 1];
+    let data: Vec<Sequence> = vec![(&data_a).into(), (&data_b).into()];
+
+    let mut expected = vec![CompressedRistretto::default(); 2];
+    compute_curve25519_commitments(&mut expected, &data, 0);
+    let mut commitments = Vec::with_capacity(2);
+    let res =
+        compute_curve25519_commitments_uninit(&mut commitments.spare_capacity_mut()[..2], &data, 0);
+    assert_eq!(res, &expected[..]);
+
+    let mut rng = ark_std::test_rng();
+    let generators: Vec<bn254_g1_affine> =
+        (0..4).map(|_| bn254_g1_affine::rand(&mut rng)).collect();
+    let mut expected = vec![bn254_g1_affine::default(); 2];
+    compute_bn254_g1_uncompressed_commitments_with_generators(&mut expected, &data, &generators);
+    let mut commitments = [std::mem::MaybeUninit::uninit(); 2];
+    let res = compute_bn254_g1_uncompressed_commitments_with_generators_uninit(
+        &mut commitments,
+        &data,
+        &generators,
+    );
+    assert_eq!(res, &expected[..]);
+}
+
+#[test]
+#[should_panic(expected = "commitments must hold one element for each sequence")]
+fn we_cannot_compute_commitments_into_uninitialized_memory_of_the_wrong_length() {
+    let data: Vec<u64> = vec![2, 3, 1, 5];
+    let mut commitments = [std::mem::MaybeUninit::uninit(); 2];
+    compute_curve25519_commitments_uninit(&mut commitments, &[(&data).into()], 0);
+}
+
+#[test]
+fn we_can_compute_commitments_without_checking_the_backend_once_initialized() {
+    assert_eq!(ensure_initialized(), Ok(()));
+
+    let data_a: Vec<u64> = vec![2, 3, 1, 5];
+    let data_b: Vec<i32> = vec![-3, 1];
+    let data: Vec<Sequence> = vec![(&data_a).into(), (&data_b).into()];
+
+    let mut expected = vec![CompressedRistretto::default(); 2];
+    compute_curve25519_commitments(&mut expected, &data, 3);
+    let mut commitments = vec![CompressedRistretto::default(); 2];
+    unsafe { compute_curve25519_commitments_unchecked(&mut commitments, &data, 3) };
+    assert_eq!(commitments, expected);
+
+    let mut rng = OsRng;
+    let generators: Vec<RistrettoPoint> =
+        (0..4).map(|_| RistrettoPoint::random(&mut rng)).collect();
+    compute_curve25519_commitments_with_generators(&mut expected, &data, &generators);
+    unsafe {
+        compute_curve25519_commitments_with_generators_unchecked(
+            &mut commitments,
+            &data,
+            &generators,
+        )
+    };
+    assert_eq!(commitments, expected);
+}
+
+#[test]
+#[cfg(feature = "bn254")]
+fn we_can_rerandomize_commitments() {
+    let data_a: Vec<u64> = vec![2, 3, 1, 5];
+    let data_b: Vec<i32> = vec![-3, 1];
+    let data: Vec<Sequence> = vec![(&data_a).into(), (&data_b).into()];
+
+    let h = RistrettoPoint::random(&mut OsRng);
+    let r = [Scalar::random(&mut OsRng), Scalar::random(&mut OsRng)];
+    let mut commitments = vec![CompressedRistretto::default(); 2];
+    compute_curve25519_commitments(&mut commitments, &data, 0);
+    let expected: Vec<CompressedRistretto> = commitments
+        .iter()
+        .zip(&r)
+        .map(|(c, r)| (c.decompress().unwrap() + r * h).compress())
+        .collect();
+    rerandomize_curve25519_commitments(&mut commitments, &r, &h);
+    assert_eq!(commitments, expected);
+
+    let mut rng = ark_std::test_rng();
+    let generators: Vec<bn254_g1_affine> =
+        (0..4).map(|_| bn254_g1_affine::rand(&mut rng)).collect();
+    let h = bn254_g1_affine::rand(&mut rng);
+    let r = [bn254_fr::rand(&mut rng), bn254_fr::rand(&mut rng)];
+    let mut commitments = vec![bn254_g1_affine::default(); 2];
+    compute_bn254_g1_uncompressed_commitments_with_generators(&mut commitments, &data, &generators);
+    let expected: Vec<bn254_g1_affine> = commitments
+        .iter()
+        .zip(&r)
+        .map(|(c, r)| (*c + h * r).into_affine())
+        .collect();
+    rerandomize_bn254_g1_uncompressed_commitments(&mut commitments, &r, &h);
+    assert_eq!(commitments, expected);
+}
+
+#[test]
+#[cfg(all(feature = "bls12_381", feature = "bn254", feature = "grumpkin"))]
+fn empty_inputs_commit_to_the_identity() {
+    let empty: Vec<u64> = Vec::new();
+    let data: Vec<Sequence> = vec![(&empty).into(), (&empty).into()];
+
+    let mut commitments = vec![CompressedRistretto::from_slice(&[1; 32]).unwrap(); 2];
+    compute_curve25519_commitments(&mut commitments, &data, 3);
+    assert_eq!(commitments, vec![CompressedRistretto::default(); 2]);
+    compute_curve25519_commitments(&mut [], &[], 3);
+    unsafe { compute_curve25519_commitments_unchecked(&mut commitments, &data, 3) };
+    assert_eq!(commitments, vec![CompressedRistretto::default(); 2]);
+
+    let mut commitments = vec![CompressedRistretto::from_slice(&[1; 32]).unwrap(); 2];
+    compute_curve25519_commitments_with_generators(&mut commitments, &data, &[]);
+    assert_eq!(commitments, vec![CompressedRistretto::default(); 2]);
+    update_curve25519_commitments(&mut commitments, &data, 0);
+    assert_eq!(commitments, vec![CompressedRistretto::default(); 2]);
+
+    let mut commitments = vec![[1_u8; 48]; 2];
+    compute_bls12_381_g1_commitments_with_generators(&mut commitments, &data, &[]);
+    let mut identity = [0_u8; 48];
+    G1Affine::identity()
+        .serialize_compressed(&mut identity[..])
+        .unwrap();
+    assert_eq!(commitments, vec![identity; 2]);
+
+    let mut rng = ark_std::test_rng();
+    let mut commitments = vec![bn254_g1_affine::rand(&mut rng); 2];
+    compute_bn254_g1_uncompressed_commitments_with_generators(&mut commitments, &data, &[]);
+    assert_eq!(commitments, vec![bn254_g1_affine::identity(); 2]);
+
+    let mut commitments = vec![grumpkin_affine::rand(&mut rng); 2];
+    compute_grumpkin_uncompressed_commitments_with_generators(&mut commitments, &data, &[]);
+    assert_eq!(commitments, vec![grumpkin_affine::identity(); 2]);
+
+    let mut commitments = vec![CompressedRistretto::from_slice(&[1; 32]).unwrap(); 2];
+    let generators: Vec<RistrettoPoint> =
+        (0..2).map(|_| RistrettoPoint::random(&mut OsRng)).collect();
+    compute_curve25519_commitments_with_handle(
+        &mut commitments,
+        &data,
+        &MsmHandle::new(&generators),
+    );
+    assert_eq!(commitments, vec![CompressedRistretto::default(); 2]);
+
+    let generators: Vec<G1Affine> = (0..2).map(|_| G1Affine::rand(&mut rng)).collect();
+    let mut commitments = vec![[1_u8; 48]; 2];
+    compute_bls12_381_g1_commitments_with_handle(
+        &mut commitments,
+        &data,
+        &MsmHandle::new_with_affine(&generators),
+    );
+    assert_eq!(commitments, vec![identity; 2]);
+
+    let generators: Vec<bn254_g1_affine> =
+        (0..2).map(|_| bn254_g1_affine::rand(&mut rng)).collect();
+    let mut commitments = vec![generators[0]; 2];
+    compute_bn254_g1_uncompressed_commitments_with_handle(
+        &mut commitments,
+        &data,
+        &MsmHandle::new_with_affine(&generators),
+    );
+    assert_eq!(commitments, vec![bn254_g1_affine::identity(); 2]);
+
+    let generators: Vec<grumpkin_affine> =
+        (0..2).map(|_| grumpkin_affine::rand(&mut rng)).collect();
+    let mut commitments = vec![generators[0]; 2];
+    compute_grumpkin_uncompressed_commitments_with_handle(
+        &mut commitments,
+        &data,
+        &MsmHandle::new_with_affine(&generators),
+    );
+    assert_eq!(commitments, vec![grumpkin_affine::identity(); 2]);
+
+    let mut generators: Vec<RistrettoPoint> = Vec::new();
+    get_curve25519_generators(&mut generators, 0);
+    assert_eq!(
+        compute_curve25519_commitment_chunked(&[], 4, false, 0, 8),
+        CompressedRistretto::default()
+    );
+}
+
+#[test]
+#[cfg(feature = "bls12_381")]
+fn we_can_compute_typed_compressed_bls12_381_commitments() {
+    let mut rng = ark_std::test_rng();
+    let data_a: Vec<u64> = vec![2, 3, 1, 5];
+    let data_b: Vec<i32> = vec![-3, 1];
+    let data: Vec<Sequence> = vec![(&data_a).into(), (&data_b).into()];
+    let generators: Vec<G1Affine> = (0..4).map(|_| G1Affine::rand(&mut rng)).collect();
+
+    let mut expected = vec![[0_u8; 48]; 2];
+    compute_bls12_381_g1_commitments_with_generators(&mut expected, &data, &generators);
+    let mut commitments = vec![CompressedG1Bls12_381::default(); 2];
+    compute_bls12_381_g1_compressed_commitments_with_generators(
+        &mut commitments,
+        &data,
+        &generators,
+    );
+    assert_eq!(commitments[0].as_bytes(), &expected[0]);
+    assert_eq!(commitments[1].as_bytes(), &expected[1]);
+
+    let expected_point = G1Projective::msm(&generators, &[2, 3, 1, 5].map(Fr::from))
+        .unwrap()
+        .into_affine();
+    assert_eq!(commitments[0].decompress().unwrap(), expected_point);
+    assert_eq!(CompressedG1Bls12_381::from(expected_point), commitments[0]);
+    assert_eq!(
+        G1Affine::try_from(CompressedG1Bls12_381::default()).unwrap(),
+        G1Affine::default()
+    );
+    assert!(CompressedG1Bls12_381([0xff; 48]).decompress().is_err());
+}
+
+#[test]
+#[cfg(all(feature = "arkworks", feature = "bls12_381"))]
+fn we_can_serialize_typed_compressed_bls12_381_commitments_with_arkworks() {
+    use ark_serialize::CanonicalDeserialize;
+
+    let point = CompressedG1Bls12_381::from(G1Affine::rand(&mut ark_std::test_rng()));
+    let mut bytes = Vec::new();
+    point.serialize_compressed(&mut bytes).unwrap();
+    assert_eq!(&bytes[..], point.as_bytes());
+    assert_eq!(
+        CompressedG1Bls12_381::deserialize_compressed(&bytes[..]).unwrap(),
+        point
+    );
+
+    let invalid = [0xff_u8; 48];
+    assert!(CompressedG1Bls12_381::deserialize_compressed(&invalid[..]).is_err());
+    assert_eq!(
+        CompressedG1Bls12_381::deserialize_compressed_unchecked(&invalid[..]).unwrap(),
+        CompressedG1Bls12_381(invalid)
+    );
+}
+
+#[test]
+fn we_can_compute_commitments_with_the_fallible_functions() {
+    use crate::error::{Error, InvalidInputError};
+
+    let data_a: Vec<u64> = vec![2, 3, 1, 5];
+    let data_b: Vec<i32> = vec![-3, 1];
+    let data: Vec<Sequence> = vec![(&data_a).into(), (&data_b).into()];
+
+    let mut expected = vec![CompressedRistretto::default(); 2];
+    compute_curve25519_commitments(&mut expected, &data, 1);
+    let mut commitments = vec![CompressedRistretto::default(); 2];
+    try_compute_curve25519_commitments(&mut commitments, &data, 1).unwrap();
+    assert_eq!(commitments, expected);
+
+    let generators: Vec<RistrettoPoint> =
+        (0..4).map(|_| RistrettoPoint::random(&mut OsRng)).collect();
+    compute_curve25519_commitments_with_generators(&mut expected, &data, &generators);
+    try_compute_curve25519_commitments_with_generators(&mut commitments, &data, &generators)
+        .unwrap();
+    assert_eq!(commitments, expected);
+
+    assert!(matches!(
+        try_compute_curve25519_commitments(&mut commitments[..1], &data, 1),
+        Err(Error::InvalidInput(InvalidInputError::LengthMismatch {
+            name: "commitments",
+            expected: 2,
+            actual: 1
+        }))
+    ));
+    assert!(matches!(
+        try_compute_curve25519_commitments_with_generators(
+            &mut commitments,
+            &data,
+            &generators[..3]
+        ),
+        Err(Error::InvalidInput(
+            InvalidInputError::NotEnoughGenerators {
+                index: 0,
+                sequence_len: 4,
+                num_generators: 3
+            }
+        ))
+    ));
+}
+
+#[test]
+#[cfg(all(feature = "bls12_381", feature = "bn254", feature = "grumpkin"))]
+fn we_can_compute_short_weierstrass_commitments_with_the_fallible_functions() {
+    use crate::error::{Error, InvalidInputError};
+
+    let mut rng = ark_std::test_rng();
+    let data_a: Vec<u64> = vec![2, 3, 1, 5];
+    let data_b: Vec<i32> = vec![-3, 1];
+    let data: Vec<Sequence> = vec![(&data_a).into(), (&data_b).into()];
+
+    let generators: Vec<G1Affine> = (0..4).map(|_| G1Affine::rand(&mut rng)).collect();
+    let mut expected = vec![[0_u8; 48]; 2];
+    compute_bls12_381_g1_commitments_with_generators(&mut expected, &data, &generators);
+    let mut commitments = vec![[0_u8; 48]; 2];
+    try_compute_bls12_381_g1_commitments_with_generators(&mut commitments, &data, &generators)
+        .unwrap();
+    assert_eq!(commitments, expected);
+    assert!(matches!(
+        try_compute_bls12_381_g1_commitments_with_generators(
+            &mut commitments,
+            &data,
+            &generators[..3]
+        ),
+        Err(Error::InvalidInput(
+            InvalidInputError::NotEnoughGenerators { index: 0, .. }
+        ))
+    ));
+
+    let generators: Vec<bn254_g1_affine> =
+        (0..4).map(|_| bn254_g1_affine::rand(&mut rng)).collect();
+    let mut expected = vec![bn254_g1_affine::default(); 2];
+    compute_bn254_g1_uncompressed_commitments_with_generators(&mut expected, &data, &generators);
+    let mut commitments = vec![bn254_g1_affine::default(); 2];
+    try_compute_bn254_g1_uncompressed_commitments_with_generators(
+        &mut commitments,
+        &data,
+        &generators,
+    )
+    .unwrap();
+    assert_eq!(commitments, expected);
+    assert!(matches!(
+        try_compute_bn254_g1_uncompressed_commitments_with_generators(
+            &mut commitments[..1],
+            &data,
+            &generators
+        ),
+        Err(Error::InvalidInput(InvalidInputError::LengthMismatch {
+            name: "commitments",
+            expected: 2,
+            actual: 1
+        }))
+    ));
+
+    let generators: Vec<grumpkin_affine> =
+        (0..4).map(|_| grumpkin_affine::rand(&mut rng)).collect();
+    let mut expected = vec![grumpkin_affine::default(); 2];
+    compute_grumpkin_uncompressed_commitments_with_generators(&mut expected, &data, &generators);
+    let mut commitments = vec![grumpkin_affine::default(); 2];
+    try_compute_grumpkin_uncompressed_commitments_with_generators(
+        &mut commitments,
+        &data,
+        &generators,
+    )
+    .unwrap();
+    assert_eq!(commitments, expected);
+}
+
+#[test]
+#[cfg(all(feature = "bls12_381", feature = "bn254", feature = "grumpkin"))]
+fn short_weierstrass_commitments_split_into_chunks_of_rows_match_a_single_call() {
+    use super::commitments::{
+        compute_bls12_381_g1_commitments_with_generators_impl,
+        compute_bn254_g1_uncompressed_commitments_with_generators_impl,
+        compute_grumpkin_uncompressed_commitments_with_generators_impl,
+    };
+    use std::mem::MaybeUninit;
+
+    let mut rng = ark_std::test_rng();
+    let data_a: Vec<u64> = (0..20).map(|i| i * 3 + 1).collect();
+    let data_b: Vec<i16> = (0..11).map(|i| 5 - i).collect();
+    let validity_b = [0b1101_0110_u8, 0b101];
+    let data: Vec<Sequence> = vec![
+        (&data_a).into(),
+        Sequence::from(&data_b).with_validity(&validity_b),
+    ];
+    init_backend();
+
+    let generators: Vec<G1Affine> = (0..20).map(|_| G1Affine::rand(&mut rng)).collect();
+    let mut whole = vec![MaybeUninit::uninit(); 2];
+    let mut chunked = vec![MaybeUninit::uninit(); 2];
+    unsafe {
+        compute_bls12_381_g1_commitments_with_generators_impl(&mut whole, &data, &generators, None);
+        compute_bls12_381_g1_commitments_with_generators_impl(
+            &mut chunked,
+            &data,
+            &generators,
+            Some(8),
+        );
+        assert_eq!(
+            commitments::assume_init_mut(&mut whole),
+            commitments::assume_init_mut(&mut chunked)
+        );
+    }
+
+    let generators: Vec<bn254_g1_affine> =
+        (0..20).map(|_| bn254_g1_affine::rand(&mut rng)).collect();
+    let mut whole = vec![MaybeUninit::uninit(); 2];
+    let mut chunked = vec![MaybeUninit::uninit(); 2];
+    unsafe {
+        compute_bn254_g1_uncompressed_commitments_with_generators_impl(
+            &mut whole,
+            &data,
+            &generators,
+            None,
+        );
+        compute_bn254_g1_uncompressed_commitments_with_generators_impl(
+            &mut chunked,
+            &data,
+            &generators,
+            Some(8),
+        );
+        assert_eq!(
+            commitments::assume_init_mut(&mut whole),
+            commitments::assume_init_mut(&mut chunked)
+        );
+    }
+
+    let generators: Vec<grumpkin_affine> =
+        (0..20).map(|_| grumpkin_affine::rand(&mut rng)).collect();
+    let mut whole = vec![MaybeUninit::uninit(); 2];
+    let mut chunked = vec![MaybeUninit::uninit(); 2];
+    unsafe {
+        compute_grumpkin_uncompressed_commitments_with_generators_impl(
+            &mut whole,
+            &data,
+            &generators,
+            None,
+        );
+        compute_grumpkin_uncompressed_commitments_with_generators_impl(
+            &mut chunked,
+            &data,
+            &generators,
+            Some(8),
+        );
+        assert_eq!(
+            commitments::assume_init_mut(&mut whole),
+            commitments::assume_init_mut(&mut chunked)
+        );
+    }
+}
+
+#[test]
+#[cfg(all(feature = "bls12_381", feature = "bn254", feature = "grumpkin"))]
+fn null_elements_are_committed_as_zero() {
+    let data_a: Vec<u64> = vec![2, 3, 1, 5, 4, 7, 6, 8, 9];
+    let data_b: Vec<i16> = vec![-3, 1, i16::MIN, 7];
+    let data_c: Vec<u8> = vec![1, 2, 3];
+    let validity_a = [0b1011_0110_u8, 0b0];
+    let validity_b = [0b1010_u8];
+    let zeroed_a: Vec<u64> = vec![0, 3, 1, 0, 4, 7, 0, 8, 0];
+    let zeroed_b: Vec<i16> = vec![0, 1, 0, 7];
+    let data: Vec<Sequence> = vec![
+        Sequence::from(&data_a).with_validity(&validity_a),
+        Sequence::from(&data_b).with_validity(&validity_b),
+        (&data_c).into(),
+    ];
+    let zeroed: Vec<Sequence> = vec![(&zeroed_a).into(), (&zeroed_b).into(), (&data_c).into()];
+
+    let mut commitments = vec![CompressedRistretto::default(); 3];
+    let mut expected = vec![CompressedRistretto::default(); 3];
+    compute_curve25519_commitments(&mut commitments, &data, 4);
+    compute_curve25519_commitments(&mut expected, &zeroed, 4);
+    assert_eq!(commitments, expected);
+
+    let generators: Vec<RistrettoPoint> =
+        (0..9).map(|_| RistrettoPoint::random(&mut OsRng)).collect();
+    compute_curve25519_commitments_with_generators(&mut commitments, &data, &generators);
+    compute_curve25519_commitments_with_generators(&mut expected, &zeroed, &generators);
+    assert_eq!(commitments, expected);
+    let handle = MsmHandle::new(&generators);
+    compute_curve25519_commitments_with_handle(&mut commitments, &data, &handle);
+    assert_eq!(commitments, expected);
+
+    let mut rng = ark_std::test_rng();
+    let generators: Vec<G1Affine> = (0..9).map(|_| G1Affine::rand(&mut rng)).collect();
+    let mut commitments = vec![[0_u8; 48]; 3];
+    let mut expected = vec![[0_u8; 48]; 3];
+    compute_bls12_381_g1_commitments_with_generators(&mut commitments, &data, &generators);
+    compute_bls12_381_g1_commitments_with_generators(&mut expected, &zeroed, &generators);
+    assert_eq!(commitments, expected);
+
+    let generators: Vec<bn254_g1_affine> =
+        (0..9).map(|_| bn254_g1_affine::rand(&mut rng)).collect();
+    let mut commitments = vec![bn254_g1_affine::default(); 3];
+    let mut expected = vec![bn254_g1_affine::default(); 3];
+    compute_bn254_g1_uncompressed_commitments_with_generators(&mut commitments, &data, &generators);
+    compute_bn254_g1_uncompressed_commitments_with_generators(&mut expected, &zeroed, &generators);
+    assert_eq!(commitments, expected);
+
+    let generators: Vec<grumpkin_affine> =
+        (0..9).map(|_| grumpkin_affine::rand(&mut rng)).collect();
+    let mut commitments = vec![grumpkin_affine::default(); 3];
+    let mut expected = vec![grumpkin_affine::default(); 3];
+    compute_grumpkin_uncompressed_commitments_with_generators(&mut commitments, &data, &generators);
+    compute_grumpkin_uncompressed_commitments_with_generators(&mut expected, &zeroed, &generators);
+    assert_eq!(commitments, expected);
+}
+
+#[test]
+fn we_can_detect_small_batches() {
+    let data_a: Vec<u8> = vec![1, 2];
+    let data_b: Vec<u16> = vec![1, 2, 3];
+    let data: Vec<Sequence> = vec![(&data_a).into(), (&data_b).into()];
+    assert!(!is_small_batch_with_threshold(&data, 0));
+    assert!(!is_small_batch_with_threshold(&data, 4));
+    assert!(is_small_batch_with_threshold(&data, 5));
+    assert!(is_small_batch_with_threshold(&data, 100));
+}
+
+#[test]
+#[cfg(all(feature = "bls12_381", feature = "bn254", feature = "grumpkin"))]
+fn small_batches_committed_on_the_host_match_the_backend() {
+    let data_a: Vec<u64> = vec![2, 3, 1, 5, 4, 7, 6, 8, 9];
+    let data_b: Vec<i16> = vec![-3, 1, i16::MIN, 7];
+    let validity_a = [0b1011_0110_u8, 0b1];
+    let data: Vec<Sequence> = vec![
+        Sequence::from(&data_a).with_validity(&validity_a),
+        (&data_b).into(),
+    ];
+    assert!(is_small_batch_with_threshold(&data, 13));
+
+    // the backend is used by default, and the reference functions are the host path
+    let generators: Vec<RistrettoPoint> =
+        (0..9).map(|_| RistrettoPoint::random(&mut OsRng)).collect();
+    let mut commitments = vec![CompressedRistretto::default(); 2];
+    let mut expected = vec![CompressedRistretto::default(); 2];
+    compute_curve25519_commitments_with_generators(&mut commitments, &data, &generators);
+    reference::compute_curve25519_commitments_with_generators(&mut expected, &data, &generators);
+    assert_eq!(commitments, expected);
+
+    let mut rng = ark_std::test_rng();
+    let generators: Vec<G1Affine> = (0..9).map(|_| G1Affine::rand(&mut rng)).collect();
+    let mut commitments = vec![[0_u8; 48]; 2];
+    let mut expected = vec![[0_u8; 48]; 2];
+    compute_bls12_381_g1_commitments_with_generators(&mut commitments, &data, &generators);
+    reference::compute_bls12_381_g1_commitments_with_generators(&mut expected, &data, &generators);
+    assert_eq!(commitments, expected);
+
+    let generators: Vec<bn254_g1_affine> =
+        (0..9).map(|_| bn254_g1_affine::rand(&mut rng)).collect();
+    let mut commitments = vec![bn254_g1_affine::default(); 2];
+    let mut expected = vec![bn254_g1_affine::default(); 2];
+    compute_bn254_g1_uncompressed_commitments_with_generators(&mut commitments, &data, &generators);
+    reference::compute_bn254_g1_uncompressed_commitments_with_generators(
+        &mut expected,
+        &data,
+        &generators,
+    );
+    assert_eq!(commitments, expected);
+
+    let generators: Vec<grumpkin_affine> =
+        (0..9).map(|_| grumpkin_affine::rand(&mut rng)).collect();
+    let mut commitments = vec![grumpkin_affine::default(); 2];
+    let mut expected = vec![grumpkin_affine::default(); 2];
+    compute_grumpkin_uncompressed_commitments_with_generators(&mut commitments, &data, &generators);
+    reference::compute_grumpkin_uncompressed_commitments_with_generators(
+        &mut expected,
+        &data,
+        &generators,
+    );
+    assert_eq!(commitments, expected);
+}
+
+#[test]
+fn we_can_check_the_inputs_of_a_commitment_computation() {
+    use crate::error::InvalidInputError;
+
+    let data_a: Vec<u8> = vec![1, 2];
+    let data_b: Vec<u16> = vec![1, 2, 3];
+    let data: Vec<Sequence> = vec![(&data_a).into(), (&data_b).into()];
+    assert!(check_inputs(2, &data, None).is_ok());
+    assert!(check_inputs(2, &data, Some(3)).is_ok());
+    assert!(matches!(
+        check_inputs(1, &data, None),
+        Err(InvalidInputError::LengthMismatch { .. })
+    ));
+    assert!(matches!(
+        check_inputs(2, &data, Some(2)),
+        Err(InvalidInputError::NotEnoughGenerators {
+            index: 1,
+            sequence_len: 3,
+            num_generators: 2
+        })
+    ));
+}
+
+#[test]
+#[should_panic(expected = "sequence 1 has 3 elements but only 2 generators are available")]
+fn the_panic_message_names_the_sequence_that_is_longer_than_the_generators() {
+    let data_a: Vec<u8> = vec![1, 2];
+    let data_b: Vec<u16> = vec![1, 2, 3];
+    let data: Vec<Sequence> = vec![(&data_a).into(), (&data_b).into()];
+    let generators: Vec<RistrettoPoint> =
+        (0..2).map(|_| RistrettoPoint::random(&mut OsRng)).collect();
+    let mut commitments = vec![CompressedRistretto::default(); 2];
+    compute_curve25519_commitments_with_generators(&mut commitments, &data, &generators);
+}
+
+#[test]
+fn we_can_compute_commitments_of_an_iterator_of_sequences() {
+    let columns: Vec<Vec<u64>> = vec![vec![1, 2, 3], vec![], vec![7, 8]];
+    let data: Vec<Sequence> = columns.iter().map(Sequence::from).collect();
+    let mut expected = vec![CompressedRistretto::default(); 3];
+    compute_curve25519_commitments(&mut expected, &data, 4);
+
+    let commitments =
+        compute_curve25519_commitments_from_iter(columns.iter().map(Sequence::from), 4);
+    assert_eq!(commitments, expected);
+    assert!(compute_curve25519_commitments_from_iter([], 0).is_empty());
+}
+
+#[test]
+#[cfg(feature = "bn254")]
+fn we_can_compute_commitments_through_the_per_curve_modules() {
+    let data: Vec<u64> = vec![1, 2, 3];
+    let sequences = [Sequence::from(&data)];
+    let mut expected = [CompressedRistretto::default()];
+    compute_curve25519_commitments(&mut expected, &sequences, 2);
+    let mut commitments = [CompressedRistretto::default()];
+    crate::compute::curve25519::compute_commitments(&mut commitments, &sequences, 2);
+    assert_eq!(commitments, expected);
+
+    let generators: Vec<bn254_g1_affine> =
+        (0..3).map(|_| bn254_g1_affine::rand(&mut OsRng)).collect();
+    let mut expected = [bn254_g1_affine::default()];
+    compute_bn254_g1_uncompressed_commitments_with_generators(
+        &mut expected,
+        &sequences,
+        &generators,
+    );
+    let mut commitments = [bn254_g1_affine::default()];
+    crate::compute::bn254::compute_commitments_with_generators(
+        &mut commitments,
+        &sequences,
+        &generators,
+    );
+    assert_eq!(commitments, expected);
+}
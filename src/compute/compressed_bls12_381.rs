@@ -0,0 +1,129 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::commitments::compute_bls12_381_g1_commitments_with_generators;
+use crate::sequence::Sequence;
+use ark_bls12_381::G1Affine;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+
+/// A `bls12-381` `G1` point in the 48 byte compressed encoding used by arkworks.
+///
+/// The newtype keeps compressed points from being confused with other byte arrays. Its
+/// layout is the same as `[u8; 48]`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct CompressedG1Bls12_381(pub [u8; 48]);
+
+impl CompressedG1Bls12_381 {
+    /// Decompresses the point, checking that it is on the curve and in the prime order
+    /// subgroup.
+    pub fn decompress(&self) -> Result<G1Affine, SerializationError> {
+        G1Affine::deserialize_compressed(&self.0[..])
+    }
+
+    /// Returns the bytes of the compressed point.
+    pub fn as_bytes(&self) -> &[u8; 48] {
+        &self.0
+    }
+}
+
+impl Default for CompressedG1Bls12_381 {
+    /// Returns the compressed identity.
+    fn default() -> Self {
+        G1Affine::identity().into()
+    }
+}
+
+impl From<G1Affine> for CompressedG1Bls12_381 {
+    fn from(point: G1Affine) -> Self {
+        let mut bytes = [0_u8; 48];
+        point
+            .serialize_compressed(&mut bytes[..])
+            .expect("a compressed bls12-381 G1 point is 48 bytes");
+        Self(bytes)
+    }
+}
+
+impl TryFrom<CompressedG1Bls12_381> for G1Affine {
+    type Error = SerializationError;
+
+    fn try_from(point: CompressedG1Bls12_381) -> Result<Self, Self::Error> {
+        point.decompress()
+    }
+}
+
+impl From<[u8; 48]> for CompressedG1Bls12_381 {
+    fn from(bytes: [u8; 48]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<CompressedG1Bls12_381> for [u8; 48] {
+    fn from(point: CompressedG1Bls12_381) -> Self {
+        point.0
+    }
+}
+
+#[cfg(feature = "arkworks")]
+impl ark_serialize::Valid for CompressedG1Bls12_381 {
+    /// Checks that the point can be decompressed.
+    fn check(&self) -> Result<(), SerializationError> {
+        self.decompress().map(|_| ())
+    }
+}
+
+#[cfg(feature = "arkworks")]
+impl CanonicalSerialize for CompressedG1Bls12_381 {
+    /// Writes the 48 bytes of the compressed point, in either mode.
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        mut writer: W,
+        _compress: ark_serialize::Compress,
+    ) -> Result<(), SerializationError> {
+        writer.write_all(&self.0)?;
+        Ok(())
+    }
+
+    fn serialized_size(&self, _compress: ark_serialize::Compress) -> usize {
+        48
+    }
+}
+
+#[cfg(feature = "arkworks")]
+impl CanonicalDeserialize for CompressedG1Bls12_381 {
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        mut reader: R,
+        _compress: ark_serialize::Compress,
+        validate: ark_serialize::Validate,
+    ) -> Result<Self, SerializationError> {
+        use ark_serialize::Valid;
+        let mut point = Self([0_u8; 48]);
+        reader.read_exact(&mut point.0)?;
+        if validate == ark_serialize::Validate::Yes {
+            point.check()?;
+        }
+        Ok(point)
+    }
+}
+
+/// Same as [compute_bls12_381_g1_commitments_with_generators], with the commitments
+/// typed as [CompressedG1Bls12_381].
+pub fn compute_bls12_381_g1_compressed_commitments_with_generators(
+    commitments: &mut [CompressedG1Bls12_381],
+    data: &[Sequence],
+    generators: &[G1Affine],
+) {
+    // SAFETY: CompressedG1Bls12_381 is a repr(transparent) wrapper of [u8; 48]
+    let raw = unsafe { &mut *(commitments as *mut [CompressedG1Bls12_381] as *mut [[u8; 48]]) };
+    compute_bls12_381_g1_commitments_with_generators(raw, data, generators);
+}
@@ -1,30 +1,131 @@
 use crate::compute::ElementP2;
-use curve25519_dalek::ristretto::RistrettoPoint;
+use ark_ec::{short_weierstrass::Affine, AffineRepr, CurveGroup};
+use ark_ff::AdditiveGroup;
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
 
-pub trait SwCurveConfig: ark_ec::short_weierstrass::SWCurveConfig {
+mod sealed {
+    /// Prevents implementations of the curve traits outside of this crate.
+    ///
+    /// The backend identifies curves by a fixed list of ids (`blitzar_sys::SXT_CURVE_*`)
+    /// and has no way to register curve parameters at runtime, so only the curves it
+    /// ships with can be supported. Adding a curve requires backend support first.
+    pub trait Sealed {}
+
+    #[cfg(feature = "bls12_381")]
+    impl Sealed for ark_bls12_381::g1::Config {}
+    #[cfg(feature = "bn254")]
+    impl Sealed for ark_bn254::g1::Config {}
+    #[cfg(feature = "grumpkin")]
+    impl Sealed for ark_grumpkin::GrumpkinConfig {}
+    impl Sealed for curve25519_dalek::ristretto::RistrettoPoint {}
+    impl<C: super::SwCurveConfig> Sealed for super::ElementP2<C> {}
+}
+
+/// Short Weierstrass curves supported by the backend.
+///
+/// This trait is sealed and implemented for the curves enabled by cargo features:
+/// - `ark_bls12_381::g1::Config` (feature `bls12_381`)
+/// - `ark_bn254::g1::Config` (feature `bn254`)
+/// - `ark_grumpkin::GrumpkinConfig` (feature `grumpkin`)
+///
+/// It can be used as a bound to write code that is generic over these curves, e.g. over
+/// `MsmHandle<ElementP2<C>>`.
+pub trait SwCurveConfig: ark_ec::short_weierstrass::SWCurveConfig + sealed::Sealed {
+    /// Id of the curve in the backend, one of `blitzar_sys::SXT_CURVE_*`.
     const CURVE_ID: u32;
 }
 
+#[cfg(feature = "bls12_381")]
 impl SwCurveConfig for ark_bls12_381::g1::Config {
     const CURVE_ID: u32 = blitzar_sys::SXT_CURVE_BLS_381;
 }
 
+#[cfg(feature = "bn254")]
 impl SwCurveConfig for ark_bn254::g1::Config {
     const CURVE_ID: u32 = blitzar_sys::SXT_CURVE_BN_254;
 }
 
+#[cfg(feature = "grumpkin")]
 impl SwCurveConfig for ark_grumpkin::GrumpkinConfig {
     const CURVE_ID: u32 = blitzar_sys::SXT_CURVE_GRUMPKIN;
 }
 
-pub trait CurveId {
+/// Curve elements that can be used with `MsmHandle`.
+///
+/// This trait is sealed and implemented for:
+/// - `curve25519_dalek::ristretto::RistrettoPoint`
+/// - `ElementP2<C>` for every enabled [SwCurveConfig] `C`
+///
+/// It can be used as a bound to write code that is generic over the curves of the
+/// backend:
+///
+/// ```
+/// use blitzar::compute::{CurveId, MsmHandle};
+///
+/// /// Returns the sum of the generators of `handle`.
+/// fn sum_of_generators<T: CurveId + Clone>(handle: &MsmHandle<T>) -> T {
+///     let scalars = vec![1_u8; handle.num_generators()];
+///     handle.msm_sum(1, 1, &scalars)
+/// }
+/// ```
+pub trait CurveId: sealed::Sealed + Sized {
+    /// Id of the curve in the backend, one of `blitzar_sys::SXT_CURVE_*`.
     const CURVE_ID: u32;
+
+    /// Returns the identity element, i.e. the result of an empty MSM.
+    fn identity_element() -> Self;
+
+    /// Returns `raw - 2^num_bits * sign`.
+    ///
+    /// This turns the MSM of the raw bits of two's complement scalars into the MSM of
+    /// their signed values, given the MSM of their sign bits.
+    #[doc(hidden)]
+    fn sub_shifted(raw: &Self, sign: &Self, num_bits: u32) -> Self;
+
+    /// Returns `low + 2^num_bits * high`.
+    #[doc(hidden)]
+    fn add_shifted(low: &Self, high: &Self, num_bits: u32) -> Self;
 }
 
 impl CurveId for RistrettoPoint {
     const CURVE_ID: u32 = blitzar_sys::SXT_CURVE_RISTRETTO255;
+
+    fn identity_element() -> Self {
+        RistrettoPoint::default()
+    }
+
+    fn sub_shifted(raw: &Self, sign: &Self, num_bits: u32) -> Self {
+        let mut shift = [0_u8; 64];
+        shift[num_bits as usize / 8] = 1 << (num_bits % 8);
+        raw - Scalar::from_bytes_mod_order_wide(&shift) * sign
+    }
+
+    fn add_shifted(low: &Self, high: &Self, num_bits: u32) -> Self {
+        let mut shift = [0_u8; 64];
+        shift[num_bits as usize / 8] = 1 << (num_bits % 8);
+        low + Scalar::from_bytes_mod_order_wide(&shift) * high
+    }
 }
 
 impl<C: SwCurveConfig> CurveId for ElementP2<C> {
     const CURVE_ID: u32 = C::CURVE_ID;
+
+    fn identity_element() -> Self {
+        ElementP2::default()
+    }
+
+    fn sub_shifted(raw: &Self, sign: &Self, num_bits: u32) -> Self {
+        let neg_sign: Self = (-Affine::<C>::from(sign)).into();
+        Self::add_shifted(raw, &neg_sign, num_bits)
+    }
+
+    fn add_shifted(low: &Self, high: &Self, num_bits: u32) -> Self {
+        let mut shifted = Affine::<C>::from(high).into_group();
+        for _ in 0..num_bits {
+            shifted.double_in_place();
+        }
+        (Affine::<C>::from(low).into_group() + shifted)
+            .into_affine()
+            .into()
+    }
 }
@@ -0,0 +1,58 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! commitments and MSMs on the ristretto255 group of curve25519
+//!
+//! The functions of this module are the `*_curve25519_*` functions of [crate::compute]
+//! without the curve in their names.
+//!
+//! ```no_run
+//! use blitzar::compute::curve25519;
+//! use curve25519_dalek::ristretto::CompressedRistretto;
+//!
+//! let data: Vec<u32> = vec![1, 2, 3];
+//! let mut commitments = [CompressedRistretto::default()];
+//! curve25519::compute_commitments(&mut commitments, &[(&data).into()], 0);
+//! ```
+
+pub use super::{
+    batch_compress_ristretto as batch_compress, batch_decompress_ristretto as batch_decompress,
+    batch_scalar_mul_curve25519 as batch_scalar_mul,
+    compressed_ristretto_to_edwards as compressed_to_edwards,
+    compute_curve25519_commitment_chunked as compute_commitment_chunked,
+    compute_curve25519_commitment_chunked_cancellable as compute_commitment_chunked_cancellable,
+    compute_curve25519_commitment_chunked_with_progress as compute_commitment_chunked_with_progress,
+    compute_curve25519_commitment_from_file as compute_commitment_from_file,
+    compute_curve25519_commitments as compute_commitments,
+    compute_curve25519_commitments_from_iter as compute_commitments_from_iter,
+    compute_curve25519_commitments_unchecked as compute_commitments_unchecked,
+    compute_curve25519_commitments_uninit as compute_commitments_uninit,
+    compute_curve25519_commitments_with_generators as compute_commitments_with_generators,
+    compute_curve25519_commitments_with_generators_unchecked as compute_commitments_with_generators_unchecked,
+    compute_curve25519_commitments_with_generators_uninit as compute_commitments_with_generators_uninit,
+    compute_curve25519_commitments_with_handle as compute_commitments_with_handle,
+    compute_curve25519_commitments_with_selection as compute_commitments_with_selection,
+    compute_curve25519_i256_commitments as compute_i256_commitments,
+    delta_update_curve25519_commitments as delta_update_commitments,
+    get_curve25519_generators as get_generators, get_curve25519_range_commit as get_range_commit,
+    get_one_curve25519_commit as get_one_commit,
+    rerandomize_curve25519_commitments as rerandomize_commitments,
+    ristretto_to_edwards as to_edwards,
+    try_compute_curve25519_commitments as try_compute_commitments,
+    try_compute_curve25519_commitments_with_generators as try_compute_commitments_with_generators,
+    update_curve25519_commitments as update_commitments, Curve25519 as Scheme,
+};
+
+/// Handle to compute MSMs with pre-specified ristretto255 generators
+pub type MsmHandle = super::MsmHandle<curve25519_dalek::ristretto::RistrettoPoint>;
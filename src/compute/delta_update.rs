@@ -0,0 +1,121 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::{
+    commitments::compute_curve25519_commitments_with_generators, get_curve25519_generators,
+};
+use crate::sequence::Sequence;
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+};
+use rayon::prelude::*;
+
+/// A change of the value of one row of a column.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RowUpdate {
+    /// Index of the row in the column, not counting `offset_generators`.
+    pub row: u64,
+    /// Value of the row that was committed.
+    pub old_value: Scalar,
+    /// Value the row now holds.
+    pub new_value: Scalar,
+}
+
+/// Updates `curve25519` commitments to reflect changes of individual rows.
+///
+/// For each column `j` and each update `u` of `updates[j]`, the commitment is updated as
+///
+/// ```text
+/// commitments[j] += (u.new_value - u.old_value) * g[offset_generators + u.row]
+/// ```
+///
+/// where `g` are the generators used by `compute_curve25519_commitments`. Only the
+/// generators of the updated rows are fetched, so the cost is proportional to the number
+/// of updates rather than to the length of the columns. Several updates of the same row
+/// of a column are applied one after the other.
+///
+/// # Panics
+///
+/// If `commitments.len()` is different from `updates.len()`, or if a commitment is not a
+/// valid ristretto point.
+pub fn delta_update_curve25519_commitments(
+    commitments: &mut [CompressedRistretto],
+    updates: &[&[RowUpdate]],
+    offset_generators: u64,
+) {
+    assert_eq!(
+        commitments.len(),
+        updates.len(),
+        "updates must hold one slice for each commitment"
+    );
+
+    let mut rows: Vec<u64> = updates
+        .iter()
+        .flat_map(|updates| updates.iter().map(|u| u.row))
+        .collect();
+    rows.sort_unstable();
+    rows.dedup();
+    if rows.is_empty() {
+        return;
+    }
+
+    let generators = get_row_generators(&rows, offset_generators);
+
+    let deltas: Vec<Vec<Scalar>> = updates
+        .par_iter()
+        .map(|updates| {
+            let mut deltas = vec![Scalar::ZERO; rows.len()];
+            for u in updates.iter() {
+                let index = rows.binary_search(&u.row).expect("row was collected above");
+                deltas[index] += u.new_value - u.old_value;
+            }
+            deltas
+        })
+        .collect();
+    let data: Vec<Sequence> = deltas.iter().map(Into::into).collect();
+    let mut partial_commitments = vec![CompressedRistretto::default(); commitments.len()];
+    compute_curve25519_commitments_with_generators(&mut partial_commitments, &data, &generators);
+
+    commitments
+        .par_iter_mut()
+        .zip(partial_commitments)
+        .for_each(|(c_a, c_b)| {
+            *c_a = (c_a.decompress().unwrap_or_else(|| {
+                panic!(
+                    "invalid ristretto point decompression on delta_update_curve25519_commitments"
+                )
+            }) + c_b.decompress().unwrap_or_else(|| {
+                panic!(
+                    "invalid ristretto point decompression on delta_update_curve25519_commitments"
+                )
+            }))
+            .compress()
+        });
+}
+
+/// Fetches the generators of the sorted, distinct `rows`, with one backend call per run
+/// of consecutive rows.
+pub(crate) fn get_row_generators(rows: &[u64], offset_generators: u64) -> Vec<RistrettoPoint> {
+    let mut generators = vec![RistrettoPoint::default(); rows.len()];
+    let mut start = 0;
+    while start < rows.len() {
+        let mut end = start + 1;
+        while end < rows.len() && rows[end] == rows[end - 1] + 1 {
+            end += 1;
+        }
+        get_curve25519_generators(&mut generators[start..end], offset_generators + rows[start]);
+        start = end;
+    }
+    generators
+}
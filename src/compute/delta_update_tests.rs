@@ -0,0 +1,59 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+use curve25519_dalek::{ristretto::CompressedRistretto, scalar::Scalar};
+
+#[test]
+fn we_can_update_commitments_from_row_changes() {
+    let mut column_a: Vec<u64> = vec![2, 3, 1, 5, 8, 13];
+    let mut column_b: Vec<u64> = vec![7, 0, 4];
+    let mut commitments = vec![CompressedRistretto::default(); 2];
+    compute_curve25519_commitments(
+        &mut commitments,
+        &[(&column_a).into(), (&column_b).into()],
+        5,
+    );
+
+    let update = |column: &mut Vec<u64>, row: usize, new_value: u64| {
+        let old_value = column[row];
+        column[row] = new_value;
+        RowUpdate {
+            row: row as u64,
+            old_value: Scalar::from(old_value),
+            new_value: Scalar::from(new_value),
+        }
+    };
+    let updates_a = [
+        update(&mut column_a, 4, 1),
+        update(&mut column_a, 1, 10),
+        update(&mut column_a, 4, 6),
+        update(&mut column_a, 5, 0),
+    ];
+    let updates_b = [update(&mut column_b, 0, 2)];
+    delta_update_curve25519_commitments(&mut commitments, &[&updates_a, &updates_b], 5);
+
+    let mut expected = vec![CompressedRistretto::default(); 2];
+    compute_curve25519_commitments(&mut expected, &[(&column_a).into(), (&column_b).into()], 5);
+    assert_eq!(commitments, expected);
+}
+
+#[test]
+fn we_can_apply_an_empty_list_of_row_changes() {
+    let column: Vec<u64> = vec![2, 3, 1];
+    let mut commitments = vec![CompressedRistretto::default(); 1];
+    compute_curve25519_commitments(&mut commitments, &[(&column).into()], 0);
+    let expected = commitments.clone();
+    delta_update_curve25519_commitments(&mut commitments, &[&[]], 0);
+    assert_eq!(commitments, expected);
+}
@@ -0,0 +1,92 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::{commitments::compute_commitments_with_handle, ElementP2, MsmHandle, SwMsmHandle};
+use crate::sequence::Sequence;
+use ark_bls12_381::{g1, Bls12_381, G1Affine, G2Affine};
+use ark_ec::pairing::{Pairing, PairingOutput};
+
+/// Computes Dory commitments to matrices over `bls12-381`.
+///
+/// A matrix `M` with rows `M_0, ..., M_{r-1}` is committed to as
+///
+/// ```text
+/// T_i = M_i0 * Gamma_1[0] + M_i1 * Gamma_1[1] + ... + M_i(c-1) * Gamma_1[c-1]
+/// C   = e(T_0, Gamma_2[0]) * e(T_1, Gamma_2[1]) * ... * e(T_{r-1}, Gamma_2[r-1])
+/// ```
+///
+/// The row commitments `T_i` are computed with an [MsmHandle] holding the `Gamma_1`
+/// generators. The pairings are computed on the host with arkworks, as the backend has
+/// no support for `G2` or pairings.
+#[derive(Clone)]
+pub struct DoryCommitmentEngine {
+    gamma_1: MsmHandle<ElementP2<g1::Config>>,
+    gamma_2: Vec<G2Affine>,
+}
+
+impl DoryCommitmentEngine {
+    /// Create an engine from the `Gamma_1` and `Gamma_2` generators.
+    ///
+    /// Matrices may have at most `gamma_1.len()` columns and `gamma_2.len()` rows.
+    pub fn new(gamma_1: &[G1Affine], gamma_2: &[G2Affine]) -> Self {
+        Self::from_handle(MsmHandle::new_with_affine(gamma_1), gamma_2.to_vec())
+    }
+
+    /// Create an engine from an existing handle of `Gamma_1` generators, e.g. one read
+    /// with [MsmHandle::new_from_file], and the `Gamma_2` generators.
+    pub fn from_handle(gamma_1: MsmHandle<ElementP2<g1::Config>>, gamma_2: Vec<G2Affine>) -> Self {
+        Self { gamma_1, gamma_2 }
+    }
+
+    /// The handle of `Gamma_1` generators.
+    pub fn gamma_1(&self) -> &MsmHandle<ElementP2<g1::Config>> {
+        &self.gamma_1
+    }
+
+    /// The `Gamma_2` generators.
+    pub fn gamma_2(&self) -> &[G2Affine] {
+        &self.gamma_2
+    }
+
+    /// Computes the row commitments `T_i` of the matrix with the given `rows`.
+    ///
+    /// Rows may have different lengths, element sizes and signedness.
+    ///
+    /// # Panics
+    ///
+    /// If a row is longer than the number of `Gamma_1` generators.
+    pub fn row_commitments(&self, rows: &[Sequence]) -> Vec<G1Affine> {
+        compute_commitments_with_handle(rows, &self.gamma_1)
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
+
+    /// Computes the Dory commitment `C` of the matrix with the given `rows`, together
+    /// with its row commitments.
+    ///
+    /// # Panics
+    ///
+    /// If there are more rows than `Gamma_2` generators or if a row is longer than the
+    /// number of `Gamma_1` generators.
+    pub fn commit(&self, rows: &[Sequence]) -> (PairingOutput<Bls12_381>, Vec<G1Affine>) {
+        assert!(
+            rows.len() <= self.gamma_2.len(),
+            "the matrix has more rows than Gamma_2 generators"
+        );
+        let row_commitments = self.row_commitments(rows);
+        let commitment =
+            Bls12_381::multi_pairing(&row_commitments, &self.gamma_2[..row_commitments.len()]);
+        (commitment, row_commitments)
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G1Projective, G2Affine};
+use ark_ec::{pairing::Pairing, CurveGroup, VariableBaseMSM};
+use ark_std::UniformRand;
+
+#[test]
+fn we_can_compute_a_dory_commitment() {
+    let mut rng = ark_std::test_rng();
+    let gamma_1: Vec<G1Affine> = (0..4).map(|_| G1Affine::rand(&mut rng)).collect();
+    let gamma_2: Vec<G2Affine> = (0..3).map(|_| G2Affine::rand(&mut rng)).collect();
+    let engine = DoryCommitmentEngine::new(&gamma_1, &gamma_2);
+
+    let row_a: Vec<u64> = vec![2, 3, 1, 5];
+    let row_b: Vec<i32> = vec![-7, 4];
+    let (commitment, row_commitments) = engine.commit(&[(&row_a).into(), (&row_b).into()]);
+
+    let scalars_a: Vec<Fr> = row_a.iter().map(|d| Fr::from(*d)).collect();
+    let scalars_b: Vec<Fr> = row_b.iter().map(|d| Fr::from(*d)).collect();
+    let expected_rows = vec![
+        G1Projective::msm(&gamma_1, &scalars_a)
+            .unwrap()
+            .into_affine(),
+        G1Projective::msm(&gamma_1[..2], &scalars_b)
+            .unwrap()
+            .into_affine(),
+    ];
+    assert_eq!(row_commitments, expected_rows);
+    assert_eq!(
+        commitment,
+        Bls12_381::multi_pairing(&expected_rows, &gamma_2[..2])
+    );
+}
+
+#[test]
+fn the_dory_commitment_of_an_empty_matrix_is_the_identity() {
+    let mut rng = ark_std::test_rng();
+    let gamma_1: Vec<G1Affine> = (0..4).map(|_| G1Affine::rand(&mut rng)).collect();
+    let gamma_2: Vec<G2Affine> = (0..3).map(|_| G2Affine::rand(&mut rng)).collect();
+    let engine = DoryCommitmentEngine::new(&gamma_1, &gamma_2);
+
+    let (commitment, row_commitments) = engine.commit(&[]);
+    assert!(row_commitments.is_empty());
+    assert_eq!(commitment, Default::default());
+}
+
+#[test]
+fn the_dory_commitment_of_a_matrix_of_empty_rows_is_the_identity() {
+    let mut rng = ark_std::test_rng();
+    let gamma_1: Vec<G1Affine> = (0..4).map(|_| G1Affine::rand(&mut rng)).collect();
+    let gamma_2: Vec<G2Affine> = (0..3).map(|_| G2Affine::rand(&mut rng)).collect();
+    let engine = DoryCommitmentEngine::new(&gamma_1, &gamma_2);
+
+    let empty: Vec<u64> = Vec::new();
+    let (commitment, row_commitments) = engine.commit(&[(&empty).into(), (&empty).into()]);
+    assert_eq!(row_commitments, vec![G1Affine::identity(); 2]);
+    assert_eq!(commitment, Default::default());
+}
+
+#[test]
+#[should_panic]
+fn we_cannot_commit_to_a_matrix_with_more_rows_than_gamma_2_generators() {
+    let mut rng = ark_std::test_rng();
+    let gamma_1: Vec<G1Affine> = (0..4).map(|_| G1Affine::rand(&mut rng)).collect();
+    let gamma_2: Vec<G2Affine> = (0..1).map(|_| G2Affine::rand(&mut rng)).collect();
+    let engine = DoryCommitmentEngine::new(&gamma_1, &gamma_2);
+
+    let row: Vec<u64> = vec![2, 3];
+    let _ = engine.commit(&[(&row).into(), (&row).into()]);
+}
@@ -0,0 +1,111 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use ark_ff::{
+    fields::{Fp256, MontBackend, MontConfig},
+    BigInt, Field, One, PrimeField, Zero,
+};
+use curve25519_dalek::{
+    edwards::{CompressedEdwardsY, EdwardsPoint},
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+};
+
+#[derive(MontConfig)]
+#[modulus = "57896044618658097711785492504343953926634992332820282019728792003956564819949"]
+#[generator = "2"]
+struct FieldConfig;
+
+/// The base field of curve25519, i.e. integers modulo 2^255 - 19.
+type Field25519 = Fp256<MontBackend<FieldConfig, 4>>;
+
+fn is_negative(x: &Field25519) -> bool {
+    x.into_bigint().0[0] & 1 == 1
+}
+
+fn abs(x: Field25519) -> Field25519 {
+    if is_negative(&x) {
+        -x
+    } else {
+        x
+    }
+}
+
+/// Decodes a ristretto encoding into the affine coordinates `(x, y)` of one of the
+/// Edwards points it represents, following RFC 9496, section 4.3.1.
+fn decode(bytes: &[u8; 32]) -> Option<(Field25519, Field25519)> {
+    let mut limbs = [0_u64; 4];
+    limbs
+        .iter_mut()
+        .zip(bytes.chunks_exact(8))
+        .for_each(|(limb, chunk)| *limb = u64::from_le_bytes(chunk.try_into().unwrap()));
+    let s = Field25519::from_bigint(BigInt(limbs))?;
+    if is_negative(&s) {
+        return None;
+    }
+
+    let d = -Field25519::from(121665_u64) / Field25519::from(121666_u64);
+    let ss = s.square();
+    let u1 = Field25519::one() - ss;
+    let u2 = Field25519::one() + ss;
+    let u2_sqr = u2.square();
+    let v = -(d * u1.square()) - u2_sqr;
+    let invsqrt = abs((v * u2_sqr).inverse()?.sqrt()?);
+
+    let den_x = invsqrt * u2;
+    let den_y = invsqrt * den_x * v;
+    let x = abs((s + s) * den_x);
+    let y = u1 * den_y;
+    if is_negative(&(x * y)) || y.is_zero() {
+        return None;
+    }
+    Some((x, y))
+}
+
+/// Converts a compressed ristretto point, e.g. a commitment computed by
+/// [crate::compute::compute_curve25519_commitments], to an ed25519 point.
+///
+/// A ristretto point stands for a coset of 4 Edwards points that differ by a torsion
+/// component. The result is the unique point of the coset in the prime-order subgroup,
+/// so it is torsion-free and the conversion is a group homomorphism: converting a sum of
+/// ristretto points gives the sum of the converted points.
+///
+/// Note that Edwards points obtained from another source are only equal to the result
+/// if they are torsion-free as well. Verification code that multiplies by the cofactor
+/// accepts any point of the coset.
+///
+/// Returns `None` if `point` is not a valid ristretto encoding.
+pub fn compressed_ristretto_to_edwards(point: &CompressedRistretto) -> Option<EdwardsPoint> {
+    let (x, y) = decode(point.as_bytes())?;
+
+    let mut bytes = [0_u8; 32];
+    y.into_bigint()
+        .0
+        .iter()
+        .zip(bytes.chunks_exact_mut(8))
+        .for_each(|(limb, chunk)| chunk.copy_from_slice(&limb.to_le_bytes()));
+    bytes[31] |= (is_negative(&x) as u8) << 7;
+    let representative = CompressedEdwardsY(bytes).decompress()?;
+
+    // 8 * representative removes the torsion component, and multiplying by the inverse
+    // of 8 modulo the group order maps back onto the prime-order subgroup
+    Some(representative.mul_by_cofactor() * Scalar::from(8_u64).invert())
+}
+
+/// Converts a ristretto point to an ed25519 point.
+///
+/// See [compressed_ristretto_to_edwards] for how the representative is chosen.
+pub fn ristretto_to_edwards(point: &RistrettoPoint) -> EdwardsPoint {
+    compressed_ristretto_to_edwards(&point.compress())
+        .expect("a compressed ristretto point is a valid encoding")
+}
@@ -0,0 +1,89 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+use curve25519_dalek::{
+    constants::{ED25519_BASEPOINT_POINT, RISTRETTO_BASEPOINT_POINT},
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+    traits::Identity,
+};
+use rand_core::OsRng;
+
+#[test]
+fn the_ristretto_basepoint_converts_to_the_ed25519_basepoint() {
+    assert_eq!(
+        ristretto_to_edwards(&RISTRETTO_BASEPOINT_POINT),
+        ED25519_BASEPOINT_POINT
+    );
+}
+
+#[test]
+fn the_ristretto_identity_converts_to_the_edwards_identity() {
+    assert_eq!(
+        ristretto_to_edwards(&RistrettoPoint::identity()),
+        curve25519_dalek::EdwardsPoint::identity()
+    );
+}
+
+#[test]
+fn converting_ristretto_points_to_edwards_is_a_homomorphism() {
+    let mut rng = OsRng;
+    let a = Scalar::random(&mut rng);
+    let p = RistrettoPoint::random(&mut rng);
+    let q = RistrettoPoint::random(&mut rng);
+
+    let converted = ristretto_to_edwards(&(a * p + q));
+    assert_eq!(
+        converted,
+        a * ristretto_to_edwards(&p) + ristretto_to_edwards(&q)
+    );
+    assert!(converted.is_torsion_free());
+}
+
+#[test]
+fn converted_commitments_match_edwards_msms() {
+    let data: Vec<u32> = vec![2000, 7500, 5000, 1500];
+    let mut generators = vec![RistrettoPoint::default(); data.len()];
+    get_curve25519_generators(&mut generators, 0);
+
+    let mut commitments = vec![CompressedRistretto::default(); 1];
+    compute_curve25519_commitments(&mut commitments, &[(&data).into()], 0);
+
+    let expected = data
+        .iter()
+        .zip(&generators)
+        .map(|(d, g)| Scalar::from(*d) * ristretto_to_edwards(g))
+        .sum::<curve25519_dalek::EdwardsPoint>();
+    assert_eq!(
+        compressed_ristretto_to_edwards(&commitments[0]),
+        Some(expected)
+    );
+}
+
+#[test]
+fn we_cannot_convert_invalid_ristretto_encodings() {
+    // negative field element
+    let mut bytes = [0_u8; 32];
+    bytes[0] = 1;
+    assert_eq!(
+        compressed_ristretto_to_edwards(&CompressedRistretto(bytes)),
+        None
+    );
+
+    // non-canonical field element
+    assert_eq!(
+        compressed_ristretto_to_edwards(&CompressedRistretto([0xff; 32])),
+        None
+    );
+}
@@ -1,6 +1,8 @@
-use ark_ec::short_weierstrass::{Affine, SWCurveConfig};
+use ark_ec::short_weierstrass::{Affine, Projective, SWCurveConfig};
 use ark_ff::fields::Field;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError};
 use ark_std::{One, Zero};
+use rayon::prelude::*;
 use std::convert::From;
 
 /// Projective form for a short Weierstrass curve element.
@@ -51,6 +53,25 @@ impl<P: SWCurveConfig> From<&Affine<P>> for ElementP2<P> {
     }
 }
 
+impl<P: SWCurveConfig> From<Projective<P>> for ElementP2<P> {
+    fn from(pt: Projective<P>) -> Self {
+        ElementP2::<P>::from(&pt)
+    }
+}
+
+impl<P: SWCurveConfig> From<&Projective<P>> for ElementP2<P> {
+    /// Converts from the Jacobian coordinates of arkworks, where (X, Y, Z) represents
+    /// (X / Z^2, Y / Z^3), without any field inversion.
+    fn from(pt: &Projective<P>) -> Self {
+        let z_squared = pt.z.square();
+        Self {
+            x: pt.x * pt.z,
+            y: pt.y,
+            z: z_squared * pt.z,
+        }
+    }
+}
+
 impl<P: SWCurveConfig> From<ElementP2<P>> for Affine<P> {
     fn from(pt: ElementP2<P>) -> Self {
         Affine::<P>::from(&pt)
@@ -74,3 +95,66 @@ impl<P: SWCurveConfig> From<&ElementP2<P>> for Affine<P> {
         }
     }
 }
+
+impl<P: SWCurveConfig> ElementP2<P> {
+    /// Number of bytes of the compressed encoding of a point.
+    pub fn compressed_size() -> usize {
+        P::serialized_size(Compress::Yes)
+    }
+
+    /// Returns the arkworks compressed encoding of the point.
+    pub fn to_compressed_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0_u8; Self::compressed_size()];
+        Affine::<P>::from(self)
+            .serialize_compressed(&mut bytes[..])
+            .expect("the buffer holds a compressed point");
+        bytes
+    }
+
+    /// Reads a point from its arkworks compressed encoding, checking that it is on the
+    /// curve and in the prime order subgroup.
+    pub fn from_compressed_bytes(bytes: &[u8]) -> Result<Self, SerializationError> {
+        Affine::<P>::deserialize_compressed(bytes).map(Into::into)
+    }
+
+    /// Writes the compressed encodings of `points` one after the other, in parallel.
+    ///
+    /// The points are normalized with a single batch inversion, and the result is a single
+    /// buffer of `points.len() * Self::compressed_size()` bytes.
+    pub fn batch_to_compressed_bytes(points: &[Self]) -> Vec<u8> {
+        let size = Self::compressed_size();
+        let mut z_inverses: Vec<P::BaseField> = points.iter().map(|pt| pt.z).collect();
+        // zeros are left untouched, so the identity keeps z == 0
+        ark_ff::batch_inversion(&mut z_inverses);
+        let mut bytes = vec![0_u8; points.len() * size];
+        bytes
+            .par_chunks_exact_mut(size)
+            .zip(points.par_iter().zip(z_inverses))
+            .for_each(|(out, (pt, z_inv))| {
+                let affine = if pt.z.is_zero() {
+                    Affine::<P>::identity()
+                } else {
+                    Affine::<P>::new_unchecked(pt.x * z_inv, pt.y * z_inv)
+                };
+                affine
+                    .serialize_compressed(out)
+                    .expect("the buffer holds a compressed point");
+            });
+        bytes
+    }
+
+    /// Reads points from consecutive compressed encodings, in parallel.
+    ///
+    /// Returns an error if `bytes.len()` is not a multiple of `Self::compressed_size()`
+    /// or if any encoding is not a valid point.
+    pub fn batch_from_compressed_bytes(bytes: &[u8]) -> Result<Vec<Self>, SerializationError> {
+        let size = Self::compressed_size();
+        if bytes.len() % size != 0 {
+            return Err(SerializationError::InvalidData);
+        }
+        bytes
+            .par_chunks_exact(size)
+            .map(Self::from_compressed_bytes)
+            .collect()
+    }
+}
@@ -1,8 +1,18 @@
+#[cfg(any(feature = "bls12_381", feature = "bn254"))]
 use super::*;
+#[cfg(feature = "bls12_381")]
 use ark_bls12_381::G1Affine;
+#[cfg(feature = "bn254")]
+use ark_ec::AffineRepr;
+#[cfg(any(feature = "bls12_381", feature = "bn254"))]
+use ark_ec::CurveGroup;
+#[cfg(feature = "bls12_381")]
+use ark_serialize::CanonicalSerialize;
+#[cfg(any(feature = "bls12_381", feature = "bn254"))]
 use ark_std::UniformRand;
 
 #[test]
+#[cfg(feature = "bls12_381")]
 fn we_can_convert_between_different_point_representations() {
     // we handle zero
     let e1 = G1Affine::identity();
@@ -17,3 +27,64 @@ fn we_can_convert_between_different_point_representations() {
     let e1p = G1Affine::from(e2);
     assert_eq!(e1, e1p);
 }
+
+#[test]
+#[cfg(feature = "bls12_381")]
+fn we_can_convert_points_to_and_from_compressed_bytes() {
+    let mut rng = ark_std::test_rng();
+    let affine = G1Affine::rand(&mut rng);
+    // use a point with z != 1
+    let point = ElementP2::<ark_bls12_381::g1::Config> {
+        x: affine.x * affine.x,
+        y: affine.y * affine.x,
+        z: affine.x,
+    };
+    let mut expected = Vec::new();
+    affine.serialize_compressed(&mut expected).unwrap();
+
+    let bytes = point.to_compressed_bytes();
+    assert_eq!(bytes, expected);
+    assert_eq!(
+        bytes.len(),
+        ElementP2::<ark_bls12_381::g1::Config>::compressed_size()
+    );
+    let res = ElementP2::<ark_bls12_381::g1::Config>::from_compressed_bytes(&bytes).unwrap();
+    assert_eq!(G1Affine::from(res), affine);
+    assert!(ElementP2::<ark_bls12_381::g1::Config>::from_compressed_bytes(&[0xff; 48]).is_err());
+}
+
+#[test]
+#[cfg(feature = "bn254")]
+fn we_can_convert_batches_of_points_to_and_from_compressed_bytes() {
+    type P2 = ElementP2<ark_bn254::g1::Config>;
+    let mut rng = ark_std::test_rng();
+    let affines: Vec<ark_bn254::G1Affine> = (0..5)
+        .map(|i| match i {
+            2 => ark_bn254::G1Affine::zero(),
+            _ => (ark_bn254::G1Affine::rand(&mut rng) * ark_bn254::Fr::from(i + 1)).into_affine(),
+        })
+        .collect();
+    let points: Vec<P2> = affines.iter().map(Into::into).collect();
+
+    let bytes = P2::batch_to_compressed_bytes(&points);
+    let expected: Vec<u8> = points.iter().flat_map(P2::to_compressed_bytes).collect();
+    assert_eq!(bytes, expected);
+
+    let res = P2::batch_from_compressed_bytes(&bytes).unwrap();
+    let res: Vec<ark_bn254::G1Affine> = res.iter().map(Into::into).collect();
+    assert_eq!(res, affines);
+
+    assert!(P2::batch_to_compressed_bytes(&[]).is_empty());
+    assert!(P2::batch_from_compressed_bytes(&bytes[1..]).is_err());
+}
+
+#[test]
+#[cfg(feature = "bls12_381")]
+fn we_can_convert_projective_points_without_normalizing_them() {
+    let mut rng = ark_std::test_rng();
+    let p =
+        ark_bls12_381::G1Projective::rand(&mut rng) + ark_bls12_381::G1Projective::rand(&mut rng);
+    assert_eq!(G1Affine::from(ElementP2::from(p)), p.into_affine());
+    let zero = ark_bls12_381::G1Projective::default();
+    assert_eq!(G1Affine::from(ElementP2::from(zero)), G1Affine::identity());
+}
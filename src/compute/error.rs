@@ -0,0 +1,109 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use thiserror::Error;
+
+/// HandleFileError related to reading and writing serialized MSM handles
+#[derive(Error, Debug)]
+pub enum HandleFileError {
+    /// This error occurs when the handle file cannot be read or written.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// This error occurs when the header file next to the handle file is missing or
+    /// invalid.
+    #[error("Invalid handle file header")]
+    InvalidHeader,
+
+    /// This error occurs when the file was written with an unsupported format version.
+    #[error("Unsupported handle file format version {0}")]
+    UnsupportedVersion(u32),
+
+    /// This error occurs when the file was written for a different curve than the handle's.
+    #[error("Handle file curve id {found} does not match the expected curve id {expected}")]
+    CurveMismatch {
+        /// The curve id of the handle being loaded
+        expected: u32,
+        /// The curve id stored in the file
+        found: u32,
+    },
+
+    /// This error occurs when the backend cannot deserialize the handle file.
+    #[error("The backend could not deserialize the handle file")]
+    InvalidHandle,
+}
+
+/// MsmError related to the inputs of an MSM computed with a handle
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MsmError {
+    /// This error occurs when the scalars do not have 1, 2, 4, 8, 16 or 32 bytes.
+    #[error("Scalars of {0} bytes are not supported, use 1, 2, 4, 8, 16 or 32 bytes")]
+    InvalidScalarWidth(u32),
+
+    /// This error occurs when the scalars do not hold the same number of scalars for
+    /// every output.
+    #[error(
+        "{num_bytes} bytes of scalars is not a multiple of the {row_num_bytes} bytes of a row"
+    )]
+    InvalidScalarsLength {
+        /// The number of bytes of the scalars
+        num_bytes: usize,
+        /// The number of bytes of one scalar for every output
+        row_num_bytes: usize,
+    },
+
+    /// This error occurs when there are more outputs than the backend accepts in one call.
+    #[error("{0} outputs exceed the {max} outputs the backend accepts", max = u32::MAX)]
+    TooManyOutputs(usize),
+
+    /// This error occurs when there are more scalars per output than generators in the handle.
+    #[error("{n} scalars per output exceed the {num_generators} generators of the handle")]
+    NotEnoughGenerators {
+        /// The number of scalars per output
+        n: usize,
+        /// The number of generators of the handle
+        num_generators: usize,
+    },
+
+    /// This error occurs when the MSM exceeds a limit of the backend, such as
+    /// `BackendConfig::max_device_memory_bytes`.
+    #[error(transparent)]
+    Backend(#[from] BackendError),
+}
+
+/// BackendError related to the initialization of the backend
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum BackendError {
+    /// This error occurs when the backend fails to initialize.
+    #[error("Backend initialization failed with code {0}")]
+    InitializationFailed(i32),
+
+    /// This error occurs when a `BLITZAR_*` environment variable cannot be parsed.
+    #[error("Invalid value {value:?} of the environment variable {name}")]
+    InvalidEnvironmentVariable {
+        /// The name of the variable
+        name: &'static str,
+        /// The value of the variable
+        value: String,
+    },
+
+    /// This error occurs when a computation does not fit in `BackendConfig::max_device_memory_bytes`,
+    /// even when split into chunks of rows, or when it cannot be split, as for an MSM.
+    #[error("Computation requires {required} bytes of device memory, more than the limit of {limit} bytes")]
+    MemoryLimitExceeded {
+        /// The number of bytes required by the smallest chunk of the computation
+        required: u64,
+        /// The device memory limit
+        limit: u64,
+    },
+}
@@ -1,24 +1,89 @@
-use super::backend::init_backend;
-use crate::compute::{curve::SwCurveConfig, CurveId, ElementP2};
-use ark_ec::short_weierstrass::Affine;
+use super::{
+    backend::{init_backend, max_device_memory_bytes},
+    commitments::assume_init_mut,
+    error::{BackendError, HandleFileError, MsmError},
+    handle_file::{read_header, write_header, HandleFileHeader, FORMAT_VERSION},
+    memory_limit::check_device_bytes,
+    msm_sum::{pack_row_sums, MAX_OUTPUT_BITS},
+    packing::append_sign_outputs,
+    scalar::{serialize_scalars, ScalarSerialize, ScalarWidth},
+    scalar_matrix::ScalarMatrix,
+};
+use crate::{
+    compute::{CurveId, ElementP2, SwCurveConfig},
+    error::backend_len,
+};
+use ark_ec::short_weierstrass::{Affine, Projective};
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
 use rayon::prelude::*;
-use std::{ffi::CString, marker::PhantomData};
+use std::{ffi::CString, io, marker::PhantomData, mem::MaybeUninit, path::Path, sync::Arc};
 
 fn count_scalars_per_output(scalars_len: usize, output_bit_table: &[u32]) -> u32 {
     let bit_sum: usize = output_bit_table.iter().map(|s| *s as usize).sum();
     let num_output_bytes = (bit_sum + 7) / 8;
+    assert!(
+        num_output_bytes > 0,
+        "output_bit_table must have a non-zero total bit width for non-empty scalars"
+    );
     assert!(scalars_len % num_output_bytes == 0);
-    (scalars_len / num_output_bytes).try_into().unwrap()
+    backend_len("scalars per output", scalars_len / num_output_bytes)
+}
+
+/// Owner of a backend multiexponentiation handle, freed when dropped.
+struct RawHandle(*mut blitzar_sys::sxt_multiexp_handle);
+
+// The backend handle is only read after creation, so it can be used and freed from
+// any thread.
+unsafe impl Send for RawHandle {}
+unsafe impl Sync for RawHandle {}
+
+impl Drop for RawHandle {
+    fn drop(&mut self) {
+        unsafe {
+            blitzar_sys::sxt_multiexp_handle_free(self.0);
+        }
+    }
+}
+
+/// Combines the sum outputs of `msm_sum::pack_row_sums` into the sum.
+fn combine_sum<T: CurveId + Clone>(raw: &[T]) -> T {
+    match raw {
+        [sum] => sum.clone(),
+        [low, high] => T::add_shifted(low, high, MAX_OUTPUT_BITS as u32),
+        _ => unreachable!("the sum is split into one or two outputs"),
+    }
+}
+
+/// Returns `scalars` preceded by `num_zeros` zero bytes.
+fn with_zero_rows(num_zeros: usize, scalars: &[u8]) -> Vec<u8> {
+    let mut res = vec![0_u8; num_zeros + scalars.len()];
+    res[num_zeros..].copy_from_slice(scalars);
+    res
 }
 
 /// Handle to compute multi-scalar multiplications (MSMs) with pre-specified generators
 ///
+/// Cloning a handle is cheap: clones share the same backend handle through reference
+/// counting, and the backend handle is freed when the last clone is dropped.
+///
+/// A handle is `Send` and `Sync`, so one handle can serve MSMs from several threads at once.
+///
+/// The backend takes 32-bit counts, so the methods other than [MsmHandle::try_msm] panic if
+/// there are more than `u32::MAX` outputs, generators or scalars per output. Longer inputs
+/// are not split into several backend calls.
+///
+/// The backend always starts an MSM at the first generator of a handle, so an MSM is not
+/// split into chunks of rows either. The methods other than [MsmHandle::try_msm] panic if
+/// the scalars and outputs of a backend call exceed
+/// [BackendConfig::max_device_memory_bytes](super::BackendConfig::max_device_memory_bytes).
+///
 /// # Example 1 - compute an MSM using the handle
 ///```no_run
 #[doc = include_str!("../../examples/simple_fixed_msm.rs")]
 ///```
 pub struct MsmHandle<T: CurveId> {
-    handle: *mut blitzar_sys::sxt_multiexp_handle,
+    handle: Arc<RawHandle>,
+    num_generators: usize,
     phantom: PhantomData<T>,
 }
 
@@ -37,41 +102,66 @@ impl<T: CurveId> MsmHandle<T> {
             let handle = blitzar_sys::sxt_multiexp_handle_new(
                 T::CURVE_ID,
                 generators.as_ptr() as *const std::ffi::c_void,
-                generators.len() as u32,
+                backend_len("generators", generators.len()),
             );
             Self {
-                handle,
+                handle: Arc::new(RawHandle(handle)),
+                num_generators: generators.len(),
                 phantom: PhantomData,
             }
         }
     }
 
-    /// New handle from a serialized file.
+    /// New handle from a file written by `write`.
+    ///
+    /// The header file next to `filename` is checked against the handle's curve and the
+    /// supported format version before the backend deserializes the handle. An error is
+    /// returned if the files cannot be read or were written for an incompatible handle.
     ///
     /// Note: any MSMs computed with the handle must have length less than or equal
     /// to the number of generators used to create the handle.
-    pub fn new_from_file(filename: &str) -> Self {
+    pub fn new_from_file(filename: &str) -> Result<Self, HandleFileError> {
         init_backend();
-        let filename = CString::new(filename).expect("filename cannot have null bytes");
-        unsafe {
-            let handle =
-                blitzar_sys::sxt_multiexp_handle_new_from_file(T::CURVE_ID, filename.as_ptr());
-            Self {
-                handle,
-                phantom: PhantomData,
-            }
+        let header = read_header(Path::new(filename), T::CURVE_ID)?;
+        let c_filename = CString::new(filename).map_err(io::Error::from)?;
+        let handle = unsafe {
+            blitzar_sys::sxt_multiexp_handle_new_from_file(T::CURVE_ID, c_filename.as_ptr())
+        };
+        if handle.is_null() {
+            return Err(HandleFileError::InvalidHandle);
         }
+        Ok(Self {
+            handle: Arc::new(RawHandle(handle)),
+            num_generators: header.num_generators as usize,
+            phantom: PhantomData,
+        })
     }
 
     /// Serialize the handle to a file.
     ///
+    /// The backend writes the serialized handle to `filename` and a versioned header
+    /// recording the curve and the number of generators is written next to it, to
+    /// `filename` with a `.header` suffix. Both files are needed by new_from_file.
+    ///
     /// This function can be used together with new_from_file to reduce
     /// the cost of creating a handle.
-    pub fn write(&self, filename: &str) {
-        let filename = CString::new(filename).expect("filename cannot have null bytes");
+    pub fn write(&self, filename: &str) -> Result<(), HandleFileError> {
+        let c_filename = CString::new(filename).map_err(io::Error::from)?;
         unsafe {
-            blitzar_sys::sxt_multiexp_handle_write_to_file(self.handle, filename.as_ptr());
+            blitzar_sys::sxt_multiexp_handle_write_to_file(self.handle.0, c_filename.as_ptr());
         }
+        let header = HandleFileHeader {
+            format_version: FORMAT_VERSION,
+            curve_id: T::CURVE_ID,
+            num_generators: self.num_generators as u64,
+        };
+        write_header(Path::new(filename), &header)?;
+        Ok(())
+    }
+
+    /// Returns the number of generators the handle was created with.
+    pub fn num_generators(&self) -> usize {
+        self.num_generators
     }
 
     /// Compute an MSM using pre-specified generators.
@@ -99,22 +189,290 @@ impl<T: CurveId> MsmHandle<T> {
     ///       .
     ///       .
     ///    res[m-1] = s_m1 * g_1 + s_12 * g_2 + ... + s_mn * g_n
+    ///
+    /// # Panics
+    ///
+    /// If `scalars` is not empty and `element_num_bytes` is zero, if `scalars.len()` is
+    /// not a multiple of `res.len() * element_num_bytes`, or if the MSM exceeds
+    /// [BackendConfig::max_device_memory_bytes](super::BackendConfig::max_device_memory_bytes).
+    /// See [MsmHandle::try_msm] for a version that checks the layout of `scalars` and
+    /// returns an error instead of panicking.
     pub fn msm(&self, res: &mut [T], element_num_bytes: u32, scalars: &[u8]) {
-        let num_outputs = res.len() as u32;
-        assert!(scalars.len() as u32 % (num_outputs * element_num_bytes) == 0);
-        let n = scalars.len() as u32 / (num_outputs * element_num_bytes);
         unsafe {
-            blitzar_sys::sxt_fixed_multiexponentiation(
-                res.as_ptr() as *mut std::ffi::c_void,
-                self.handle,
+            self.msm_raw(
+                res.as_mut_ptr(),
+                backend_len("res", res.len()),
                 element_num_bytes,
-                num_outputs,
-                n,
-                scalars.as_ptr(),
+                scalars,
             );
         }
     }
 
+    /// Compute an MSM into uninitialized memory.
+    ///
+    /// This is the same as `msm`, except that `res` does not need to be initialized
+    /// beforehand. Every element of `res` is initialized on return.
+    ///
+    /// # Panics
+    ///
+    /// In the same cases as `msm`.
+    pub fn msm_uninit<'a>(
+        &self,
+        res: &'a mut [MaybeUninit<T>],
+        element_num_bytes: u32,
+        scalars: &[u8],
+    ) -> &'a mut [T] {
+        unsafe {
+            self.msm_raw(
+                res.as_mut_ptr() as *mut T,
+                backend_len("res", res.len()),
+                element_num_bytes,
+                scalars,
+            );
+            assume_init_mut(res)
+        }
+    }
+
+    /// Compute an MSM as `msm` does, with a typed scalar width.
+    ///
+    /// Returns an error instead of computing a wrong result if `scalars` does not hold
+    /// the same number of scalars for every output, or if it holds more scalars per
+    /// output than the handle has generators. Returns an error instead of panicking if
+    /// the MSM exceeds
+    /// [BackendConfig::max_device_memory_bytes](super::BackendConfig::max_device_memory_bytes).
+    pub fn try_msm(
+        &self,
+        res: &mut [T],
+        width: ScalarWidth,
+        scalars: &[u8],
+    ) -> Result<(), MsmError> {
+        if res.len() > u32::MAX as usize {
+            return Err(MsmError::TooManyOutputs(res.len()));
+        }
+        let row_num_bytes = res.len() * width.num_bytes() as usize;
+        if row_num_bytes > 0 {
+            if scalars.len() % row_num_bytes != 0 {
+                return Err(MsmError::InvalidScalarsLength {
+                    num_bytes: scalars.len(),
+                    row_num_bytes,
+                });
+            }
+            let n = scalars.len() / row_num_bytes;
+            if n > self.num_generators {
+                return Err(MsmError::NotEnoughGenerators {
+                    n,
+                    num_generators: self.num_generators,
+                });
+            }
+            if n > 0 {
+                Self::check_memory_limit(res.len(), scalars.len())?;
+            }
+        }
+        self.msm(res, width.num_bytes(), scalars);
+        Ok(())
+    }
+
+    /// Returns an error if a backend call with `num_outputs` outputs and
+    /// `num_scalar_bytes` bytes of scalars exceeds the device memory limit.
+    ///
+    /// The generators are already held by the handle, so only the scalars and the
+    /// outputs are transferred.
+    fn check_memory_limit(num_outputs: usize, num_scalar_bytes: usize) -> Result<(), BackendError> {
+        let num_bytes = num_scalar_bytes + num_outputs * std::mem::size_of::<T>();
+        check_device_bytes(num_bytes as u64, max_device_memory_bytes())
+    }
+
+    /// # Safety
+    ///
+    /// `res` must be valid for writes of `num_outputs` elements of type `T`.
+    unsafe fn msm_raw(
+        &self,
+        res: *mut T,
+        num_outputs: u32,
+        element_num_bytes: u32,
+        scalars: &[u8],
+    ) {
+        if num_outputs == 0 {
+            return;
+        }
+        if scalars.is_empty() {
+            (0..num_outputs as usize).for_each(|i| res.add(i).write(T::identity_element()));
+            return;
+        }
+        assert!(
+            element_num_bytes > 0,
+            "element_num_bytes must be non-zero for non-empty scalars"
+        );
+        let row_num_bytes = num_outputs as usize * element_num_bytes as usize;
+        assert!(scalars.len() % row_num_bytes == 0);
+        let n = backend_len("scalars per output", scalars.len() / row_num_bytes);
+        Self::check_memory_limit(num_outputs as usize, scalars.len())
+            .unwrap_or_else(|err| panic!("{err}"));
+        blitzar_sys::sxt_fixed_multiexponentiation(
+            res as *mut std::ffi::c_void,
+            self.handle.0,
+            element_num_bytes,
+            num_outputs,
+            n,
+            scalars.as_ptr(),
+        );
+    }
+
+    /// Compute an MSM using the generators `[offset, offset + n)` of the handle, at a
+    /// cost of `O(offset + n)` rather than `O(n)`.
+    ///
+    /// `scalars` uses the same layout as `msm`, with `n` scalars per output, and
+    ///
+    ///    res[j] = s_j1 * g_(offset+1) + s_j2 * g_(offset+2) + ... + s_jn * g_(offset+n)
+    ///
+    /// # Performance
+    ///
+    /// The backend has no offset parameter and always starts at the first generator of a
+    /// handle, so the scalars are copied after `offset` rows of zeros before being passed
+    /// to it. Both the host copy and the backend's work grow with `offset`; for large
+    /// offsets and short inputs, a handle created from the generators starting at `offset`
+    /// is faster.
+    ///
+    /// # Panics
+    ///
+    /// If `offset + n` is larger than the number of generators of the handle.
+    pub fn msm_with_offset(
+        &self,
+        res: &mut [T],
+        element_num_bytes: u32,
+        scalars: &[u8],
+        offset: usize,
+    ) {
+        let row_num_bytes = res.len() * element_num_bytes as usize;
+        if row_num_bytes == 0 || scalars.is_empty() {
+            self.msm(res, element_num_bytes, scalars);
+            return;
+        }
+        let n = scalars.len() / row_num_bytes;
+        self.check_offset(offset, n);
+        self.msm(
+            res,
+            element_num_bytes,
+            &with_zero_rows(offset * row_num_bytes, scalars),
+        );
+    }
+
+    /// Compute an MSM where only a subset of the generators have non-zero scalars.
+    ///
+    /// Suppose i_1, ..., i_k are the entries of `indexes`. Then `scalars` holds the
+    /// element_num_bytes scalars for those generators, laid out as in `msm`
+    ///
+    ///    scalars = [s_1i_1, s_2i_1, ..., s_mi_1, s_1i_2, s_2i_2, ..., s_mi_k ]
+    ///
+    /// and res will contain the MSM result
+    ///
+    ///    res[j] = s_ji_1 * g_i_1 + s_ji_2 * g_i_2 + ... + s_ji_k * g_i_k
+    ///
+    /// The entries are scattered into zero rows up to the largest index, so the cost is
+    /// that of `msm` with `max(indexes) + 1` scalars per output, and the scalars of the
+    /// generators beyond the largest index are never copied or transferred.
+    ///
+    /// # Panics
+    ///
+    /// If `scalars.len()` is different from `indexes.len() * res.len() * element_num_bytes`,
+    /// if an index is repeated or is not less than the number of generators of the handle,
+    /// or in the same cases as `msm`.
+    pub fn sparse_msm(
+        &self,
+        res: &mut [T],
+        element_num_bytes: u32,
+        indexes: &[u32],
+        scalars: &[u8],
+    ) {
+        let row_num_bytes = res.len() * element_num_bytes as usize;
+        assert_eq!(
+            scalars.len(),
+            indexes.len() * row_num_bytes,
+            "scalars must hold res.len() scalars for each index"
+        );
+        let mut sorted_indexes = indexes.to_vec();
+        sorted_indexes.sort_unstable();
+        assert!(
+            sorted_indexes.windows(2).all(|w| w[0] != w[1]),
+            "indexes must be distinct"
+        );
+        let Some(max_index) = sorted_indexes.last().map(|i| *i as usize) else {
+            self.msm(res, element_num_bytes, scalars);
+            return;
+        };
+        assert!(
+            max_index < self.num_generators,
+            "index {max_index} is out of range for {} generators",
+            self.num_generators
+        );
+        if row_num_bytes == 0 {
+            self.msm(res, element_num_bytes, scalars);
+            return;
+        }
+        let mut dense = vec![0_u8; (max_index + 1) * row_num_bytes];
+        for (index, row) in indexes.iter().zip(scalars.chunks_exact(row_num_bytes)) {
+            let index = *index as usize;
+            dense[index * row_num_bytes..(index + 1) * row_num_bytes].copy_from_slice(row);
+        }
+        self.msm(res, element_num_bytes, &dense);
+    }
+
+    /// Compute an MSM using pre-specified generators and typed scalars.
+    ///
+    /// This is equivalent to `msm` with `element_num_bytes = S::NUM_BYTES`,
+    /// except that the scalars are serialized to their canonical little-endian
+    /// encoding internally. `scalars` uses the same layout as `msm`
+    ///
+    ///    scalars = [s_11, s_21, ..., s_m1, s_12, s_22, ..., s_m2, ..., s_mn ]
+    pub fn msm_from_scalars<S: ScalarSerialize>(&self, res: &mut [T], scalars: &[S]) {
+        let scalars = serialize_scalars(scalars);
+        self.msm(res, S::NUM_BYTES as u32, &scalars);
+    }
+
+    /// Compute an MSM using pre-specified generators and a [ScalarMatrix].
+    ///
+    /// This is equivalent to `msm`, with the scalars already laid out by `scalars`.
+    ///
+    /// # Panics
+    ///
+    /// If `res.len()` is different from `scalars.num_outputs()`.
+    pub fn matrix_msm(&self, res: &mut [T], scalars: &ScalarMatrix) {
+        assert_eq!(
+            res.len(),
+            scalars.num_outputs(),
+            "res must hold one element for each output of the matrix"
+        );
+        self.msm(res, scalars.element_num_bytes(), scalars.as_bytes());
+    }
+
+    /// Compute an MSM using pre-specified generators and the scalars of each output.
+    ///
+    /// `outputs[i]` holds the scalars of `res[i]`, each of `element_num_bytes` bytes,
+    /// i.e. `[s_i1, s_i2, ..., s_in]`. The scalars are interleaved in parallel into the
+    /// layout of `msm` through a [ScalarMatrix], and outputs with fewer scalars than the
+    /// others are padded with zeros.
+    ///
+    /// # Panics
+    ///
+    /// If `res.len()` is different from `outputs.len()`, if `element_num_bytes` is zero, if
+    /// the length of an output is not a multiple of `element_num_bytes`, or in the same
+    /// cases as `msm`.
+    pub fn msm_from_outputs<S: AsRef<[u8]>>(
+        &self,
+        res: &mut [T],
+        element_num_bytes: u32,
+        outputs: &[S],
+    ) {
+        assert_eq!(
+            res.len(),
+            outputs.len(),
+            "res must hold one element for each output"
+        );
+        let scalars =
+            ScalarMatrix::from_outputs(element_num_bytes, outputs.iter().map(AsRef::as_ref));
+        self.matrix_msm(res, &scalars);
+    }
+
     /// Compute an MSM in packed format using pre-specified generators.
     ///
     /// On completion `res` contains an array of size `num_outputs` for the multiexponentiation
@@ -133,12 +491,21 @@ impl<T: CurveId> MsmHandle<T> {
     /// a packed column-major order as specified by output_bit_table. A given row determines the scalar
     /// exponents for generator g_i with the output scalars packed contiguously and padded with zeros.
     pub fn packed_msm(&self, res: &mut [T], output_bit_table: &[u32], scalars: &[u8]) {
-        let num_outputs = res.len() as u32;
+        let num_outputs = backend_len("res", res.len());
+        assert_eq!(output_bit_table.len(), num_outputs as usize);
+        if num_outputs == 0 {
+            return;
+        }
+        if scalars.is_empty() {
+            res.iter_mut().for_each(|r| *r = T::identity_element());
+            return;
+        }
         let n = count_scalars_per_output(scalars.len(), output_bit_table);
+        Self::check_memory_limit(res.len(), scalars.len()).unwrap_or_else(|err| panic!("{err}"));
         unsafe {
             blitzar_sys::sxt_fixed_packed_multiexponentiation(
                 res.as_ptr() as *mut std::ffi::c_void,
-                self.handle,
+                self.handle.0,
                 output_bit_table.as_ptr(),
                 num_outputs,
                 n,
@@ -147,6 +514,209 @@ impl<T: CurveId> MsmHandle<T> {
         }
     }
 
+    /// Compute a packed MSM using the generators `[offset, offset + n)` of the handle, at
+    /// a cost of `O(offset + n)` rather than `O(n)`.
+    ///
+    /// This is the same as `packed_msm`, except that the `i`-th row of `scalars` is
+    /// multiplied by generator `g_(offset+i)`.
+    ///
+    /// # Performance
+    ///
+    /// As with `msm_with_offset`, the scalars are copied after `offset` rows of zeros, so
+    /// the cost grows with `offset`.
+    ///
+    /// # Panics
+    ///
+    /// If `offset + n` is larger than the number of generators of the handle.
+    pub fn packed_msm_with_offset(
+        &self,
+        res: &mut [T],
+        output_bit_table: &[u32],
+        scalars: &[u8],
+        offset: usize,
+    ) {
+        if res.is_empty() || scalars.is_empty() {
+            self.packed_msm(res, output_bit_table, scalars);
+            return;
+        }
+        let n = count_scalars_per_output(scalars.len(), output_bit_table) as usize;
+        self.check_offset(offset, n);
+        let row_num_bytes = scalars.len() / n;
+        self.packed_msm(
+            res,
+            output_bit_table,
+            &with_zero_rows(offset * row_num_bytes, scalars),
+        );
+    }
+
+    /// Compute the sum of the outputs of an MSM, without computing the outputs.
+    ///
+    /// `scalars` is laid out as in `msm` for `num_outputs` outputs, and the result is
+    ///
+    ///    res[0] + res[1] + ... + res[num_outputs - 1]
+    ///
+    /// The scalars of each generator are added together as integers on the host, and
+    /// the sum is computed by the backend with a single MSM, so that only one point is
+    /// transferred back.
+    ///
+    /// # Panics
+    ///
+    /// If `scalars` is not empty and `element_num_bytes` is zero, or if `scalars.len()` is
+    /// not a multiple of `num_outputs * element_num_bytes`.
+    pub fn msm_sum(&self, num_outputs: usize, element_num_bytes: u32, scalars: &[u8]) -> T
+    where
+        T: Clone,
+    {
+        if num_outputs == 0 || scalars.is_empty() {
+            return T::identity_element();
+        }
+        assert!(
+            element_num_bytes > 0,
+            "element_num_bytes must be non-zero for non-empty scalars"
+        );
+        let packed = pack_row_sums(num_outputs, element_num_bytes as usize, scalars, false);
+        let mut raw = vec![T::identity_element(); packed.output_bit_table.len()];
+        self.packed_msm(&mut raw, &packed.output_bit_table, &packed.scalars);
+        combine_sum(&raw)
+    }
+
+    /// Compute an MSM as `msm` does, and also return the sum of its outputs.
+    ///
+    /// The outputs and their sum are computed by the same backend call. See `msm_sum`.
+    ///
+    /// # Panics
+    ///
+    /// In the same cases as `msm`.
+    pub fn msm_with_sum(&self, res: &mut [T], element_num_bytes: u32, scalars: &[u8]) -> T
+    where
+        T: Clone,
+    {
+        if res.is_empty() || scalars.is_empty() {
+            self.msm(res, element_num_bytes, scalars);
+            return T::identity_element();
+        }
+        assert!(
+            element_num_bytes > 0,
+            "element_num_bytes must be non-zero for non-empty scalars"
+        );
+        let packed = pack_row_sums(res.len(), element_num_bytes as usize, scalars, true);
+        let mut raw = vec![T::identity_element(); packed.output_bit_table.len()];
+        self.packed_msm(&mut raw, &packed.output_bit_table, &packed.scalars);
+        let sum = combine_sum(&raw[res.len()..]);
+        raw.truncate(res.len());
+        res.clone_from_slice(&raw);
+        sum
+    }
+
+    /// Compute a packed MSM where some outputs hold two's complement scalars.
+    ///
+    /// `output_bit_table` and `scalars` are laid out as in `packed_msm`. When
+    /// `output_signed[j]` is true, the `output_bit_table[j]` bits of output `j` are read
+    /// as a signed two's complement integer, matching the semantics of signed sequences
+    /// in the commitment functions.
+    ///
+    /// The sign bits of the signed outputs are packed as additional 1-bit outputs of the
+    /// same backend call, and each signed result is then corrected as
+    ///
+    ///    res[j] = raw[j] - 2^output_bit_table[j] * sign[j]
+    ///
+    /// # Panics
+    ///
+    /// If `output_signed.len()` is different from `output_bit_table.len()`, if a signed
+    /// output has no bits, or in the same cases as `packed_msm`.
+    pub fn packed_msm_signed(
+        &self,
+        res: &mut [T],
+        output_bit_table: &[u32],
+        output_signed: &[bool],
+        scalars: &[u8],
+    ) where
+        T: Clone,
+    {
+        assert_eq!(
+            res.len(),
+            output_bit_table.len(),
+            "res must hold one element for each output"
+        );
+        let packed = append_sign_outputs(output_bit_table, output_signed, scalars);
+        let mut raw = vec![T::identity_element(); packed.output_bit_table.len()];
+        self.packed_msm(&mut raw, &packed.output_bit_table, &packed.scalars);
+        res.iter_mut()
+            .zip(&raw)
+            .zip(packed.sign_outputs.iter().zip(output_bit_table))
+            .for_each(|((res, raw_j), (sign_output, bits))| {
+                *res = match sign_output {
+                    Some(k) => T::sub_shifted(raw_j, &raw[*k], *bits),
+                    None => raw_j.clone(),
+                }
+            });
+    }
+
+    /// Compute MSMs whose outputs use scalars of different sizes in a single backend call.
+    ///
+    /// `scalars[j]` holds the `n` scalars of output `j`, of `element_num_bytes_table[j]`
+    /// bytes each, so that
+    ///
+    ///    res[j] = scalars[j][0] * g_1 + scalars[j][1] * g_2 + ... + scalars[j][n-1] * g_n
+    ///
+    /// The scalars are interleaved into the layout of `packed_msm`, with
+    /// `8 * element_num_bytes_table[j]` bits for output `j`, so that narrow outputs do not
+    /// pay for the width of the widest one.
+    ///
+    /// # Panics
+    ///
+    /// If `element_num_bytes_table` or `scalars` do not have one entry for each element of
+    /// `res`, if an entry of `element_num_bytes_table` is not between 1 and 32, or if the
+    /// outputs do not all have the same number of scalars.
+    pub fn mixed_msm(&self, res: &mut [T], element_num_bytes_table: &[u32], scalars: &[&[u8]]) {
+        assert_eq!(
+            element_num_bytes_table.len(),
+            res.len(),
+            "element_num_bytes_table must have one entry for each output"
+        );
+        assert_eq!(
+            scalars.len(),
+            res.len(),
+            "scalars must have one entry for each output"
+        );
+        element_num_bytes_table.iter().for_each(|enb| {
+            assert!(
+                (1..=32).contains(enb),
+                "element_num_bytes_table entries must be between 1 and 32, found {enb}"
+            )
+        });
+        let n = scalars
+            .first()
+            .zip(element_num_bytes_table.first())
+            .map_or(0, |(s, enb)| s.len() / *enb as usize);
+        scalars
+            .iter()
+            .zip(element_num_bytes_table)
+            .for_each(|(s, enb)| {
+                assert_eq!(
+                    s.len(),
+                    n * *enb as usize,
+                    "every output must have the same number of scalars"
+                )
+            });
+
+        let row_num_bytes: usize = element_num_bytes_table.iter().map(|x| *x as usize).sum();
+        let mut packed = vec![0_u8; n * row_num_bytes];
+        packed
+            .par_chunks_exact_mut(row_num_bytes.max(1))
+            .enumerate()
+            .for_each(|(i, row)| {
+                let mut offset = 0;
+                for (s, enb) in scalars.iter().zip(element_num_bytes_table) {
+                    let enb = *enb as usize;
+                    row[offset..offset + enb].copy_from_slice(&s[i * enb..(i + 1) * enb]);
+                    offset += enb;
+                }
+            });
+        let output_bit_table: Vec<u32> = element_num_bytes_table.iter().map(|x| 8 * x).collect();
+        self.packed_msm(res, &output_bit_table, &packed);
+    }
+
     /// Compute a varying lengthing multiexponentiation of scalars in packed format using a handle to
     /// pre-specified generators.
     ///
@@ -174,13 +744,18 @@ impl<T: CurveId> MsmHandle<T> {
         output_lengths: &[u32],
         scalars: &[u8],
     ) {
-        let num_outputs = res.len() as u32;
+        let num_outputs = backend_len("res", res.len());
         assert_eq!(output_bit_table.len(), num_outputs as usize);
         assert_eq!(output_lengths.len(), num_outputs as usize);
+        if output_lengths.iter().all(|n| *n == 0) {
+            res.iter_mut().for_each(|r| *r = T::identity_element());
+            return;
+        }
+        Self::check_memory_limit(res.len(), scalars.len()).unwrap_or_else(|err| panic!("{err}"));
         unsafe {
             blitzar_sys::sxt_fixed_vlen_multiexponentiation(
                 res.as_ptr() as *mut std::ffi::c_void,
-                self.handle,
+                self.handle.0,
                 output_bit_table.as_ptr(),
                 output_lengths.as_ptr(),
                 num_outputs,
@@ -188,12 +763,61 @@ impl<T: CurveId> MsmHandle<T> {
             );
         }
     }
+
+    /// Compute a variable length MSM using the generators starting at `offset`, at a cost
+    /// that grows with `offset` plus the longest output length.
+    ///
+    /// This is the same as `vlen_msm`, except that the `i`-th row of `scalars` is
+    /// multiplied by generator `g_(offset+i)`.
+    ///
+    /// # Performance
+    ///
+    /// As with `msm_with_offset`, the scalars are copied after `offset` rows of zeros, so
+    /// the cost grows with `offset`.
+    ///
+    /// # Panics
+    ///
+    /// If `offset` plus the longest output length is larger than the number of
+    /// generators of the handle.
+    pub fn vlen_msm_with_offset(
+        &self,
+        res: &mut [T],
+        output_bit_table: &[u32],
+        output_lengths: &[u32],
+        scalars: &[u8],
+        offset: usize,
+    ) {
+        let longest_output = output_lengths.iter().max().map_or(0, |n| *n as usize);
+        if longest_output == 0 {
+            self.vlen_msm(res, output_bit_table, output_lengths, scalars);
+            return;
+        }
+        self.check_offset(offset, longest_output);
+        let bit_sum: usize = output_bit_table.iter().map(|s| *s as usize).sum();
+        let row_num_bytes = bit_sum.div_ceil(8);
+        let output_lengths: Vec<u32> = output_lengths.iter().map(|n| *n + offset as u32).collect();
+        self.vlen_msm(
+            res,
+            output_bit_table,
+            &output_lengths,
+            &with_zero_rows(offset * row_num_bytes, scalars),
+        );
+    }
+
+    fn check_offset(&self, offset: usize, n: usize) {
+        assert!(
+            offset + n <= self.num_generators,
+            "offset + n must not exceed the number of generators of the handle"
+        );
+    }
 }
 
-impl<T: CurveId> Drop for MsmHandle<T> {
-    fn drop(&mut self) {
-        unsafe {
-            blitzar_sys::sxt_multiexp_handle_free(self.handle);
+impl<T: CurveId> Clone for MsmHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            handle: Arc::clone(&self.handle),
+            num_generators: self.num_generators,
+            phantom: PhantomData,
         }
     }
 }
@@ -203,9 +827,18 @@ pub trait SwMsmHandle {
     /// Type of an Affine curve element
     type AffineElement;
 
+    /// Type of a projective curve element
+    type ProjectiveElement;
+
     /// Create a handle from affine generators
     fn new_with_affine(generators: &[Self::AffineElement]) -> Self;
 
+    /// Create a handle from projective generators
+    ///
+    /// The generators are converted without any field inversion and normalized by the
+    /// backend, so they do not need to be converted to affine form beforehand.
+    fn new_with_projective(generators: &[Self::ProjectiveElement]) -> Self;
+
     /// Compute a MSM with the result given as affine elements
     fn affine_msm(&self, res: &mut [Self::AffineElement], element_num_bytes: u32, scalars: &[u8]);
 
@@ -229,12 +862,18 @@ pub trait SwMsmHandle {
 
 impl<C: SwCurveConfig + Clone> SwMsmHandle for MsmHandle<ElementP2<C>> {
     type AffineElement = Affine<C>;
+    type ProjectiveElement = Projective<C>;
 
     fn new_with_affine(generators: &[Self::AffineElement]) -> Self {
         let generators: Vec<ElementP2<C>> = generators.iter().map(|e| e.into()).collect();
         MsmHandle::new(&generators)
     }
 
+    fn new_with_projective(generators: &[Self::ProjectiveElement]) -> Self {
+        let generators: Vec<ElementP2<C>> = generators.par_iter().map(|e| e.into()).collect();
+        MsmHandle::new(&generators)
+    }
+
     fn affine_msm(&self, res: &mut [Self::AffineElement], element_num_bytes: u32, scalars: &[u8]) {
         let mut res_p: Vec<ElementP2<C>> = vec![ElementP2::<C>::default(); res.len()];
         self.msm(&mut res_p, element_num_bytes, scalars);
@@ -271,6 +910,76 @@ impl<C: SwCurveConfig + Clone> SwMsmHandle for MsmHandle<ElementP2<C>> {
     }
 }
 
+/// Extend MsmHandle to write compressed results for ristretto255 elements
+pub trait RistrettoMsmHandle {
+    /// Compute a MSM with the result given as compressed elements
+    fn compressed_msm(
+        &self,
+        res: &mut [CompressedRistretto],
+        element_num_bytes: u32,
+        scalars: &[u8],
+    );
+
+    /// Compute a packed MSM with the result given as compressed elements
+    fn compressed_packed_msm(
+        &self,
+        res: &mut [CompressedRistretto],
+        output_bit_table: &[u32],
+        scalars: &[u8],
+    );
+
+    /// Compute a variable length MSM with the result given as compressed elements
+    fn compressed_vlen_msm(
+        &self,
+        res: &mut [CompressedRistretto],
+        output_bit_table: &[u32],
+        output_lengths: &[u32],
+        scalars: &[u8],
+    );
+}
+
+impl RistrettoMsmHandle for MsmHandle<RistrettoPoint> {
+    fn compressed_msm(
+        &self,
+        res: &mut [CompressedRistretto],
+        element_num_bytes: u32,
+        scalars: &[u8],
+    ) {
+        let mut res_p = vec![RistrettoPoint::default(); res.len()];
+        self.msm(&mut res_p, element_num_bytes, scalars);
+        res.par_iter_mut().zip(res_p).for_each(|(resi, resi_p)| {
+            *resi = resi_p.compress();
+        });
+    }
+
+    fn compressed_packed_msm(
+        &self,
+        res: &mut [CompressedRistretto],
+        output_bit_table: &[u32],
+        scalars: &[u8],
+    ) {
+        let mut res_p = vec![RistrettoPoint::default(); res.len()];
+        self.packed_msm(&mut res_p, output_bit_table, scalars);
+        res.par_iter_mut().zip(res_p).for_each(|(resi, resi_p)| {
+            *resi = resi_p.compress();
+        });
+    }
+
+    fn compressed_vlen_msm(
+        &self,
+        res: &mut [CompressedRistretto],
+        output_bit_table: &[u32],
+        output_lengths: &[u32],
+        scalars: &[u8],
+    ) {
+        let mut res_p = vec![RistrettoPoint::default(); res.len()];
+        self.vlen_msm(&mut res_p, output_bit_table, output_lengths, scalars);
+        res.par_iter_mut().zip(res_p).for_each(|(resi, resi_p)| {
+            *resi = resi_p.compress();
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
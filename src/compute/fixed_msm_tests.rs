@@ -1,8 +1,21 @@
 use super::*;
+#[cfg(any(feature = "bls12_381", feature = "bn254"))]
 use crate::compute::ElementP2;
-use ark_bls12_381::G1Affine;
+use crate::compute::{MsmError, ScalarMatrix, ScalarWidth};
+#[cfg(feature = "bls12_381")]
+use ark_bls12_381::{Fr, G1Affine};
+#[cfg(feature = "bn254")]
+use ark_ec::AffineRepr;
+#[cfg(any(feature = "bls12_381", feature = "bn254"))]
+use ark_ec::CurveGroup;
+#[cfg(feature = "bls12_381")]
+use ark_ec::VariableBaseMSM;
+#[cfg(any(feature = "bls12_381", feature = "bn254"))]
 use ark_std::UniformRand;
-use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+};
 use rand_core::OsRng;
 use tempfile::TempDir;
 
@@ -49,6 +62,58 @@ fn we_can_compute_msms_using_multiple_generator() {
     assert_eq!(res[0], generators[0] + generators[1] + generators[1]);
 }
 
+#[test]
+fn we_can_compute_msms_with_a_typed_scalar_width() {
+    let mut rng = OsRng;
+    let generators: Vec<RistrettoPoint> =
+        (0..2).map(|_| RistrettoPoint::random(&mut rng)).collect();
+    let handle = MsmHandle::new(&generators);
+
+    // g[0] + 2 * g[1] and 3 * g[0] + 4 * g[1]
+    let scalars: Vec<u8> = [1_u16, 3, 2, 4]
+        .iter()
+        .flat_map(|x| x.to_le_bytes())
+        .collect();
+    let mut res = vec![RistrettoPoint::default(); 2];
+    handle.try_msm(&mut res, ScalarWidth::B2, &scalars).unwrap();
+    let mut expected = vec![RistrettoPoint::default(); 2];
+    handle.msm(&mut expected, 2, &scalars);
+    assert_eq!(res, expected);
+    assert_eq!(res[0], generators[0] + generators[1] + generators[1]);
+}
+
+#[test]
+fn we_cannot_compute_msms_with_an_invalid_scalar_layout() {
+    let generators = vec![RistrettoPoint::default(); 2];
+    let handle = MsmHandle::new(&generators);
+    let mut res = vec![RistrettoPoint::default(); 2];
+
+    assert_eq!(
+        handle.try_msm(&mut res, ScalarWidth::B4, &[0; 12]),
+        Err(MsmError::InvalidScalarsLength {
+            num_bytes: 12,
+            row_num_bytes: 8
+        })
+    );
+    assert_eq!(
+        handle.try_msm(&mut res, ScalarWidth::B1, &[0; 6]),
+        Err(MsmError::NotEnoughGenerators {
+            n: 3,
+            num_generators: 2
+        })
+    );
+}
+
+#[test]
+fn we_can_compute_msms_with_sums_of_zero_byte_scalars() {
+    let generators = vec![RistrettoPoint::random(&mut OsRng); 2];
+    let handle = MsmHandle::new(&generators);
+    let mut res = vec![generators[0]; 2];
+    let sum = handle.msm_with_sum(&mut res, 0, &[]);
+    assert_eq!(sum, RistrettoPoint::default());
+    assert_eq!(res, vec![RistrettoPoint::default(); 2]);
+}
+
 #[test]
 fn we_can_serialize_a_handle_to_a_file() {
     let mut rng = OsRng;
@@ -65,10 +130,14 @@ fn we_can_serialize_a_handle_to_a_file() {
     // write the handle to a file
     let tmp_dir = TempDir::new().unwrap();
     let filename = tmp_dir.path().join("t").to_str().unwrap().to_string();
-    handle.write(&filename);
+    handle.write(&filename).unwrap();
+
+    // the header is written next to the handle
+    assert!(tmp_dir.path().join("t.header").exists());
 
     // read the handle back from file
-    let handle = MsmHandle::<RistrettoPoint>::new_from_file(&filename);
+    let handle = MsmHandle::<RistrettoPoint>::new_from_file(&filename).unwrap();
+    assert_eq!(handle.num_generators(), 2);
 
     // we can compute a multiexponentiation
     let scalars: Vec<u8> = vec![1, 2];
@@ -76,6 +145,45 @@ fn we_can_serialize_a_handle_to_a_file() {
     assert_eq!(res[0], generators[0] + generators[1] + generators[1]);
 }
 
+#[test]
+#[cfg(feature = "bls12_381")]
+fn we_cannot_read_a_handle_file_written_for_a_different_curve() {
+    let mut rng = OsRng;
+
+    // randomly obtain the generator points
+    let generators: Vec<RistrettoPoint> =
+        (0..2).map(|_| RistrettoPoint::random(&mut rng)).collect();
+
+    // write the handle to a file
+    let handle = MsmHandle::new(&generators);
+    let tmp_dir = TempDir::new().unwrap();
+    let filename = tmp_dir.path().join("t").to_str().unwrap().to_string();
+    handle.write(&filename).unwrap();
+
+    // read the handle back as a handle of another curve
+    let res = MsmHandle::<ElementP2<ark_bls12_381::g1::Config>>::new_from_file(&filename);
+    assert!(matches!(res, Err(HandleFileError::CurveMismatch { .. })));
+}
+
+#[test]
+fn we_cannot_read_a_handle_file_without_a_header() {
+    let tmp_dir = TempDir::new().unwrap();
+    let path = tmp_dir.path().join("t");
+    std::fs::write(&path, [1_u8; 64]).unwrap();
+
+    let res = MsmHandle::<RistrettoPoint>::new_from_file(path.to_str().unwrap());
+    assert!(matches!(res, Err(HandleFileError::InvalidHeader)));
+
+    std::fs::write(tmp_dir.path().join("t.header"), [1_u8; 24]).unwrap();
+    let res = MsmHandle::<RistrettoPoint>::new_from_file(path.to_str().unwrap());
+    assert!(matches!(res, Err(HandleFileError::InvalidHeader)));
+
+    let res = MsmHandle::<RistrettoPoint>::new_from_file(
+        tmp_dir.path().join("missing").to_str().unwrap(),
+    );
+    assert!(matches!(res, Err(HandleFileError::Io(_))));
+}
+
 #[test]
 fn we_can_compute_msms_using_multiple_outputs() {
     let mut rng = OsRng;
@@ -149,6 +257,7 @@ fn we_can_compute_variable_length_msms() {
 }
 
 #[test]
+#[cfg(feature = "bls12_381")]
 fn we_can_compute_msms_using_a_single_generator_bls12_381() {
     let mut rng = ark_std::test_rng();
 
@@ -171,6 +280,7 @@ fn we_can_compute_msms_using_a_single_generator_bls12_381() {
 }
 
 #[test]
+#[cfg(feature = "bls12_381")]
 fn for_short_weierstrass_curvs_we_can_compute_msms_with_affine_elements() {
     let mut rng = ark_std::test_rng();
 
@@ -198,3 +308,607 @@ fn for_short_weierstrass_curvs_we_can_compute_msms_with_affine_elements() {
     handle.affine_vlen_msm(&mut res, &output_bit_table, &output_lengths, &scalars);
     assert_eq!(res[0], g + g);
 }
+
+#[test]
+#[cfg(feature = "bls12_381")]
+fn we_can_create_handles_from_projective_generators() {
+    let mut rng = ark_std::test_rng();
+
+    // sums of random points have z != 1
+    let generators: Vec<ark_bls12_381::G1Projective> = (0..2)
+        .map(|_| {
+            ark_bls12_381::G1Projective::rand(&mut rng)
+                + ark_bls12_381::G1Projective::rand(&mut rng)
+        })
+        .collect();
+    let handle: MsmHandle<ElementP2<ark_bls12_381::g1::Config>> =
+        MsmHandle::new_with_projective(&generators);
+
+    // 2 * g[0] + 3 * g[1]
+    let mut res = vec![G1Affine::default(); 1];
+    handle.affine_msm(&mut res, 1, &[2, 3]);
+    let expected = generators[0] + generators[0] + generators[1] * Fr::from(3_u8);
+    assert_eq!(res[0], expected.into_affine());
+}
+
+#[test]
+fn we_can_compute_msms_from_dalek_scalars() {
+    let mut rng = OsRng;
+
+    let mut res = vec![RistrettoPoint::default(); 2];
+
+    // randomly obtain the generator points
+    let generators: Vec<RistrettoPoint> =
+        (0..2).map(|_| RistrettoPoint::random(&mut rng)).collect();
+
+    // create handle
+    let handle = MsmHandle::new(&generators);
+
+    // s[0] * g[0] + s[2] * g[1]
+    // s[1] * g[0] + s[3] * g[1]
+    let scalars: Vec<Scalar> = (0..4).map(|_| Scalar::random(&mut rng)).collect();
+    handle.msm_from_scalars(&mut res, &scalars);
+    assert_eq!(
+        res[0],
+        scalars[0] * generators[0] + scalars[2] * generators[1]
+    );
+    assert_eq!(
+        res[1],
+        scalars[1] * generators[0] + scalars[3] * generators[1]
+    );
+}
+
+#[test]
+#[cfg(feature = "bls12_381")]
+fn we_can_compute_msms_from_ark_scalars() {
+    let mut rng = ark_std::test_rng();
+
+    let mut res = vec![ElementP2::<ark_bls12_381::g1::Config>::default(); 1];
+
+    // randomly obtain the generator points
+    let generators: Vec<G1Affine> = (0..2).map(|_| G1Affine::rand(&mut rng)).collect();
+
+    // create handle
+    let handle: MsmHandle<ElementP2<ark_bls12_381::g1::Config>> =
+        MsmHandle::new_with_affine(&generators);
+
+    // s[0] * g[0] + s[1] * g[1]
+    let scalars: Vec<Fr> = (0..2).map(|_| Fr::rand(&mut rng)).collect();
+    handle.msm_from_scalars(&mut res, &scalars);
+    let r: G1Affine = res[0].clone().into();
+    assert_eq!(
+        r,
+        (generators[0] * scalars[0] + generators[1] * scalars[1]).into_affine()
+    );
+}
+
+#[test]
+fn we_can_compute_msms_from_a_scalar_matrix() {
+    let mut rng = OsRng;
+
+    let mut res = vec![RistrettoPoint::default(); 2];
+
+    // randomly obtain the generator points
+    let generators: Vec<RistrettoPoint> =
+        (0..3).map(|_| RistrettoPoint::random(&mut rng)).collect();
+
+    // create handle
+    let handle = MsmHandle::new(&generators);
+
+    // s[0][0] * g[0] + s[0][1] * g[1] + s[0][2] * g[2]
+    // s[1][0] * g[0]
+    let scalars: Vec<Vec<Scalar>> = vec![
+        (0..3).map(|_| Scalar::random(&mut rng)).collect(),
+        (0..1).map(|_| Scalar::random(&mut rng)).collect(),
+    ];
+    let matrix = ScalarMatrix::from_scalars(scalars.iter().map(Vec::as_slice));
+    handle.matrix_msm(&mut res, &matrix);
+    assert_eq!(
+        res[0],
+        scalars[0][0] * generators[0]
+            + scalars[0][1] * generators[1]
+            + scalars[0][2] * generators[2]
+    );
+    assert_eq!(res[1], scalars[1][0] * generators[0]);
+}
+
+#[test]
+fn we_can_compute_msms_from_the_scalars_of_each_output() {
+    let mut rng = OsRng;
+
+    // randomly obtain the generator points
+    let generators: Vec<RistrettoPoint> =
+        (0..3).map(|_| RistrettoPoint::random(&mut rng)).collect();
+
+    // create handle
+    let handle = MsmHandle::new(&generators);
+
+    // g[0] + 2 * g[1] + 3 * g[2]
+    // 4 * g[0] + 5 * g[1]
+    // 6 * g[0] + 7 * g[1] + 8 * g[2]
+    let outputs: Vec<Vec<u8>> = vec![
+        vec![1, 0, 2, 0, 3, 0],
+        vec![4, 0, 5, 0],
+        vec![6, 0, 7, 0, 8, 0],
+    ];
+    let mut res = vec![RistrettoPoint::default(); 3];
+    handle.msm_from_outputs(&mut res, 2, &outputs);
+
+    let mut expected = vec![RistrettoPoint::default(); 3];
+    handle.msm(
+        &mut expected,
+        2,
+        &[1, 0, 4, 0, 6, 0, 2, 0, 5, 0, 7, 0, 3, 0, 0, 0, 8, 0],
+    );
+    assert_eq!(res, expected);
+}
+
+#[test]
+#[should_panic(expected = "res must hold one element for each output")]
+fn we_cannot_compute_msms_from_outputs_with_the_wrong_number_of_results() {
+    let handle = MsmHandle::new(&[RistrettoPoint::default()]);
+    let mut res = vec![RistrettoPoint::default(); 1];
+    handle.msm_from_outputs(&mut res, 1, &[[1_u8], [2_u8]]);
+}
+
+#[test]
+fn we_can_compute_msms_into_uninitialized_memory() {
+    let mut rng = OsRng;
+
+    // randomly obtain the generator points
+    let generators: Vec<RistrettoPoint> =
+        (0..2).map(|_| RistrettoPoint::random(&mut rng)).collect();
+
+    // create handle
+    let handle = MsmHandle::new(&generators);
+
+    // g[0] + 3 * g[1]
+    // 2 * g[0] + 4 * g[1]
+    let scalars: Vec<u8> = vec![1, 2, 3, 4];
+    let mut res = [std::mem::MaybeUninit::uninit(); 2];
+    let res = handle.msm_uninit(&mut res, 1, &scalars);
+    assert_eq!(res[0], generators[0] + generators[1] * Scalar::from(3_u8));
+    assert_eq!(
+        res[1],
+        generators[0] * Scalar::from(2_u8) + generators[1] * Scalar::from(4_u8)
+    );
+}
+
+#[test]
+fn we_can_compute_sparse_msms() {
+    let mut rng = OsRng;
+
+    let mut res = vec![RistrettoPoint::default(); 2];
+
+    // randomly obtain the generator points
+    let generators: Vec<RistrettoPoint> =
+        (0..4).map(|_| RistrettoPoint::random(&mut rng)).collect();
+
+    // 2 * g[3] + g[1]
+    // 3 * g[3]
+    let handle = MsmHandle::new(&generators);
+    let indexes: Vec<u32> = vec![3, 1];
+    let scalars: Vec<u8> = vec![2, 3, 1, 0];
+    handle.sparse_msm(&mut res, 1, &indexes, &scalars);
+    assert_eq!(res[0], generators[3] + generators[3] + generators[1]);
+    assert_eq!(res[1], generators[3] + generators[3] + generators[3]);
+}
+
+#[test]
+fn sparse_msms_match_msms_with_the_zero_scalars_included() {
+    let generators: Vec<RistrettoPoint> =
+        (0..6).map(|_| RistrettoPoint::random(&mut OsRng)).collect();
+    let handle = MsmHandle::new(&generators);
+
+    let mut sparse = vec![RistrettoPoint::default(); 2];
+    handle.sparse_msm(
+        &mut sparse,
+        2,
+        &[4, 0, 2],
+        &[1, 0, 2, 0, 3, 0, 4, 0, 5, 0, 6, 1],
+    );
+
+    let dense_scalars = [3, 0, 4, 0, 0, 0, 0, 0, 5, 0, 6, 1, 0, 0, 0, 0, 1, 0, 2, 0];
+    let mut dense = vec![RistrettoPoint::default(); 2];
+    handle.msm(&mut dense, 2, &dense_scalars);
+    assert_eq!(sparse, dense);
+}
+
+#[test]
+fn sparse_msms_with_zero_size_rows_return_the_identity() {
+    let generators: Vec<RistrettoPoint> =
+        (0..4).map(|_| RistrettoPoint::random(&mut OsRng)).collect();
+
+    let handle = MsmHandle::new(&generators);
+
+    handle.sparse_msm(&mut [], 1, &[3, 1], &[]);
+
+    let mut res = vec![generators[0]; 2];
+    handle.sparse_msm(&mut res, 0, &[3, 1], &[]);
+    assert_eq!(res, vec![RistrettoPoint::default(); 2]);
+}
+
+#[test]
+#[should_panic(expected = "indexes must be distinct")]
+fn we_cannot_compute_sparse_msms_with_duplicate_indexes() {
+    let generators = vec![RistrettoPoint::default(); 4];
+    let mut res = vec![RistrettoPoint::default(); 1];
+    MsmHandle::new(&generators).sparse_msm(&mut res, 1, &[1, 2, 1], &[1, 2, 3]);
+}
+
+#[test]
+#[should_panic(expected = "index 4 is out of range for 4 generators")]
+fn we_cannot_compute_sparse_msms_with_out_of_range_indexes() {
+    let generators = vec![RistrettoPoint::default(); 4];
+    let mut res = vec![RistrettoPoint::default(); 1];
+    MsmHandle::new(&generators).sparse_msm(&mut res, 1, &[1, 4], &[1, 2]);
+}
+
+#[test]
+fn for_ristretto_we_can_compute_msms_with_compressed_elements() {
+    let mut rng = OsRng;
+
+    let mut res = vec![CompressedRistretto::default(); 1];
+
+    // randomly obtain the generator points
+    let generators: Vec<RistrettoPoint> =
+        (0..1).map(|_| RistrettoPoint::random(&mut rng)).collect();
+
+    let g = generators[0];
+
+    // create handle
+    let handle = MsmHandle::new(&generators);
+
+    // 2 * g
+    let scalars: Vec<u8> = vec![2];
+    handle.compressed_msm(&mut res, 1, &scalars);
+    assert_eq!(res[0], (g + g).compress());
+
+    let output_bit_table: Vec<u32> = vec![2];
+    handle.compressed_packed_msm(&mut res, &output_bit_table, &scalars);
+    assert_eq!(res[0], (g + g).compress());
+
+    let output_lengths: Vec<u32> = vec![1];
+    handle.compressed_vlen_msm(&mut res, &output_bit_table, &output_lengths, &scalars);
+    assert_eq!(res[0], (g + g).compress());
+}
+
+#[test]
+fn we_can_compute_msms_with_different_element_sizes() {
+    let mut rng = OsRng;
+
+    let generators: Vec<RistrettoPoint> =
+        (0..3).map(|_| RistrettoPoint::random(&mut rng)).collect();
+    let handle = MsmHandle::new(&generators);
+
+    let narrow: Vec<u8> = vec![1, 2, 3];
+    let wide: Vec<Scalar> = (0..3).map(|_| Scalar::random(&mut rng)).collect();
+    let wide_bytes: Vec<u8> = wide.iter().flat_map(|s| *s.as_bytes()).collect();
+    let medium: Vec<u8> = [300_u16, 0, 7]
+        .iter()
+        .flat_map(|x| x.to_le_bytes())
+        .collect();
+
+    let mut res = vec![RistrettoPoint::default(); 3];
+    handle.mixed_msm(&mut res, &[1, 32, 2], &[&narrow, &wide_bytes, &medium]);
+
+    let mut expected = vec![RistrettoPoint::default(); 1];
+    handle.msm(&mut expected, 1, &narrow);
+    assert_eq!(res[0], expected[0]);
+    handle.msm(&mut expected, 32, &wide_bytes);
+    assert_eq!(res[1], expected[0]);
+    handle.msm(&mut expected, 2, &medium);
+    assert_eq!(res[2], expected[0]);
+}
+
+#[test]
+#[should_panic(expected = "every output must have the same number of scalars")]
+fn we_cannot_compute_mixed_msms_with_outputs_of_different_lengths() {
+    let generators: Vec<RistrettoPoint> =
+        (0..3).map(|_| RistrettoPoint::random(&mut OsRng)).collect();
+    let handle = MsmHandle::new(&generators);
+    let mut res = vec![RistrettoPoint::default(); 2];
+    handle.mixed_msm(&mut res, &[1, 2], &[&[1, 2], &[1, 2]]);
+}
+
+#[test]
+fn we_can_clone_a_handle_and_use_it_after_the_original_is_dropped() {
+    let generators: Vec<RistrettoPoint> =
+        (0..2).map(|_| RistrettoPoint::random(&mut OsRng)).collect();
+    let handle = MsmHandle::new(&generators);
+    let clone = handle.clone();
+    drop(handle);
+
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let handle = clone.clone();
+            std::thread::spawn(move || {
+                let mut res = vec![RistrettoPoint::default(); 1];
+                handle.msm(&mut res, 1, &[1, 2]);
+                res[0]
+            })
+        })
+        .collect();
+    let results: Vec<RistrettoPoint> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+    assert_eq!(clone.num_generators(), 2);
+    assert!(results
+        .iter()
+        .all(|res| *res == generators[0] + generators[1] + generators[1]));
+}
+
+#[test]
+fn empty_msms_return_the_identity_without_calling_the_backend() {
+    let generators: Vec<RistrettoPoint> =
+        (0..2).map(|_| RistrettoPoint::random(&mut OsRng)).collect();
+    let handle = MsmHandle::new(&generators);
+    let non_identity = generators[0];
+
+    handle.msm(&mut [], 4, &[1, 2, 3]);
+
+    let mut res = vec![non_identity; 2];
+    handle.msm(&mut res, 4, &[]);
+    assert_eq!(res, vec![RistrettoPoint::default(); 2]);
+
+    let mut res = vec![non_identity; 2];
+    handle.sparse_msm(&mut res, 4, &[], &[]);
+    assert_eq!(res, vec![RistrettoPoint::default(); 2]);
+
+    let mut res = vec![non_identity; 2];
+    handle.msm_from_scalars::<Scalar>(&mut res, &[]);
+    assert_eq!(res, vec![RistrettoPoint::default(); 2]);
+
+    let mut res = vec![non_identity; 2];
+    handle.packed_msm(&mut res, &[3, 1], &[]);
+    assert_eq!(res, vec![RistrettoPoint::default(); 2]);
+    handle.packed_msm(&mut [], &[], &[]);
+    handle.packed_msm(&mut [], &[], &[1, 2]);
+
+    let mut res = vec![non_identity; 2];
+    handle.vlen_msm(&mut res, &[3, 1], &[0, 0], &[]);
+    assert_eq!(res, vec![RistrettoPoint::default(); 2]);
+    handle.vlen_msm(&mut [], &[], &[], &[]);
+
+    let mut res = vec![non_identity; 2];
+    handle.mixed_msm(&mut res, &[1, 32], &[&[], &[]]);
+    assert_eq!(res, vec![RistrettoPoint::default(); 2]);
+    handle.mixed_msm(&mut [], &[], &[]);
+
+    let mut res = [std::mem::MaybeUninit::uninit(); 2];
+    assert_eq!(
+        handle.msm_uninit(&mut res, 1, &[]),
+        &[RistrettoPoint::default(); 2]
+    );
+
+    let mut res = vec![non_identity; 2];
+    handle.msm_with_offset(&mut res, 1, &[], 1);
+    assert_eq!(res, vec![RistrettoPoint::default(); 2]);
+
+    let mut res = vec![non_identity; 2];
+    handle.packed_msm_with_offset(&mut res, &[3, 1], &[], 1);
+    assert_eq!(res, vec![RistrettoPoint::default(); 2]);
+
+    let mut res = vec![non_identity; 2];
+    handle.vlen_msm_with_offset(&mut res, &[3, 1], &[0, 0], &[], 1);
+    assert_eq!(res, vec![RistrettoPoint::default(); 2]);
+
+    let mut res = vec![non_identity; 2];
+    handle.packed_msm_signed(&mut res, &[3, 1], &[true, false], &[]);
+    assert_eq!(res, vec![RistrettoPoint::default(); 2]);
+
+    assert_eq!(handle.msm_sum(2, 1, &[]), RistrettoPoint::default());
+    let mut res = vec![non_identity; 2];
+    assert_eq!(
+        handle.msm_with_sum(&mut res, 1, &[]),
+        RistrettoPoint::default()
+    );
+    assert_eq!(res, vec![RistrettoPoint::default(); 2]);
+}
+
+#[test]
+#[should_panic(expected = "element_num_bytes_table entries must be between 1 and 32, found 0")]
+fn we_cannot_compute_mixed_msms_with_zero_byte_scalars() {
+    let generators = vec![RistrettoPoint::default(); 2];
+    let handle = MsmHandle::new(&generators);
+    let mut res = vec![RistrettoPoint::default(); 2];
+    handle.mixed_msm(&mut res, &[0, 1], &[&[], &[1, 2]]);
+}
+
+#[test]
+#[should_panic(expected = "element_num_bytes_table entries must be between 1 and 32, found 33")]
+fn we_cannot_compute_mixed_msms_with_scalars_wider_than_32_bytes() {
+    let generators = vec![RistrettoPoint::default(); 2];
+    let handle = MsmHandle::new(&generators);
+    let mut res = vec![RistrettoPoint::default(); 1];
+    handle.mixed_msm(&mut res, &[33], &[&[0; 33]]);
+}
+
+#[test]
+#[should_panic(expected = "element_num_bytes must be non-zero for non-empty scalars")]
+fn we_cannot_compute_msms_of_zero_byte_scalars_with_non_empty_scalars() {
+    let generators = vec![RistrettoPoint::default(); 2];
+    let handle = MsmHandle::new(&generators);
+    let mut res = vec![RistrettoPoint::default(); 2];
+    handle.msm(&mut res, 0, &[1, 2]);
+}
+
+#[test]
+#[should_panic(expected = "element_num_bytes must be non-zero for non-empty scalars")]
+fn we_cannot_compute_msm_sums_of_zero_byte_scalars_with_non_empty_scalars() {
+    let generators = vec![RistrettoPoint::default(); 2];
+    let handle = MsmHandle::new(&generators);
+    handle.msm_sum(2, 0, &[1, 2]);
+}
+
+#[test]
+#[should_panic(expected = "element_num_bytes must be non-zero for non-empty scalars")]
+fn we_cannot_compute_msms_with_sums_of_zero_byte_scalars_with_non_empty_scalars() {
+    let generators = vec![RistrettoPoint::default(); 2];
+    let handle = MsmHandle::new(&generators);
+    let mut res = vec![RistrettoPoint::default(); 2];
+    handle.msm_with_sum(&mut res, 0, &[1, 2]);
+}
+
+#[test]
+#[should_panic(expected = "output_bit_table must have a non-zero total bit width")]
+fn we_cannot_compute_packed_msms_of_zero_bit_scalars_with_non_empty_scalars() {
+    let generators = vec![RistrettoPoint::default(); 2];
+    let handle = MsmHandle::new(&generators);
+    let mut res = vec![RistrettoPoint::default(); 2];
+    handle.packed_msm(&mut res, &[0, 0], &[1, 2]);
+}
+
+#[test]
+fn we_can_compute_msms_with_a_generator_offset() {
+    let generators: Vec<RistrettoPoint> =
+        (0..6).map(|_| RistrettoPoint::random(&mut OsRng)).collect();
+    let handle = MsmHandle::new(&generators);
+    let offset_handle = MsmHandle::new(&generators[2..]);
+
+    let scalars: Vec<u8> = vec![1, 2, 3, 4, 5, 6];
+    let mut res = vec![RistrettoPoint::default(); 2];
+    let mut expected = vec![RistrettoPoint::default(); 2];
+    handle.msm_with_offset(&mut res, 1, &scalars, 2);
+    offset_handle.msm(&mut expected, 1, &scalars);
+    assert_eq!(res, expected);
+
+    let output_bit_table: Vec<u32> = vec![3, 1];
+    let scalars: Vec<u8> = vec![0b1001, 0b0011, 0b1111];
+    handle.packed_msm_with_offset(&mut res, &output_bit_table, &scalars, 2);
+    offset_handle.packed_msm(&mut expected, &output_bit_table, &scalars);
+    assert_eq!(res, expected);
+
+    let output_lengths: Vec<u32> = vec![1, 3];
+    handle.vlen_msm_with_offset(&mut res, &output_bit_table, &output_lengths, &scalars, 3);
+    let offset_handle = MsmHandle::new(&generators[3..]);
+    offset_handle.vlen_msm(&mut expected, &output_bit_table, &output_lengths, &scalars);
+    assert_eq!(res, expected);
+}
+
+#[test]
+#[should_panic(expected = "offset + n must not exceed the number of generators of the handle")]
+fn we_cannot_compute_msms_past_the_last_generator() {
+    let generators: Vec<RistrettoPoint> =
+        (0..3).map(|_| RistrettoPoint::random(&mut OsRng)).collect();
+    let handle = MsmHandle::new(&generators);
+    let mut res = vec![RistrettoPoint::default(); 1];
+    handle.msm_with_offset(&mut res, 1, &[1, 2], 2);
+}
+
+#[test]
+#[cfg(feature = "bls12_381")]
+fn we_can_compute_packed_msms_with_signed_outputs() {
+    let n = 6;
+    let a: Vec<i16> = vec![-3, 7, i16::MIN, i16::MAX, -1, 0];
+    let b: Vec<u8> = vec![255, 1, 2, 3, 128, 9];
+    let c: Vec<i8> = vec![-8, 7, -1, 0, 3, -5];
+    // rows hold a (16 bits), b (8 bits) and c (4 bits)
+    let scalars: Vec<u8> = (0..n)
+        .flat_map(|i| {
+            let [a0, a1] = a[i].to_le_bytes();
+            [a0, a1, b[i], (c[i] as u8) & 0xf]
+        })
+        .collect();
+    let output_bit_table = [16, 8, 4];
+    let output_signed = [true, false, true];
+
+    let to_scalar = |x: i64| {
+        if x < 0 {
+            -Scalar::from(x.unsigned_abs())
+        } else {
+            Scalar::from(x as u64)
+        }
+    };
+    let generators: Vec<RistrettoPoint> =
+        (0..n).map(|_| RistrettoPoint::random(&mut OsRng)).collect();
+    let expected: Vec<RistrettoPoint> = [
+        a.iter().map(|x| *x as i64).collect::<Vec<_>>(),
+        b.iter().map(|x| *x as i64).collect(),
+        c.iter().map(|x| *x as i64).collect(),
+    ]
+    .iter()
+    .map(|column| {
+        column
+            .iter()
+            .zip(&generators)
+            .map(|(x, g)| to_scalar(*x) * g)
+            .sum()
+    })
+    .collect();
+
+    let handle = MsmHandle::new(&generators);
+    let mut res = vec![RistrettoPoint::default(); 3];
+    handle.packed_msm_signed(&mut res, &output_bit_table, &output_signed, &scalars);
+    assert_eq!(res, expected);
+
+    // without signed outputs, this is the same as packed_msm
+    let mut unsigned = vec![RistrettoPoint::default(); 3];
+    handle.packed_msm(&mut unsigned, &output_bit_table, &scalars);
+    handle.packed_msm_signed(&mut res, &output_bit_table, &[false; 3], &scalars);
+    assert_eq!(res, unsigned);
+
+    let mut rng = ark_std::test_rng();
+    let generators: Vec<G1Affine> = (0..n).map(|_| G1Affine::rand(&mut rng)).collect();
+    let handle = MsmHandle::new(
+        &generators
+            .iter()
+            .map(ElementP2::<ark_bls12_381::g1::Config>::from)
+            .collect::<Vec<_>>(),
+    );
+    let mut res = vec![ElementP2::default(); 3];
+    handle.packed_msm_signed(&mut res, &output_bit_table, &output_signed, &scalars);
+    let c_scalars: Vec<Fr> = c.iter().map(|x| Fr::from(*x as i64)).collect();
+    let expected = ark_bls12_381::G1Projective::msm(&generators, &c_scalars).unwrap();
+    assert_eq!(G1Affine::from(&res[2]), expected.into_affine());
+}
+
+#[test]
+fn we_can_compute_the_sum_of_the_outputs_of_an_msm() {
+    let n = 5;
+    let generators: Vec<RistrettoPoint> =
+        (0..n).map(|_| RistrettoPoint::random(&mut OsRng)).collect();
+    let handle = MsmHandle::new(&generators);
+
+    // 300 outputs of 1 byte need 2 more bytes, 3 outputs of 32 bytes exceed 256 bits
+    for (num_outputs, element_num_bytes) in [(1, 8), (300, 1), (3, 32), (2, 4)] {
+        let scalars: Vec<u8> = (0..n * num_outputs * element_num_bytes)
+            .map(|i| (i * 37 + 255) as u8)
+            .collect();
+        let mut res = vec![RistrettoPoint::default(); num_outputs];
+        handle.msm(&mut res, element_num_bytes as u32, &scalars);
+        let expected: RistrettoPoint = res.iter().sum();
+
+        assert_eq!(
+            handle.msm_sum(num_outputs, element_num_bytes as u32, &scalars),
+            expected
+        );
+        let mut with_sum = vec![RistrettoPoint::default(); num_outputs];
+        let sum = handle.msm_with_sum(&mut with_sum, element_num_bytes as u32, &scalars);
+        assert_eq!(sum, expected);
+        assert_eq!(with_sum, res);
+    }
+    assert_eq!(handle.msm_sum(0, 32, &[]), RistrettoPoint::default());
+}
+
+#[test]
+#[cfg(feature = "bn254")]
+fn we_can_compute_the_sum_of_the_outputs_of_an_msm_on_a_short_weierstrass_curve() {
+    let mut rng = ark_std::test_rng();
+    let n = 4;
+    let generators: Vec<ElementP2<ark_bn254::g1::Config>> = (0..n)
+        .map(|_| ark_bn254::G1Affine::rand(&mut rng).into())
+        .collect();
+    let handle = MsmHandle::new(&generators);
+    let scalars = vec![0xff_u8; n * 3 * 32];
+
+    let mut res = vec![ElementP2::default(); 3];
+    let sum = handle.msm_with_sum(&mut res, 32, &scalars);
+    let expected: ark_bn254::G1Projective = res
+        .iter()
+        .map(|x| ark_bn254::G1Affine::from(x).into_group())
+        .sum();
+    assert_eq!(ark_bn254::G1Affine::from(sum), expected.into_affine());
+    assert_eq!(
+        ark_bn254::G1Affine::from(handle.msm_sum(3, 32, &scalars)),
+        expected.into_affine()
+    );
+}
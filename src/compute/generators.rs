@@ -11,8 +11,15 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
-use super::backend::init_backend;
-use curve25519_dalek::ristretto::RistrettoPoint;
+use super::{backend::init_backend, CurveId, ElementP2, SwCurveConfig};
+use ark_ec::{
+    short_weierstrass::{Affine, Projective},
+    AffineRepr, CurveGroup,
+};
+use ark_ff::Field;
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+use rayon::prelude::*;
+use sha2::{Digest, Sha512};
 use std::mem::MaybeUninit;
 
 #[doc = include_str!("../../docs/commitments/get_curve25519_generators.md")]
@@ -22,6 +29,9 @@ use std::mem::MaybeUninit;
 #[doc = include_str!("../../examples/get_generators.rs")]
 /// ```
 pub fn get_curve25519_generators(generators: &mut [RistrettoPoint], offset_generators: u64) {
+    if generators.is_empty() {
+        return;
+    }
     init_backend();
 
     unsafe {
@@ -62,3 +72,130 @@ pub fn get_one_curve25519_commit(n: u64) -> RistrettoPoint {
         one_commit.assume_init()
     }
 }
+
+/// Gets the commitment to a column that is `value` on the rows `start..end` and zero elsewhere.
+///
+/// The commitment is computed from the prefix sums of the generators returned by
+/// [get_one_curve25519_commit], i.e.
+///
+/// ```text
+/// value * (get_one_curve25519_commit(end) - get_one_curve25519_commit(start))
+/// ```
+///
+/// which is the same as `compute_curve25519_commitments` with a generator offset of `0`,
+/// but without transferring the column to the backend. This is useful to commit to the
+/// padding of a column.
+///
+/// # Panics
+///
+/// If `start > end` or if the `get_one_curve25519_commit` calls fail.
+pub fn get_curve25519_range_commit(start: u64, end: u64, value: &Scalar) -> RistrettoPoint {
+    assert!(start <= end, "invalid range {start}..{end}");
+    if start == end || *value == Scalar::ZERO {
+        return RistrettoPoint::default();
+    }
+    value * (get_one_curve25519_commit(end) - get_one_curve25519_commit(start))
+}
+
+/// Gets the commitment to a column that is `value` on the rows `start..end` and zero elsewhere,
+/// using the short Weierstrass `generators`.
+///
+/// This is the counterpart of [get_curve25519_range_commit] for the curves whose generators
+/// are provided by the caller, e.g. to `compute_bn254_g1_uncompressed_commitments_with_generators`.
+/// The generators of the range are summed on the host, so only one scalar multiplication
+/// is needed.
+///
+/// # Panics
+///
+/// If `start > end` or if `end > generators.len()`.
+pub fn get_range_commit_with_generators<C: SwCurveConfig>(
+    generators: &[Affine<C>],
+    start: usize,
+    end: usize,
+    value: &C::ScalarField,
+) -> Affine<C> {
+    let sum: Projective<C> = generators[start..end]
+        .par_iter()
+        .map(|g| g.into_group())
+        .sum();
+    (sum * value).into_affine()
+}
+
+/// Curve elements that can be derived deterministically from a label and an index.
+///
+/// Every derivation starts by hashing
+///
+/// ```text
+/// sha512(label.len() as u64 || label || index as u64 || counter as u32)
+/// ```
+///
+/// where integers are encoded as little-endian bytes.
+///
+/// - For ristretto255, `counter = 0` and the 64-byte digest is mapped to the group
+///   with `RistrettoPoint::from_uniform_bytes`.
+/// - For short Weierstrass curves, `counter = 0, 1, 2, ...` is incremented until the
+///   digest yields a valid base field element `x` (via `Field::from_random_bytes`) with a
+///   point `(x, y)` on the curve. `y` is the larger square root if the last digest byte
+///   is odd and the smaller one otherwise. The point is then multiplied by the cofactor,
+///   and the counter is incremented again if the result is the identity.
+pub trait DeriveGenerator: CurveId + Sized {
+    /// Derive the `index`-th generator for the given `label`.
+    fn derive_generator(label: &[u8], index: u64) -> Self;
+}
+
+fn hash_generator_input(label: &[u8], index: u64, counter: u32) -> [u8; 64] {
+    let mut hasher = Sha512::new();
+    hasher.update((label.len() as u64).to_le_bytes());
+    hasher.update(label);
+    hasher.update(index.to_le_bytes());
+    hasher.update(counter.to_le_bytes());
+    hasher.finalize().into()
+}
+
+impl DeriveGenerator for RistrettoPoint {
+    fn derive_generator(label: &[u8], index: u64) -> Self {
+        RistrettoPoint::from_uniform_bytes(&hash_generator_input(label, index, 0))
+    }
+}
+
+impl<C: SwCurveConfig> DeriveGenerator for ElementP2<C> {
+    fn derive_generator(label: &[u8], index: u64) -> Self {
+        for counter in 0.. {
+            let digest = hash_generator_input(label, index, counter);
+            let Some(x) = C::BaseField::from_random_bytes(&digest) else {
+                continue;
+            };
+            let Some(point) = Affine::<C>::get_point_from_x_unchecked(x, digest[63] & 1 == 1)
+            else {
+                continue;
+            };
+            let point = point.clear_cofactor();
+            if !point.is_zero() {
+                return point.into();
+            }
+        }
+        unreachable!("no generator found for any counter value")
+    }
+}
+
+/// Derives `n` generators reproducibly from a domain separation `label`.
+///
+/// The `i`-th generator is `T::derive_generator(label, i)`, as specified by
+/// [DeriveGenerator]. The derivation runs on the host in parallel and does not
+/// require the backend, so the resulting generators can be computed offline and
+/// passed to `MsmHandle::new` or to the commitment functions taking generators.
+///
+/// # Example - Deriving generators for a handle
+/// ```no_run
+/// use blitzar::compute::{generate_generators, MsmHandle};
+/// use curve25519_dalek::ristretto::RistrettoPoint;
+///
+/// let generators: Vec<RistrettoPoint> = generate_generators(b"SXT-TABLE-v1", 1024);
+/// let handle = MsmHandle::new(&generators);
+/// ```
+pub fn generate_generators<T: DeriveGenerator + Send>(label: &[u8], n: usize) -> Vec<T> {
+    (0..n as u64)
+        .into_par_iter()
+        .map(|i| T::derive_generator(label, i))
+        .collect()
+}
@@ -85,3 +85,102 @@ fn get_one_commit_is_valid() {
     assert_eq!(get_one_curve25519_commit(1), generators[0]);
     assert_eq!(get_one_curve25519_commit(2), generators[0] + generators[1]);
 }
+
+#[test]
+fn we_can_get_range_commits_from_one_commits() {
+    let data: Vec<u64> = vec![0, 0, 7, 7, 7, 0];
+    let mut commitments = vec![CompressedRistretto::default(); 1];
+    compute_curve25519_commitments(&mut commitments, &[(&data[..]).into()], 0);
+
+    assert_eq!(
+        get_curve25519_range_commit(2, 5, &Scalar::from(7_u64)).compress(),
+        commitments[0]
+    );
+    assert_eq!(
+        get_curve25519_range_commit(0, 3, &Scalar::ONE),
+        get_one_curve25519_commit(3)
+    );
+    assert_eq!(
+        get_curve25519_range_commit(4, 4, &Scalar::from(7_u64)),
+        RistrettoPoint::identity()
+    );
+    assert_eq!(
+        get_curve25519_range_commit(1, 4, &Scalar::ZERO),
+        RistrettoPoint::identity()
+    );
+}
+
+#[test]
+#[should_panic]
+fn we_cannot_get_a_range_commit_of_a_reversed_range() {
+    get_curve25519_range_commit(3, 2, &Scalar::ONE);
+}
+
+#[test]
+#[cfg(feature = "bn254")]
+fn we_can_get_range_commits_with_short_weierstrass_generators() {
+    let generators: Vec<ElementP2<ark_bn254::g1::Config>> = generate_generators(b"range", 6);
+    let generators: Vec<ark_bn254::G1Affine> = generators.into_iter().map(Into::into).collect();
+
+    let data: Vec<u64> = vec![0, 3, 3, 3, 0, 0];
+    let mut commitments = vec![ark_bn254::G1Affine::default(); 1];
+    compute_bn254_g1_uncompressed_commitments_with_generators(
+        &mut commitments,
+        &[(&data[..]).into()],
+        &generators,
+    );
+
+    let value = ark_bn254::Fr::from(3_u64);
+    assert_eq!(
+        get_range_commit_with_generators(&generators, 1, 4, &value),
+        commitments[0]
+    );
+    assert_eq!(
+        get_range_commit_with_generators(&generators, 2, 2, &value),
+        ark_bn254::G1Affine::default()
+    );
+}
+
+#[test]
+fn generated_generators_are_reproducible_and_domain_separated() {
+    let g1: Vec<RistrettoPoint> = generate_generators(b"SXT-TABLE-v1", 4);
+    let g2: Vec<RistrettoPoint> = generate_generators(b"SXT-TABLE-v1", 8);
+    let g3: Vec<RistrettoPoint> = generate_generators(b"SXT-TABLE-v2", 4);
+
+    assert_eq!(g1[..], g2[..4]);
+    assert_eq!(g1[1], RistrettoPoint::derive_generator(b"SXT-TABLE-v1", 1));
+    assert!(g1.iter().zip(&g3).all(|(a, b)| a != b));
+    assert!(g1.iter().all(|g| *g != RistrettoPoint::identity()));
+    assert_ne!(g1[0], g1[1]);
+}
+
+#[test]
+#[cfg(all(feature = "bls12_381", feature = "bn254"))]
+fn we_can_generate_short_weierstrass_generators_in_the_prime_order_subgroup() {
+    let g: Vec<ElementP2<ark_bls12_381::g1::Config>> = generate_generators(b"SXT-TABLE-v1", 4);
+    let g: Vec<ark_bls12_381::G1Affine> = g.into_iter().map(Into::into).collect();
+    for gi in &g {
+        assert!(gi.is_on_curve());
+        assert!(gi.is_in_correct_subgroup_assuming_on_curve());
+        assert!(!gi.infinity);
+    }
+    assert_ne!(g[0], g[1]);
+
+    let h: Vec<ElementP2<ark_bls12_381::g1::Config>> = generate_generators(b"SXT-TABLE-v1", 2);
+    let h: Vec<ark_bls12_381::G1Affine> = h.into_iter().map(Into::into).collect();
+    assert_eq!(g[..2], h[..]);
+
+    let g: Vec<ElementP2<ark_bn254::g1::Config>> = generate_generators(b"SXT-TABLE-v1", 2);
+    let g: Vec<ark_bn254::G1Affine> = g.into_iter().map(Into::into).collect();
+    assert!(g.iter().all(|gi| gi.is_on_curve() && !gi.infinity));
+}
+
+#[test]
+fn we_can_compute_msms_with_generated_generators() {
+    let generators: Vec<RistrettoPoint> = generate_generators(b"SXT-TABLE-v1", 2);
+    let handle = MsmHandle::new(&generators);
+
+    let mut res = vec![RistrettoPoint::default(); 1];
+    handle.msm(&mut res, 1, &[1, 2]);
+    assert_eq!(res[0], generators[0] + generators[1] + generators[1]);
+}
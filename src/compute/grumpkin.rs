@@ -0,0 +1,30 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! commitments and MSMs on the grumpkin curve
+//!
+//! The functions of this module are the `*_grumpkin_*` functions of [crate::compute]
+//! without the curve in their names. The commitments are uncompressed affine points.
+
+pub use super::{
+    batch_scalar_mul_grumpkin as batch_scalar_mul,
+    compute_grumpkin_uncompressed_commitments_with_generators as compute_commitments_with_generators,
+    compute_grumpkin_uncompressed_commitments_with_generators_uninit as compute_commitments_with_generators_uninit,
+    compute_grumpkin_uncompressed_commitments_with_handle as compute_commitments_with_handle,
+    try_compute_grumpkin_uncompressed_commitments_with_generators as try_compute_commitments_with_generators,
+    Grumpkin as Scheme,
+};
+
+/// Handle to compute MSMs with pre-specified grumpkin generators
+pub type MsmHandle = super::MsmHandle<super::ElementP2<ark_grumpkin::GrumpkinConfig>>;
@@ -0,0 +1,173 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::error::HandleFileError;
+use std::{
+    ffi::OsString,
+    fs::File,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
+
+const MAGIC: [u8; 8] = *b"SXTMSMH\0";
+
+/// Version of the handle file layout written by `MsmHandle::write`
+pub(crate) const FORMAT_VERSION: u32 = 1;
+
+const HEADER_NUM_BYTES: usize = 24;
+
+/// Header stored in a sidecar file next to the backend's serialized handle.
+///
+/// Layout (little-endian):
+///
+/// ```text
+/// magic: [u8; 8] | format_version: u32 | curve_id: u32 | num_generators: u64
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct HandleFileHeader {
+    pub(crate) format_version: u32,
+    pub(crate) curve_id: u32,
+    pub(crate) num_generators: u64,
+}
+
+impl HandleFileHeader {
+    pub(crate) fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mut bytes = [0_u8; HEADER_NUM_BYTES];
+        bytes[..8].copy_from_slice(&MAGIC);
+        bytes[8..12].copy_from_slice(&self.format_version.to_le_bytes());
+        bytes[12..16].copy_from_slice(&self.curve_id.to_le_bytes());
+        bytes[16..24].copy_from_slice(&self.num_generators.to_le_bytes());
+        writer.write_all(&bytes)
+    }
+
+    pub(crate) fn read_from<R: Read>(reader: &mut R) -> Result<Self, HandleFileError> {
+        let mut bytes = [0_u8; HEADER_NUM_BYTES];
+        reader.read_exact(&mut bytes).map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => HandleFileError::InvalidHeader,
+            _ => HandleFileError::Io(e),
+        })?;
+        if bytes[..8] != MAGIC {
+            return Err(HandleFileError::InvalidHeader);
+        }
+        Ok(Self {
+            format_version: u32::from_le_bytes(bytes[8..12].try_into().unwrap()),
+            curve_id: u32::from_le_bytes(bytes[12..16].try_into().unwrap()),
+            num_generators: u64::from_le_bytes(bytes[16..24].try_into().unwrap()),
+        })
+    }
+
+    /// Check that a handle for `curve_id` can be loaded from a file with this header.
+    pub(crate) fn validate(&self, curve_id: u32) -> Result<(), HandleFileError> {
+        if self.format_version != FORMAT_VERSION {
+            return Err(HandleFileError::UnsupportedVersion(self.format_version));
+        }
+        if self.curve_id != curve_id {
+            return Err(HandleFileError::CurveMismatch {
+                expected: curve_id,
+                found: self.curve_id,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Path of the header file of the handle file `filename`, i.e. `filename` with a
+/// `.header` suffix.
+pub(crate) fn header_path(filename: &Path) -> PathBuf {
+    let mut path = OsString::from(filename.as_os_str());
+    path.push(".header");
+    PathBuf::from(path)
+}
+
+/// Write `header` to the header file of the handle file `filename`.
+pub(crate) fn write_header(filename: &Path, header: &HandleFileHeader) -> io::Result<()> {
+    let mut file = File::create(header_path(filename))?;
+    header.write_to(&mut file)?;
+    file.flush()
+}
+
+/// Read the header file of the handle file `filename` and validate it for `curve_id`.
+///
+/// The handle file itself must exist, and a missing header file is reported as an
+/// invalid header.
+pub(crate) fn read_header(
+    filename: &Path,
+    curve_id: u32,
+) -> Result<HandleFileHeader, HandleFileError> {
+    std::fs::metadata(filename)?;
+    let mut file = File::open(header_path(filename)).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => HandleFileError::InvalidHeader,
+        _ => HandleFileError::Io(e),
+    })?;
+    let header = HandleFileHeader::read_from(&mut file)?;
+    header.validate(curve_id)?;
+    Ok(header)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn we_can_write_and_read_back_a_header() {
+        let header = HandleFileHeader {
+            format_version: FORMAT_VERSION,
+            curve_id: 3,
+            num_generators: 1 << 40,
+        };
+        let mut bytes = Vec::new();
+        header.write_to(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), HEADER_NUM_BYTES);
+        let header_p = HandleFileHeader::read_from(&mut bytes.as_slice()).unwrap();
+        assert_eq!(header, header_p);
+        assert!(header_p.validate(3).is_ok());
+    }
+
+    #[test]
+    fn we_cannot_read_a_header_with_an_invalid_magic_or_length() {
+        let bytes = [0_u8; HEADER_NUM_BYTES];
+        assert!(matches!(
+            HandleFileHeader::read_from(&mut bytes.as_slice()),
+            Err(HandleFileError::InvalidHeader)
+        ));
+        assert!(matches!(
+            HandleFileHeader::read_from(&mut &MAGIC[..]),
+            Err(HandleFileError::InvalidHeader)
+        ));
+    }
+
+    #[test]
+    fn we_cannot_validate_a_header_with_a_different_version_or_curve() {
+        let header = HandleFileHeader {
+            format_version: FORMAT_VERSION + 1,
+            curve_id: 3,
+            num_generators: 10,
+        };
+        assert!(matches!(
+            header.validate(3),
+            Err(HandleFileError::UnsupportedVersion(v)) if v == FORMAT_VERSION + 1
+        ));
+        let header = HandleFileHeader {
+            format_version: FORMAT_VERSION,
+            curve_id: 3,
+            num_generators: 10,
+        };
+        assert!(matches!(
+            header.validate(2),
+            Err(HandleFileError::CurveMismatch {
+                expected: 2,
+                found: 3
+            })
+        ));
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::compute_curve25519_commitments;
+use crate::sequence::Sequence;
+use curve25519_dalek::{ristretto::CompressedRistretto, scalar::Scalar};
+use rayon::prelude::*;
+
+/// Computes `curve25519` commitments of signed 256-bit integers, such as the `decimal75`
+/// columns of Proof-of-SQL.
+///
+/// Each element of `data[j]` is a little-endian two's complement integer given as four
+/// `u64` limbs, i.e. the value is negative when the top bit of `x[3]` is set. The
+/// commitments are the same as those of the sequences of scalars holding each value
+/// (with negative values mapped to their additive inverse), using the generators of
+/// `compute_curve25519_commitments`.
+///
+/// The backend only accepts signed elements of up to 16 bytes, so each column is
+/// committed as unsigned values together with a column of its sign bits, and the two
+/// are combined as `c_values - 2^256 * c_signs`.
+///
+/// # Panics
+///
+/// If `commitments.len()` is different from `data.len()`.
+pub fn compute_curve25519_i256_commitments(
+    commitments: &mut [CompressedRistretto],
+    data: &[&[[u64; 4]]],
+    offset_generators: u64,
+) {
+    assert_eq!(
+        commitments.len(),
+        data.len(),
+        "commitments must hold one element for each sequence"
+    );
+    let signs: Vec<Vec<bool>> = data
+        .par_iter()
+        .map(|column| column.iter().map(|x| x[3] >> 63 == 1).collect())
+        .collect();
+    let sequences: Vec<Sequence> = data
+        .iter()
+        .map(|column| Sequence::from(*column))
+        .chain(signs.iter().map(Sequence::from))
+        .collect();
+    let mut raw = vec![CompressedRistretto::default(); sequences.len()];
+    compute_curve25519_commitments(&mut raw, &sequences, offset_generators);
+
+    let (values, signs) = raw.split_at(data.len());
+    let mut two_pow_256 = [0_u8; 64];
+    two_pow_256[32] = 1;
+    let two_pow_256 = Scalar::from_bytes_mod_order_wide(&two_pow_256);
+    commitments
+        .par_iter_mut()
+        .zip(values)
+        .zip(signs)
+        .for_each(|((c, values), signs)| {
+            let values = values.decompress().expect("backend returns valid points");
+            let signs = signs.decompress().expect("backend returns valid points");
+            *c = (values - two_pow_256 * signs).compress();
+        });
+}
@@ -0,0 +1,68 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+use crate::sequence::Sequence;
+use curve25519_dalek::{ristretto::CompressedRistretto, scalar::Scalar};
+
+/// Converts a two's complement 256-bit integer to a scalar.
+fn to_scalar(x: &[u64; 4]) -> Scalar {
+    let is_negative = x[3] >> 63 == 1;
+    // the magnitude of a negative value is its two's complement negation
+    let limbs = if is_negative {
+        let mut magnitude = x.map(|limb| !limb);
+        for limb in magnitude.iter_mut() {
+            let (sum, carry) = limb.overflowing_add(1);
+            *limb = sum;
+            if !carry {
+                break;
+            }
+        }
+        magnitude
+    } else {
+        *x
+    };
+    let bytes: Vec<u8> = limbs.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+    let magnitude = Scalar::from_bytes_mod_order(bytes.try_into().unwrap());
+    if is_negative {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+#[test]
+fn we_can_commit_to_signed_256_bit_integers() {
+    let column_a: Vec<[u64; 4]> = vec![
+        [5, 0, 0, 0],
+        [u64::MAX; 4],
+        [u64::MAX - 6, u64::MAX, u64::MAX, u64::MAX],
+        [1, 2, 3, u64::MAX >> 1],
+        [0, 0, 0, 1 << 63],
+    ];
+    let column_b: Vec<[u64; 4]> = vec![[u64::MAX; 4], [7, 0, 0, 0]];
+    let data: Vec<&[[u64; 4]]> = vec![&column_a, &column_b, &[]];
+
+    let mut commitments = vec![CompressedRistretto::default(); 3];
+    compute_curve25519_i256_commitments(&mut commitments, &data, 3);
+
+    let scalars: Vec<Vec<Scalar>> = data
+        .iter()
+        .map(|column| column.iter().map(to_scalar).collect())
+        .collect();
+    let sequences: Vec<Sequence> = scalars.iter().map(Sequence::from).collect();
+    let mut expected = vec![CompressedRistretto::default(); 3];
+    compute_curve25519_commitments(&mut expected, &sequences, 3);
+    assert_eq!(commitments, expected);
+    assert_eq!(to_scalar(&[u64::MAX; 4]), -Scalar::ONE);
+}
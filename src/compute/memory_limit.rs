@@ -0,0 +1,209 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Splitting of the commitment computations that exceed
+//! [BackendConfig::max_device_memory_bytes](super::BackendConfig::max_device_memory_bytes)
+//! or the number of rows the backend accepts in one call, and checking of the
+//! computations that cannot be split.
+
+use super::error::BackendError;
+use crate::sequence::Sequence;
+
+/// Largest number of rows committed to by one backend call.
+///
+/// The backend takes 32-bit lengths, so longer sequences are split into chunks of rows
+/// committed to with offset generators. It is a multiple of `8`, like the chunks of
+/// [rows_per_chunk].
+pub(crate) const MAX_ROWS_PER_CALL: usize = u32::MAX as usize / 8 * 8;
+
+/// Returns the number of bytes transferred to the device to commit to the rows
+/// `0..num_rows` of `data`, with generators of `generator_size` bytes and outputs of
+/// `output_size` bytes.
+fn device_bytes(
+    data: &[Sequence],
+    num_rows: usize,
+    generator_size: usize,
+    output_size: usize,
+) -> u64 {
+    let data_bytes: usize = data
+        .iter()
+        .map(|s| s.len().min(num_rows) * s.element_size())
+        .sum();
+    let max_len = data.iter().map(Sequence::len).max().unwrap_or(0);
+    (data_bytes + max_len.min(num_rows) * generator_size + data.len() * output_size) as u64
+}
+
+/// Returns the number of rows of the chunks of `data` that fit in `limit` bytes and in
+/// one backend call, or `None` if `data` fits as a whole.
+///
+/// The number of rows is a multiple of `8`, so that the chunks of the validity bitmaps
+/// stay byte-aligned.
+pub(crate) fn rows_per_chunk(
+    data: &[Sequence],
+    generator_size: usize,
+    output_size: usize,
+    limit: Option<u64>,
+) -> Result<Option<usize>, BackendError> {
+    rows_per_chunk_with_max(data, generator_size, output_size, limit, MAX_ROWS_PER_CALL)
+}
+
+fn rows_per_chunk_with_max(
+    data: &[Sequence],
+    generator_size: usize,
+    output_size: usize,
+    limit: Option<u64>,
+    max_rows: usize,
+) -> Result<Option<usize>, BackendError> {
+    let max_len = data.iter().map(Sequence::len).max().unwrap_or(0);
+    let num_rows = match limit {
+        Some(limit) if device_bytes(data, max_len, generator_size, output_size) > limit => {
+            rows_within_limit(data, generator_size, output_size, limit)?
+        }
+        _ if max_len > max_rows => return Ok(Some(max_rows)),
+        _ => return Ok(None),
+    };
+    Ok(Some(num_rows.min(max_rows)))
+}
+
+/// Returns an error if a computation transferring `num_bytes` bytes to the device does
+/// not fit in `limit`.
+pub(crate) fn check_device_bytes(num_bytes: u64, limit: Option<u64>) -> Result<(), BackendError> {
+    match limit {
+        Some(limit) if num_bytes > limit => Err(BackendError::MemoryLimitExceeded {
+            required: num_bytes,
+            limit,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the number of rows of the chunks of `data` that fit in `limit` bytes.
+fn rows_within_limit(
+    data: &[Sequence],
+    generator_size: usize,
+    output_size: usize,
+    limit: u64,
+) -> Result<usize, BackendError> {
+    let row_bytes =
+        (data.iter().map(Sequence::element_size).sum::<usize>() + generator_size) as u64;
+    let output_bytes = (data.len() * output_size) as u64;
+    let num_rows = limit.saturating_sub(output_bytes) / row_bytes / 8 * 8;
+    if num_rows == 0 {
+        return Err(BackendError::MemoryLimitExceeded {
+            required: device_bytes(data, 8, generator_size, output_size),
+            limit,
+        });
+    }
+    Ok(num_rows as usize)
+}
+
+/// Returns the chunks of `num_rows` rows of `data`, together with the index of their
+/// first row.
+pub(crate) fn row_chunks<'a, 'b>(
+    data: &'b [Sequence<'a>],
+    num_rows: usize,
+) -> impl Iterator<Item = (usize, Vec<Sequence<'a>>)> + 'b {
+    let max_len = data.iter().map(Sequence::len).max().unwrap_or(0);
+    (0..max_len).step_by(num_rows).map(move |start| {
+        let chunk = data
+            .iter()
+            .map(|s| s.rows(start, start + num_rows))
+            .collect();
+        (start, chunk)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn we_can_split_sequences_into_chunks_that_fit_the_limit() {
+        let a: Vec<u64> = (0..100).collect();
+        let b: Vec<u32> = (0..40).collect();
+        let data = [Sequence::from(&a), Sequence::from(&b)];
+
+        assert_eq!(rows_per_chunk(&data, 32, 32, None), Ok(None));
+        assert_eq!(rows_per_chunk(&data, 32, 32, Some(1 << 20)), Ok(None));
+
+        // each row takes 8 + 4 + 32 bytes and the outputs 64 bytes
+        let num_rows = rows_per_chunk(&data, 32, 32, Some(64 + 44 * 20))
+            .unwrap()
+            .unwrap();
+        assert_eq!(num_rows, 16);
+
+        let chunks: Vec<_> = row_chunks(&data, num_rows).collect();
+        assert_eq!(chunks.len(), 7);
+        assert_eq!(chunks[2].0, 32);
+        assert_eq!(chunks[2].1[0].len(), 16);
+        assert_eq!(chunks[2].1[1].len(), 8);
+        assert_eq!(chunks[6].1[0].len(), 4);
+        assert!(chunks[6].1[1].is_empty());
+    }
+
+    #[test]
+    fn we_can_split_sequences_longer_than_a_backend_call() {
+        let a: Vec<u64> = (0..100).collect();
+        let b: Vec<u32> = (0..40).collect();
+        let data = [Sequence::from(&a), Sequence::from(&b)];
+
+        assert_eq!(rows_per_chunk_with_max(&data, 32, 32, None, 100), Ok(None));
+        assert_eq!(
+            rows_per_chunk_with_max(&data, 32, 32, None, 64),
+            Ok(Some(64))
+        );
+        assert_eq!(
+            rows_per_chunk_with_max(&data, 32, 32, Some(1 << 20), 64),
+            Ok(Some(64))
+        );
+        // the memory limit allows 16 rows per chunk
+        assert_eq!(
+            rows_per_chunk_with_max(&data, 32, 32, Some(64 + 44 * 20), 64),
+            Ok(Some(16))
+        );
+        assert_eq!(
+            rows_per_chunk_with_max(&data, 32, 32, Some(64 + 44 * 20), 8),
+            Ok(Some(8))
+        );
+
+        assert_eq!(MAX_ROWS_PER_CALL % 8, 0);
+        assert!(MAX_ROWS_PER_CALL <= u32::MAX as usize);
+    }
+
+    #[test]
+    fn we_can_check_computations_that_cannot_be_split() {
+        assert_eq!(check_device_bytes(100, None), Ok(()));
+        assert_eq!(check_device_bytes(100, Some(100)), Ok(()));
+        assert_eq!(
+            check_device_bytes(101, Some(100)),
+            Err(BackendError::MemoryLimitExceeded {
+                required: 101,
+                limit: 100
+            })
+        );
+    }
+
+    #[test]
+    fn we_cannot_split_sequences_below_the_size_of_a_chunk() {
+        let a: Vec<u64> = (0..100).collect();
+        let data = [Sequence::from(&a)];
+        assert_eq!(
+            rows_per_chunk(&data, 32, 32, Some(100)),
+            Err(BackendError::MemoryLimitExceeded {
+                required: 32 + 8 * 40,
+                limit: 100
+            })
+        );
+    }
+}
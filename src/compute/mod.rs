@@ -13,36 +13,215 @@
 // limitations under the License.
 
 //! commitment and generator computation
+//!
+//! Empty inputs are handled without calling the backend: a sequence with no elements
+//! commits to the identity, an MSM with no scalars returns the identity for every
+//! output, and a call with no sequences or no outputs leaves its output untouched. An
+//! MSM with non-empty scalars of zero bytes or bits panics with a message saying so.
+//!
+//! # Input lengths
+//!
+//! The backend takes 32-bit counts of sequences, outputs, generators and scalars per
+//! output. The `compute_curve25519_commitments` and `compute_*_commitments_with_generators`
+//! functions split sequences longer than `u32::MAX` rows into several backend calls, and
+//! only their `try_` variants and [MsmHandle::try_msm] report a count that is too large
+//! as an error. The other functions, the [MsmHandle] methods and handle creation panic
+//! instead, rather than truncating the count.
+//!
+//! # Thread safety
+//!
+//! The backend is initialized at most once, so every function of this module can be
+//! called concurrently from several threads. All handle types ([MsmHandle],
+//! [DoryCommitmentEngine], [CommitmentCache] and [ScalarMatrix]) are `Send` and `Sync`:
+//! the generators held by a handle are never mutated after creation, so a single handle
+//! can serve concurrent MSMs, while a `CommitmentCache` needs `&mut self` to be updated
+//! and is shared behind a lock.
+//!
+//! # Per-curve modules
+//!
+//! The functions of each curve are also exported without the curve in their names by
+//! [curve25519], [bls12_381], [bn254] and [grumpkin], e.g.
+//! `compute::curve25519::compute_commitments` for [compute_curve25519_commitments], and
+//! [prelude] exports the traits shared by all curves.
+//!
+//! The per-curve modules are the preferred paths for new code. The flat names of this
+//! module remain supported, and both paths refer to the same functions.
+
+#[cfg(feature = "bls12_381")]
+pub mod bls12_381;
+#[cfg(feature = "bn254")]
+pub mod bn254;
+pub mod curve25519;
+#[cfg(feature = "grumpkin")]
+pub mod grumpkin;
+pub mod prelude;
 
 mod backend;
-pub use backend::{init_backend, init_backend_with_config, BackendConfig};
+pub use backend::{
+    backend_info, ensure_initialized, init_backend, init_backend_from_env,
+    init_backend_with_config, BackendConfig, BackendInfo, BackendKind, MAX_DEVICE_MEMORY_BYTES_ENV,
+    NUM_PRECOMPUTED_GENERATORS_ENV,
+};
 
 mod curve;
-use curve::CurveId;
+pub use curve::{CurveId, SwCurveConfig};
+
+mod batch_compression;
+#[cfg(feature = "bls12_381")]
+pub use batch_compression::{batch_compress_bls12_381_g1, batch_decompress_bls12_381_g1};
+pub use batch_compression::{batch_compress_ristretto, batch_decompress_ristretto};
+#[cfg(test)]
+mod batch_compression_tests;
+
+mod chunked;
+pub use chunked::{
+    compute_curve25519_commitment_chunked, compute_curve25519_commitment_chunked_cancellable,
+    compute_curve25519_commitment_chunked_with_progress, compute_curve25519_commitment_from_file,
+    CancellationToken, ChunkProgress,
+};
+
+#[cfg(test)]
+mod chunked_tests;
+
+#[cfg(feature = "bls12_381")]
+mod compressed_bls12_381;
+#[cfg(feature = "bls12_381")]
+pub use compressed_bls12_381::{
+    compute_bls12_381_g1_compressed_commitments_with_generators, CompressedG1Bls12_381,
+};
+
+mod commitment_cache;
+pub use commitment_cache::CommitmentCache;
+#[cfg(test)]
+mod commitment_cache_tests;
+
+mod commitment_scheme;
+#[cfg(feature = "bls12_381")]
+pub use commitment_scheme::Bls12381G1;
+#[cfg(feature = "bn254")]
+pub use commitment_scheme::Bn254G1;
+#[cfg(feature = "grumpkin")]
+pub use commitment_scheme::Grumpkin;
+pub use commitment_scheme::{compute_commitments_with_generators, CommitmentScheme, Curve25519};
 
 mod commitments;
 pub use commitments::{
-    compute_bls12_381_g1_commitments_with_generators,
-    compute_bn254_g1_uncompressed_commitments_with_generators, compute_curve25519_commitments,
+    check_inputs, compute_curve25519_commitments, compute_curve25519_commitments_from_iter,
+    compute_curve25519_commitments_unchecked, compute_curve25519_commitments_uninit,
     compute_curve25519_commitments_with_generators,
-    compute_grumpkin_uncompressed_commitments_with_generators, update_curve25519_commitments,
+    compute_curve25519_commitments_with_generators_unchecked,
+    compute_curve25519_commitments_with_generators_uninit,
+    compute_curve25519_commitments_with_handle, rerandomize_curve25519_commitments,
+    try_compute_curve25519_commitments, try_compute_curve25519_commitments_with_generators,
+    update_curve25519_commitments,
+};
+#[cfg(feature = "bls12_381")]
+pub use commitments::{
+    compute_bls12_381_g1_commitments_with_generators,
+    compute_bls12_381_g1_commitments_with_generators_uninit,
+    compute_bls12_381_g1_commitments_with_handle,
+    try_compute_bls12_381_g1_commitments_with_generators,
+};
+#[cfg(feature = "bn254")]
+pub use commitments::{
+    compute_bn254_g1_uncompressed_commitments_with_generators,
+    compute_bn254_g1_uncompressed_commitments_with_generators_uninit,
+    compute_bn254_g1_uncompressed_commitments_with_handle,
+    rerandomize_bn254_g1_uncompressed_commitments,
+    try_compute_bn254_g1_uncompressed_commitments_with_generators,
+};
+#[cfg(feature = "grumpkin")]
+pub use commitments::{
+    compute_grumpkin_uncompressed_commitments_with_generators,
+    compute_grumpkin_uncompressed_commitments_with_generators_uninit,
+    compute_grumpkin_uncompressed_commitments_with_handle,
+    try_compute_grumpkin_uncompressed_commitments_with_generators,
 };
 
 #[cfg(test)]
 mod commitments_tests;
 
+mod delta_update;
+pub use delta_update::{delta_update_curve25519_commitments, RowUpdate};
+#[cfg(test)]
+mod delta_update_tests;
+
+#[cfg(feature = "bls12_381")]
+mod dory;
+#[cfg(feature = "bls12_381")]
+pub use dory::DoryCommitmentEngine;
+#[cfg(all(test, feature = "bls12_381"))]
+mod dory_tests;
+
+mod edwards;
+pub use edwards::{compressed_ristretto_to_edwards, ristretto_to_edwards};
+#[cfg(test)]
+mod edwards_tests;
+
 mod element_p2;
 pub use element_p2::ElementP2;
 #[cfg(test)]
 mod element_p2_test;
 
+mod i256;
+pub use i256::compute_curve25519_i256_commitments;
+#[cfg(test)]
+mod i256_tests;
+
+mod error;
+pub use error::{BackendError, HandleFileError, MsmError};
+
 mod fixed_msm;
-pub use fixed_msm::{MsmHandle, SwMsmHandle};
+pub use fixed_msm::{MsmHandle, RistrettoMsmHandle, SwMsmHandle};
 #[cfg(test)]
 mod fixed_msm_tests;
 
+mod one_commit_table;
+pub use one_commit_table::OneCommitTable;
+#[cfg(test)]
+mod one_commit_table_tests;
+
+mod scalar;
+pub use scalar::{ScalarSerialize, ScalarWidth};
+
+mod scalar_mul;
+#[cfg(feature = "bls12_381")]
+pub use scalar_mul::batch_scalar_mul_bls12_381_g1;
+#[cfg(feature = "bn254")]
+pub use scalar_mul::batch_scalar_mul_bn254_g1;
+pub use scalar_mul::batch_scalar_mul_curve25519;
+#[cfg(feature = "grumpkin")]
+pub use scalar_mul::batch_scalar_mul_grumpkin;
+#[cfg(test)]
+mod scalar_mul_tests;
+
+#[cfg(test)]
+mod thread_safety_tests;
+
+mod selection;
+pub use selection::compute_curve25519_commitments_with_selection;
+#[cfg(test)]
+mod selection_tests;
+
+mod scalar_matrix;
+pub use scalar_matrix::ScalarMatrix;
+
+mod handle_file;
+
+mod memory_limit;
+
+mod msm_sum;
+
+mod packing;
+pub use packing::{pack_scalars, unpack_scalars};
+
+mod validity;
+
 mod generators;
-pub use generators::{get_curve25519_generators, get_one_curve25519_commit};
+pub use generators::{
+    generate_generators, get_curve25519_generators, get_curve25519_range_commit,
+    get_one_curve25519_commit, get_range_commit_with_generators, DeriveGenerator,
+};
 
 #[cfg(test)]
 mod generators_tests;
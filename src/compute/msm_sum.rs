@@ -0,0 +1,90 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use rayon::prelude::*;
+
+/// Maximum number of bits of a scalar of a `packed_msm` output.
+pub(crate) const MAX_OUTPUT_BITS: usize = 256;
+
+/// Scalars laid out for `MsmHandle::packed_msm`, with outputs holding the sums of the
+/// scalars of each generator.
+///
+/// The sum of the `m` scalars of a generator is computed as an integer, so it needs up
+/// to `ceil(log2(m))` bits more than the scalars. It is split into a low output of at
+/// most [MAX_OUTPUT_BITS] bits and, when needed, a high output with the remaining bits,
+/// so that the sum of the MSM outputs is
+///
+/// ```text
+/// sum = low + 2^MAX_OUTPUT_BITS * high
+/// ```
+pub(crate) struct PackedRowSums {
+    pub(crate) output_bit_table: Vec<u32>,
+    pub(crate) scalars: Vec<u8>,
+}
+
+/// Packs the row sums of the `msm` layout `scalars` of `num_outputs` outputs, preceded
+/// by copies of the outputs themselves if `keep_outputs` is true.
+///
+/// # Panics
+///
+/// If `scalars.len()` is not a multiple of `num_outputs * element_num_bytes`.
+pub(crate) fn pack_row_sums(
+    num_outputs: usize,
+    element_num_bytes: usize,
+    scalars: &[u8],
+    keep_outputs: bool,
+) -> PackedRowSums {
+    let row_num_bytes = num_outputs * element_num_bytes;
+    assert!(
+        row_num_bytes > 0 && scalars.len() % row_num_bytes == 0,
+        "scalars must hold num_outputs scalars for each generator"
+    );
+    let n = scalars.len() / row_num_bytes;
+
+    let num_carry_bits = (usize::BITS - (num_outputs - 1).leading_zeros()) as usize;
+    let sum_num_bytes = element_num_bytes + num_carry_bits.div_ceil(8);
+    let mut output_bit_table = if keep_outputs {
+        vec![8 * element_num_bytes as u32; num_outputs]
+    } else {
+        Vec::new()
+    };
+    let num_kept_bytes = output_bit_table.len() * element_num_bytes;
+    let low_num_bytes = sum_num_bytes.min(MAX_OUTPUT_BITS / 8);
+    output_bit_table.push(8 * low_num_bytes as u32);
+    if sum_num_bytes > low_num_bytes {
+        output_bit_table.push(8 * (sum_num_bytes - low_num_bytes) as u32);
+    }
+
+    let packed_num_bytes = num_kept_bytes + sum_num_bytes;
+    let mut packed = vec![0_u8; n * packed_num_bytes];
+    packed
+        .par_chunks_exact_mut(packed_num_bytes)
+        .zip(scalars.par_chunks_exact(row_num_bytes))
+        .for_each(|(packed_row, row)| {
+            packed_row[..num_kept_bytes].copy_from_slice(&row[..num_kept_bytes]);
+            let sum = &mut packed_row[num_kept_bytes..];
+            for scalar in row.chunks_exact(element_num_bytes) {
+                let mut carry = 0_u16;
+                for (k, byte) in sum.iter_mut().enumerate() {
+                    let x = *byte as u16 + scalar.get(k).map_or(0, |b| *b as u16) + carry;
+                    *byte = x as u8;
+                    carry = x >> 8;
+                }
+            }
+        });
+
+    PackedRowSums {
+        output_bit_table,
+        scalars: packed,
+    }
+}
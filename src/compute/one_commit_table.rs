@@ -0,0 +1,111 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::{get_curve25519_generators, SwCurveConfig};
+use ark_ec::{
+    short_weierstrass::{Affine, Projective},
+    AffineRepr, CurveGroup,
+};
+use curve25519_dalek::ristretto::RistrettoPoint;
+
+/// Table of the one-commits `g_0 + g_1 + ... + g_(n-1)` for every `n` up to `max_n`.
+///
+/// A one-commit is the commitment to a column of `n` ones, so the table answers the
+/// prefix sums of the generators a verifier needs without calling the backend for each
+/// of them. Entry `0` is the identity.
+///
+/// The table is computed once and never mutated, so it is `Send` and `Sync` and can be
+/// shared across threads, e.g. behind an `Arc`.
+///
+/// # Example
+///
+/// ```no_run
+/// use blitzar::compute::{get_one_curve25519_commit, OneCommitTable};
+///
+/// let table = OneCommitTable::new(1024);
+/// assert_eq!(table.get(10), Some(get_one_curve25519_commit(10)));
+/// assert_eq!(table.get(1025), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct OneCommitTable<G> {
+    sums: Vec<G>,
+}
+
+mod sealed {
+    use super::*;
+
+    /// Generators whose prefix sums can be stored in a [super::OneCommitTable].
+    pub trait PrefixSum: Copy + Sized {
+        /// Returns `[0, g_0, g_0 + g_1, ..., g_0 + ... + g_(n-1)]`.
+        fn prefix_sums(generators: &[Self]) -> Vec<Self>;
+    }
+
+    impl PrefixSum for RistrettoPoint {
+        fn prefix_sums(generators: &[Self]) -> Vec<Self> {
+            let mut sums = Vec::with_capacity(generators.len() + 1);
+            sums.push(RistrettoPoint::default());
+            for g in generators {
+                sums.push(sums[sums.len() - 1] + g);
+            }
+            sums
+        }
+    }
+
+    /// The sums are accumulated in projective coordinates and normalized together, with
+    /// a single field inversion.
+    impl<C: SwCurveConfig> PrefixSum for Affine<C> {
+        fn prefix_sums(generators: &[Self]) -> Vec<Self> {
+            let mut sums = Vec::with_capacity(generators.len() + 1);
+            sums.push(Projective::<C>::default());
+            for g in generators {
+                sums.push(sums[sums.len() - 1] + g.into_group());
+            }
+            Projective::normalize_batch(&sums)
+        }
+    }
+}
+
+impl<G: sealed::PrefixSum> OneCommitTable<G> {
+    /// Computes the table of the prefix sums of `generators`, up to `generators.len()`.
+    ///
+    /// For short Weierstrass curves, these are the one-commits of the
+    /// `compute_*_commitments_with_generators` functions called with `generators`.
+    pub fn from_generators(generators: &[G]) -> Self {
+        Self {
+            sums: G::prefix_sums(generators),
+        }
+    }
+
+    /// Returns the one-commit of `n`, or `None` if `n` is larger than [Self::max_n].
+    pub fn get(&self, n: usize) -> Option<G> {
+        self.sums.get(n).copied()
+    }
+
+    /// Returns the largest `n` in the table.
+    pub fn max_n(&self) -> usize {
+        self.sums.len() - 1
+    }
+}
+
+impl OneCommitTable<RistrettoPoint> {
+    /// Computes the table for the generators used by
+    /// [super::compute_curve25519_commitments] with an offset of `0`.
+    ///
+    /// The generators are fetched from the backend in one call and summed on the host,
+    /// so that `get(n)` is equal to [super::get_one_curve25519_commit] of `n`.
+    pub fn new(max_n: usize) -> Self {
+        let mut generators = vec![RistrettoPoint::default(); max_n];
+        get_curve25519_generators(&mut generators, 0);
+        Self::from_generators(&generators)
+    }
+}
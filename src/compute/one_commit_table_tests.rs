@@ -0,0 +1,104 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+#[cfg(feature = "bn254")]
+use crate::compute::compute_bn254_g1_uncompressed_commitments_with_generators;
+#[cfg(feature = "grumpkin")]
+use crate::compute::compute_grumpkin_uncompressed_commitments_with_generators;
+use crate::compute::get_one_curve25519_commit;
+#[cfg(any(feature = "bn254", feature = "grumpkin"))]
+use crate::sequence::Sequence;
+#[cfg(any(feature = "bn254", feature = "grumpkin"))]
+use ark_std::UniformRand;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use std::{sync::Arc, thread};
+
+#[test]
+fn we_can_get_curve25519_one_commits_from_a_table() {
+    let table = OneCommitTable::new(20);
+    assert_eq!(table.max_n(), 20);
+    assert_eq!(table.get(0), Some(RistrettoPoint::default()));
+    for n in [1, 2, 7, 20] {
+        assert_eq!(table.get(n), Some(get_one_curve25519_commit(n as u64)));
+    }
+    assert_eq!(table.get(21), None);
+}
+
+#[test]
+fn we_can_share_a_table_across_threads() {
+    let table = Arc::new(OneCommitTable::new(8));
+    let handles: Vec<_> = (0..4)
+        .map(|n| {
+            let table = Arc::clone(&table);
+            thread::spawn(move || table.get(2 * n).unwrap())
+        })
+        .collect();
+    for (n, handle) in handles.into_iter().enumerate() {
+        assert_eq!(
+            handle.join().unwrap(),
+            get_one_curve25519_commit(2 * n as u64)
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "bn254")]
+fn we_can_get_bn254_one_commits_from_a_table() {
+    let mut rng = ark_std::test_rng();
+    let generators: Vec<ark_bn254::G1Affine> = (0..5)
+        .map(|_| ark_bn254::G1Affine::rand(&mut rng))
+        .collect();
+    let table = OneCommitTable::from_generators(&generators);
+    assert_eq!(table.max_n(), 5);
+    assert_eq!(table.get(0), Some(ark_bn254::G1Affine::default()));
+
+    for n in 1..=5 {
+        let ones: Vec<u8> = vec![1; n];
+        let mut expected = [ark_bn254::G1Affine::default()];
+        compute_bn254_g1_uncompressed_commitments_with_generators(
+            &mut expected,
+            &[Sequence::from(&ones)],
+            &generators,
+        );
+        assert_eq!(table.get(n), Some(expected[0]));
+    }
+    assert_eq!(table.get(6), None);
+}
+
+#[test]
+#[cfg(feature = "grumpkin")]
+fn we_can_get_grumpkin_one_commits_from_a_table() {
+    let mut rng = ark_std::test_rng();
+    let generators: Vec<ark_grumpkin::Affine> = (0..3)
+        .map(|_| ark_grumpkin::Affine::rand(&mut rng))
+        .collect();
+    let table = OneCommitTable::from_generators(&generators);
+
+    let ones: Vec<u8> = vec![1; 3];
+    let mut expected = [ark_grumpkin::Affine::default()];
+    compute_grumpkin_uncompressed_commitments_with_generators(
+        &mut expected,
+        &[Sequence::from(&ones)],
+        &generators,
+    );
+    assert_eq!(table.get(3), Some(expected[0]));
+}
+
+#[test]
+fn we_can_build_an_empty_table() {
+    let table = OneCommitTable::<RistrettoPoint>::from_generators(&[]);
+    assert_eq!(table.max_n(), 0);
+    assert_eq!(table.get(0), Some(RistrettoPoint::default()));
+    assert_eq!(table.get(1), None);
+}
@@ -0,0 +1,378 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::sequence::Sequence;
+use ark_ff::{BigInteger, PrimeField};
+use rayon::prelude::*;
+
+/// Scalars of a set of sequences laid out for `MsmHandle::packed_msm`.
+///
+/// Output `j < data.len()` holds the raw bytes of `data[j]`, read as an unsigned
+/// integer of `8 * element_size` bits and zero-padded to the length of the longest
+/// sequence. Every signed sequence gets an additional 1-bit output holding the sign
+/// bits of its elements, so that its values can be recovered as
+///
+/// ```text
+/// value = raw - 2^(8 * element_size) * sign
+/// ```
+pub(crate) struct PackedSequences {
+    pub(crate) output_bit_table: Vec<u32>,
+    pub(crate) scalars: Vec<u8>,
+    /// For each sequence, the index of the output holding its sign bits.
+    pub(crate) sign_outputs: Vec<Option<usize>>,
+}
+
+pub(crate) fn pack_sequences(data: &[Sequence]) -> PackedSequences {
+    let num_sequences = data.len();
+    let n = data.iter().map(Sequence::len).max().unwrap_or(0);
+
+    let mut output_bit_table: Vec<u32> = data.iter().map(|s| 8 * s.element_size() as u32).collect();
+    let mut sign_outputs = vec![None; num_sequences];
+    for (j, s) in data.iter().enumerate() {
+        if s.is_signed() {
+            sign_outputs[j] = Some(output_bit_table.len());
+            output_bit_table.push(1);
+        }
+    }
+
+    let byte_offsets: Vec<usize> = data
+        .iter()
+        .scan(0, |offset, s| {
+            let res = *offset;
+            *offset += s.element_size();
+            Some(res)
+        })
+        .collect();
+    let num_value_bytes: usize = data.iter().map(Sequence::element_size).sum();
+    let num_signed = output_bit_table.len() - num_sequences;
+    let num_bytes = num_value_bytes + num_signed.div_ceil(8);
+
+    let mut scalars = vec![0_u8; n * num_bytes];
+    if num_bytes > 0 {
+        scalars
+            .par_chunks_exact_mut(num_bytes)
+            .enumerate()
+            .for_each(|(i, row)| {
+                for (j, s) in data.iter().enumerate() {
+                    if i >= s.len() || !s.is_valid(i) {
+                        continue;
+                    }
+                    let element_size = s.element_size();
+                    let element = &s.data_slice()[i * element_size..(i + 1) * element_size];
+                    row[byte_offsets[j]..byte_offsets[j] + element_size].copy_from_slice(element);
+                    if let Some(output) = sign_outputs[j] {
+                        let bit = output - num_sequences;
+                        row[num_value_bytes + bit / 8] |=
+                            (element[element_size - 1] >> 7) << (bit % 8);
+                    }
+                }
+            });
+    }
+
+    PackedSequences {
+        output_bit_table,
+        scalars,
+        sign_outputs,
+    }
+}
+
+/// Adds a 1-bit output holding the top bit of each signed output of the `packed_msm`
+/// layout `scalars`, so that the values of signed outputs can be recovered as for
+/// [PackedSequences].
+///
+/// # Panics
+///
+/// If `output_signed.len()` is different from `output_bit_table.len()`, if a signed
+/// output has no bits, or if `scalars.len()` is not a multiple of the packed row size.
+pub(crate) fn append_sign_outputs(
+    output_bit_table: &[u32],
+    output_signed: &[bool],
+    scalars: &[u8],
+) -> PackedSequences {
+    assert_eq!(
+        output_signed.len(),
+        output_bit_table.len(),
+        "output_signed must hold one entry for each output"
+    );
+    let mut new_bit_table = output_bit_table.to_vec();
+    let mut sign_outputs = vec![None; output_bit_table.len()];
+    let mut sign_bit_offsets = Vec::new();
+    let mut bit_offset = 0;
+    for (j, (bits, is_signed)) in output_bit_table.iter().zip(output_signed).enumerate() {
+        bit_offset += *bits as usize;
+        if *is_signed {
+            assert!(*bits > 0, "signed outputs must have at least one bit");
+            sign_outputs[j] = Some(new_bit_table.len());
+            new_bit_table.push(1);
+            sign_bit_offsets.push(bit_offset - 1);
+        }
+    }
+
+    let num_bytes = packed_row_num_bytes(output_bit_table);
+    let new_num_bytes = packed_row_num_bytes(&new_bit_table);
+    let n = if num_bytes == 0 {
+        0
+    } else {
+        assert!(
+            scalars.len() % num_bytes == 0,
+            "scalars must hold a whole number of packed rows"
+        );
+        scalars.len() / num_bytes
+    };
+    let mut new_scalars = vec![0_u8; n * new_num_bytes];
+    if n > 0 {
+        new_scalars
+            .par_chunks_exact_mut(new_num_bytes)
+            .zip(scalars.par_chunks_exact(num_bytes))
+            .for_each(|(new_row, row)| {
+                // padding bits of `row` are not copied, as sign bits follow the values
+                write_bits(new_row, 0, row, bit_offset);
+                for (k, sign_bit_offset) in sign_bit_offsets.iter().enumerate() {
+                    let mut sign = [0_u8];
+                    read_bits(row, *sign_bit_offset, &mut sign, 1);
+                    write_bits(new_row, bit_offset + k, &sign, 1);
+                }
+            });
+    }
+
+    PackedSequences {
+        output_bit_table: new_bit_table,
+        scalars: new_scalars,
+        sign_outputs,
+    }
+}
+
+/// Writes the lowest `num_bits` bits of the little-endian `src` to `row`, starting at
+/// bit `bit_offset`. The destination bits must be zero.
+fn write_bits(row: &mut [u8], bit_offset: usize, src: &[u8], num_bits: usize) {
+    let shift = bit_offset % 8;
+    let start = bit_offset / 8;
+    for (k, byte) in src.iter().take(num_bits.div_ceil(8)).enumerate() {
+        let remaining = num_bits - 8 * k;
+        let byte = if remaining < 8 {
+            byte & ((1_u8 << remaining) - 1)
+        } else {
+            *byte
+        };
+        row[start + k] |= byte << shift;
+        if shift > 0 && byte >> (8 - shift) != 0 {
+            row[start + k + 1] |= byte >> (8 - shift);
+        }
+    }
+}
+
+/// Reads `num_bits` bits of `row`, starting at bit `bit_offset`, into the little-endian
+/// `dst`.
+fn read_bits(row: &[u8], bit_offset: usize, dst: &mut [u8], num_bits: usize) {
+    let shift = bit_offset % 8;
+    let start = bit_offset / 8;
+    for (k, out) in dst.iter_mut().take(num_bits.div_ceil(8)).enumerate() {
+        let mut byte = row[start + k] >> shift;
+        if shift > 0 {
+            byte |= row.get(start + k + 1).map_or(0, |next| next << (8 - shift));
+        }
+        let remaining = num_bits - 8 * k;
+        if remaining < 8 {
+            byte &= (1_u8 << remaining) - 1;
+        }
+        *out = byte;
+    }
+}
+
+fn packed_row_num_bytes(output_bit_table: &[u32]) -> usize {
+    let bit_sum: usize = output_bit_table.iter().map(|bits| *bits as usize).sum();
+    bit_sum.div_ceil(8)
+}
+
+fn num_scalar_bytes<F: PrimeField>() -> usize {
+    F::BigInt::NUM_LIMBS * 8
+}
+
+/// Packs `columns` of field elements into the scalars layout of
+/// [crate::compute::MsmHandle::packed_msm].
+///
+/// Column `j` holds the scalars of output `j`, of which only the lowest
+/// `output_bit_table[j]` bits are kept. Row `i` of the result holds the `i`-th scalar
+/// of every column, packed contiguously starting at the least significant bit and
+/// padded with zeros to a whole number of bytes. Columns shorter than the longest one
+/// are padded with zeros.
+///
+/// # Panics
+///
+/// If `columns.len()` is different from `output_bit_table.len()` or if an entry of
+/// `output_bit_table` exceeds the size of the field's canonical encoding.
+pub fn pack_scalars<F: PrimeField>(columns: &[&[F]], output_bit_table: &[u32]) -> Vec<u8> {
+    assert_eq!(
+        columns.len(),
+        output_bit_table.len(),
+        "output_bit_table must hold one entry for each column"
+    );
+    let scalar_num_bytes = num_scalar_bytes::<F>();
+    assert!(
+        output_bit_table
+            .iter()
+            .all(|bits| *bits as usize <= 8 * scalar_num_bytes),
+        "output bit widths cannot exceed the size of the field elements"
+    );
+
+    let n = columns.iter().map(|column| column.len()).max().unwrap_or(0);
+    let num_bytes = packed_row_num_bytes(output_bit_table);
+    let mut scalars = vec![0_u8; n * num_bytes];
+    if num_bytes == 0 {
+        return scalars;
+    }
+    scalars
+        .par_chunks_exact_mut(num_bytes)
+        .enumerate()
+        .for_each(|(i, row)| {
+            let mut bit_offset = 0;
+            for (column, bits) in columns.iter().zip(output_bit_table) {
+                let bits = *bits as usize;
+                if let Some(scalar) = column.get(i) {
+                    let bytes = scalar.into_bigint().to_bytes_le();
+                    write_bits(row, bit_offset, &bytes, bits);
+                }
+                bit_offset += bits;
+            }
+        });
+    scalars
+}
+
+/// Unpacks the scalars layout of [crate::compute::MsmHandle::packed_msm] into columns
+/// of field elements. This is the inverse of [pack_scalars], up to the bits discarded
+/// by `output_bit_table` and the zero padding of shorter columns.
+///
+/// # Panics
+///
+/// If `scalars.len()` is not a multiple of the packed row size or if an entry of
+/// `output_bit_table` exceeds the size of the field's canonical encoding.
+pub fn unpack_scalars<F: PrimeField>(scalars: &[u8], output_bit_table: &[u32]) -> Vec<Vec<F>> {
+    let scalar_num_bytes = num_scalar_bytes::<F>();
+    assert!(
+        output_bit_table
+            .iter()
+            .all(|bits| *bits as usize <= 8 * scalar_num_bytes),
+        "output bit widths cannot exceed the size of the field elements"
+    );
+    let num_bytes = packed_row_num_bytes(output_bit_table);
+    if num_bytes == 0 {
+        return vec![Vec::new(); output_bit_table.len()];
+    }
+    assert_eq!(
+        scalars.len() % num_bytes,
+        0,
+        "scalars must hold a whole number of packed rows"
+    );
+
+    let mut bit_offset = 0;
+    output_bit_table
+        .iter()
+        .map(|bits| {
+            let bits = *bits as usize;
+            let column = scalars
+                .par_chunks_exact(num_bytes)
+                .map(|row| {
+                    let mut bytes = vec![0_u8; scalar_num_bytes];
+                    read_bits(row, bit_offset, &mut bytes, bits);
+                    F::from_le_bytes_mod_order(&bytes)
+                })
+                .collect();
+            bit_offset += bits;
+            column
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "bls12_381")]
+    use ark_bls12_381::Fr;
+    #[cfg(feature = "bls12_381")]
+    use ark_std::UniformRand;
+
+    #[test]
+    fn we_can_pack_unsigned_sequences_of_different_lengths() {
+        let a: Vec<u8> = vec![1, 2, 3];
+        let b: Vec<u16> = vec![0x0102];
+        let packed = pack_sequences(&[(&a).into(), (&b).into()]);
+        assert_eq!(packed.output_bit_table, vec![8, 16]);
+        assert_eq!(packed.sign_outputs, vec![None, None]);
+        assert_eq!(packed.scalars, vec![1, 2, 1, 2, 0, 0, 3, 0, 0]);
+    }
+
+    #[test]
+    fn we_can_pack_signed_sequences() {
+        let a: Vec<i8> = vec![-1, 2];
+        let b: Vec<u8> = vec![7, 8];
+        let c: Vec<i8> = vec![3, -4];
+        let packed = pack_sequences(&[(&a).into(), (&b).into(), (&c).into()]);
+        assert_eq!(packed.output_bit_table, vec![8, 8, 8, 1, 1]);
+        assert_eq!(packed.sign_outputs, vec![Some(3), None, Some(4)]);
+        assert_eq!(packed.scalars, vec![0xff, 7, 3, 0b01, 2, 8, 0xfc, 0b10]);
+    }
+
+    #[test]
+    #[cfg(feature = "bls12_381")]
+    fn we_can_pack_field_elements_into_packed_rows() {
+        let a = [Fr::from(0b101_u64), Fr::from(0b011_u64)];
+        let b = [Fr::from(0x1ff_u64)];
+        let packed = pack_scalars(&[&a, &b], &[3, 9]);
+        // row 0: 101 | 111111111 << 3, row 1: 011
+        assert_eq!(packed, vec![0b1111_1101, 0b0000_1111, 0b011, 0]);
+    }
+
+    #[test]
+    #[cfg(feature = "bls12_381")]
+    fn we_can_round_trip_packed_field_elements() {
+        let mut rng = ark_std::test_rng();
+        let full: Vec<Fr> = (0..9).map(|_| Fr::rand(&mut rng)).collect();
+        let narrow: Vec<Fr> = (0..7).map(|i| Fr::from(i as u64 % 5)).collect();
+        let bit: Vec<Fr> = (0..9).map(|i| Fr::from(i as u64 % 2)).collect();
+        let output_bit_table = [256, 3, 1, 13];
+        let wide: Vec<Fr> = (0..9).map(|i| Fr::from(1000 * i as u64)).collect();
+
+        let columns: [&[Fr]; 4] = [&full, &narrow, &bit, &wide];
+        let packed = pack_scalars(&columns, &output_bit_table);
+        assert_eq!(packed.len(), 9 * 35);
+
+        let unpacked: Vec<Vec<Fr>> = unpack_scalars(&packed, &output_bit_table);
+        assert_eq!(unpacked[0], full);
+        assert_eq!(unpacked[1][..7], narrow[..]);
+        assert_eq!(unpacked[1][7..], [Fr::from(0_u64); 2]);
+        assert_eq!(unpacked[2], bit);
+        assert_eq!(unpacked[3], wide);
+    }
+
+    #[test]
+    #[cfg(feature = "bls12_381")]
+    fn packing_field_elements_discards_bits_outside_of_the_output_bit_table() {
+        let a = [Fr::from(0xff_u64)];
+        let packed = pack_scalars(&[&a, &a], &[4, 4]);
+        assert_eq!(packed, vec![0xff]);
+        let unpacked: Vec<Vec<Fr>> = unpack_scalars(&packed, &[4, 4]);
+        assert_eq!(unpacked, vec![vec![Fr::from(0xf_u64)]; 2]);
+    }
+
+    #[test]
+    fn we_can_pack_empty_sequences() {
+        let packed = pack_sequences(&[]);
+        assert!(packed.output_bit_table.is_empty());
+        assert!(packed.scalars.is_empty());
+
+        let a: Vec<u8> = vec![];
+        let packed = pack_sequences(&[(&a).into()]);
+        assert_eq!(packed.output_bit_table, vec![8]);
+        assert!(packed.scalars.is_empty());
+    }
+}
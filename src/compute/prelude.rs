@@ -0,0 +1,25 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! the traits and types used with every curve
+//!
+//! ```no_run
+//! use blitzar::compute::{curve25519, prelude::*};
+//! ```
+
+pub use super::{
+    init_backend, CommitmentScheme, MsmHandle, RistrettoMsmHandle, ScalarSerialize, ScalarWidth,
+    SwMsmHandle,
+};
+pub use crate::sequence::Sequence;
@@ -0,0 +1,150 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::error::MsmError;
+#[cfg(any(feature = "bls12_381", feature = "bn254", feature = "grumpkin"))]
+use ark_ff::PrimeField;
+use rayon::prelude::*;
+
+/// Number of bytes of each scalar passed to [super::MsmHandle::try_msm]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ScalarWidth {
+    /// 1-byte scalars
+    B1,
+    /// 2-byte scalars
+    B2,
+    /// 4-byte scalars
+    B4,
+    /// 8-byte scalars
+    B8,
+    /// 16-byte scalars
+    B16,
+    /// 32-byte scalars, e.g. canonical field elements
+    B32,
+}
+
+impl ScalarWidth {
+    /// Returns the number of bytes of a scalar.
+    pub fn num_bytes(self) -> u32 {
+        match self {
+            ScalarWidth::B1 => 1,
+            ScalarWidth::B2 => 2,
+            ScalarWidth::B4 => 4,
+            ScalarWidth::B8 => 8,
+            ScalarWidth::B16 => 16,
+            ScalarWidth::B32 => 32,
+        }
+    }
+}
+
+impl TryFrom<u32> for ScalarWidth {
+    type Error = MsmError;
+
+    fn try_from(num_bytes: u32) -> Result<Self, MsmError> {
+        match num_bytes {
+            1 => Ok(ScalarWidth::B1),
+            2 => Ok(ScalarWidth::B2),
+            4 => Ok(ScalarWidth::B4),
+            8 => Ok(ScalarWidth::B8),
+            16 => Ok(ScalarWidth::B16),
+            32 => Ok(ScalarWidth::B32),
+            _ => Err(MsmError::InvalidScalarWidth(num_bytes)),
+        }
+    }
+}
+
+/// Scalar types accepted by the typed MSM entry points.
+///
+/// Implementations write the canonical little-endian encoding of the scalar
+/// (i.e. never the Montgomery representation), so that the bytes can be
+/// passed directly to the backend.
+pub trait ScalarSerialize: Sync {
+    /// Number of bytes used by the canonical encoding.
+    const NUM_BYTES: usize;
+
+    /// Write the canonical little-endian encoding of the scalar to `out`.
+    ///
+    /// `out` must have a length of exactly `NUM_BYTES`.
+    fn serialize_le(&self, out: &mut [u8]);
+}
+
+impl ScalarSerialize for curve25519_dalek::scalar::Scalar {
+    const NUM_BYTES: usize = 32;
+
+    fn serialize_le(&self, out: &mut [u8]) {
+        out.copy_from_slice(self.as_bytes());
+    }
+}
+
+#[allow(unused_macros)]
+macro_rules! impl_scalar_serialize_for_ark_field {
+    ($($t:ty),*) => {
+        $(
+            impl ScalarSerialize for $t {
+                const NUM_BYTES: usize = 32;
+
+                fn serialize_le(&self, out: &mut [u8]) {
+                    let limbs = self.into_bigint().0;
+                    out.chunks_exact_mut(8)
+                        .zip(limbs)
+                        .for_each(|(out, limb)| out.copy_from_slice(&limb.to_le_bytes()));
+                }
+            }
+        )*
+    };
+}
+#[cfg(feature = "bls12_381")]
+impl_scalar_serialize_for_ark_field!(ark_bls12_381::Fr);
+#[cfg(feature = "bn254")]
+impl_scalar_serialize_for_ark_field!(ark_bn254::Fr);
+#[cfg(feature = "grumpkin")]
+impl_scalar_serialize_for_ark_field!(ark_grumpkin::Fr);
+
+/// Serialize `scalars` into a contiguous byte array of `S::NUM_BYTES` per scalar.
+pub(crate) fn serialize_scalars<S: ScalarSerialize>(scalars: &[S]) -> Vec<u8> {
+    let mut bytes = vec![0_u8; scalars.len() * S::NUM_BYTES];
+    bytes
+        .par_chunks_exact_mut(S::NUM_BYTES)
+        .zip(scalars)
+        .for_each(|(out, s)| s.serialize_le(out));
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn we_can_convert_supported_scalar_widths() {
+        for width in [
+            ScalarWidth::B1,
+            ScalarWidth::B2,
+            ScalarWidth::B4,
+            ScalarWidth::B8,
+            ScalarWidth::B16,
+            ScalarWidth::B32,
+        ] {
+            assert_eq!(ScalarWidth::try_from(width.num_bytes()), Ok(width));
+        }
+    }
+
+    #[test]
+    fn we_cannot_convert_unsupported_scalar_widths() {
+        for num_bytes in [0, 3, 7, 31, 33, 64] {
+            assert_eq!(
+                ScalarWidth::try_from(num_bytes),
+                Err(MsmError::InvalidScalarWidth(num_bytes))
+            );
+        }
+    }
+}
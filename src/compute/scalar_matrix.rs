@@ -0,0 +1,206 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::scalar::ScalarSerialize;
+use rayon::prelude::*;
+
+/// Scalars laid out for `MsmHandle::matrix_msm`.
+///
+/// Given the scalars of `m` outputs
+///
+///    s_11, s_12, ..., s_1n
+///    .
+///    .
+///    s_m1, s_m2, ..., s_mn
+///
+/// the matrix stores them in the order expected by `MsmHandle::msm`
+///
+///    [s_11, s_21, ..., s_m1, s_12, s_22, ..., s_m2, ..., s_mn]
+///
+/// Outputs with fewer than `n` scalars are padded with zeros.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScalarMatrix {
+    element_num_bytes: u32,
+    num_outputs: usize,
+    scalars: Vec<u8>,
+}
+
+impl ScalarMatrix {
+    /// Build the matrix from row-major `data`, where row `i` holds the `n` scalars of
+    /// output `i`, each of `element_num_bytes` bytes.
+    ///
+    /// # Panics
+    ///
+    /// If `element_num_bytes` is zero or if `data.len()` is not a multiple of
+    /// `num_outputs * element_num_bytes`.
+    pub fn from_row_major(element_num_bytes: u32, num_outputs: usize, data: &[u8]) -> Self {
+        assert!(element_num_bytes > 0, "element_num_bytes must be non-zero");
+        let row_num_bytes = if num_outputs == 0 {
+            0
+        } else {
+            assert_eq!(
+                data.len() % (num_outputs * element_num_bytes as usize),
+                0,
+                "data must hold the same number of scalars for each output"
+            );
+            data.len() / num_outputs
+        };
+        if row_num_bytes == 0 {
+            assert!(
+                data.is_empty(),
+                "data must be empty when there are no outputs"
+            );
+            return Self::from_outputs(element_num_bytes, vec![&[][..]; num_outputs]);
+        }
+        Self::from_outputs(element_num_bytes, data.chunks_exact(row_num_bytes))
+    }
+
+    /// Build the matrix from the scalars of each output, each of `element_num_bytes`
+    /// bytes. Outputs may have different lengths.
+    ///
+    /// # Panics
+    ///
+    /// If `element_num_bytes` is zero or if the length of an output is not a multiple of
+    /// `element_num_bytes`.
+    pub fn from_outputs<'a>(
+        element_num_bytes: u32,
+        outputs: impl IntoIterator<Item = &'a [u8]>,
+    ) -> Self {
+        assert!(element_num_bytes > 0, "element_num_bytes must be non-zero");
+        let element_num_bytes_usize = element_num_bytes as usize;
+        let outputs: Vec<&[u8]> = outputs.into_iter().collect();
+        assert!(
+            outputs
+                .iter()
+                .all(|output| output.len() % element_num_bytes_usize == 0),
+            "output lengths must be a multiple of element_num_bytes"
+        );
+        let num_outputs = outputs.len();
+        let n = outputs
+            .iter()
+            .map(|output| output.len() / element_num_bytes_usize)
+            .max()
+            .unwrap_or(0);
+
+        let row_num_bytes = num_outputs * element_num_bytes_usize;
+        let mut scalars = vec![0_u8; n * row_num_bytes];
+        if row_num_bytes > 0 {
+            scalars
+                .par_chunks_exact_mut(row_num_bytes)
+                .enumerate()
+                .for_each(|(i, row)| {
+                    let range = i * element_num_bytes_usize..(i + 1) * element_num_bytes_usize;
+                    row.chunks_exact_mut(element_num_bytes_usize)
+                        .zip(&outputs)
+                        .filter(|(_, output)| range.end <= output.len())
+                        .for_each(|(out, output)| out.copy_from_slice(&output[range.clone()]));
+                });
+        }
+
+        Self {
+            element_num_bytes,
+            num_outputs,
+            scalars,
+        }
+    }
+
+    /// Build the matrix from the typed scalars of each output, serialized to their
+    /// canonical little-endian encoding. Outputs may have different lengths.
+    pub fn from_scalars<'a, S: ScalarSerialize + 'a>(
+        outputs: impl IntoIterator<Item = &'a [S]>,
+    ) -> Self {
+        let outputs: Vec<Vec<u8>> = outputs
+            .into_iter()
+            .map(super::scalar::serialize_scalars)
+            .collect();
+        Self::from_outputs(S::NUM_BYTES as u32, outputs.iter().map(Vec::as_slice))
+    }
+
+    /// Number of bytes of each scalar.
+    pub fn element_num_bytes(&self) -> u32 {
+        self.element_num_bytes
+    }
+
+    /// Number of outputs, i.e. `m`.
+    pub fn num_outputs(&self) -> usize {
+        self.num_outputs
+    }
+
+    /// Number of scalars of each output after padding, i.e. `n`.
+    pub fn len(&self) -> usize {
+        let row_num_bytes = self.num_outputs * self.element_num_bytes as usize;
+        self.scalars.len().checked_div(row_num_bytes).unwrap_or(0)
+    }
+
+    /// Returns true if the outputs have no scalars.
+    pub fn is_empty(&self) -> bool {
+        self.scalars.is_empty()
+    }
+
+    /// The scalars in the layout expected by `MsmHandle::msm`.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.scalars
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn we_can_build_a_matrix_from_row_major_data() {
+        let matrix = ScalarMatrix::from_row_major(1, 2, &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(matrix.num_outputs(), 2);
+        assert_eq!(matrix.len(), 3);
+        assert_eq!(matrix.as_bytes(), &[1, 4, 2, 5, 3, 6]);
+    }
+
+    #[test]
+    fn we_can_build_a_matrix_from_outputs_of_different_lengths() {
+        let a: &[u8] = &[1, 0, 2, 0, 3, 0];
+        let b: &[u8] = &[4, 0];
+        let matrix = ScalarMatrix::from_outputs(2, [a, b]);
+        assert_eq!(matrix.len(), 3);
+        assert_eq!(matrix.as_bytes(), &[1, 0, 4, 0, 2, 0, 0, 0, 3, 0, 0, 0]);
+    }
+
+    #[test]
+    fn we_can_build_an_empty_matrix() {
+        let matrix = ScalarMatrix::from_row_major(4, 0, &[]);
+        assert!(matrix.is_empty());
+        assert_eq!(matrix.num_outputs(), 0);
+
+        let matrix = ScalarMatrix::from_row_major(4, 3, &[]);
+        assert!(matrix.is_empty());
+        assert_eq!(matrix.num_outputs(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "element_num_bytes must be non-zero")]
+    fn we_cannot_build_a_matrix_of_zero_byte_scalars_from_row_major_data() {
+        ScalarMatrix::from_row_major(0, 2, &[1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "element_num_bytes must be non-zero")]
+    fn we_cannot_build_a_matrix_of_zero_byte_scalars_from_outputs() {
+        let a: &[u8] = &[];
+        ScalarMatrix::from_outputs(0, [a]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn we_cannot_build_a_matrix_from_ragged_row_major_data() {
+        ScalarMatrix::from_row_major(1, 2, &[1, 2, 3]);
+    }
+}
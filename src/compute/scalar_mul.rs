@@ -0,0 +1,126 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+#[cfg(any(feature = "bls12_381", feature = "bn254", feature = "grumpkin"))]
+use super::{fixed_msm::SwMsmHandle, ElementP2, SwCurveConfig};
+use super::{
+    fixed_msm::{MsmHandle, RistrettoMsmHandle},
+    scalar::{serialize_scalars, ScalarSerialize},
+};
+#[cfg(any(feature = "bls12_381", feature = "bn254", feature = "grumpkin"))]
+use ark_ec::short_weierstrass::Affine;
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+};
+
+fn check_lengths(res_len: usize, scalars_len: usize) {
+    assert_eq!(
+        res_len, scalars_len,
+        "res must hold one element for each scalar"
+    );
+}
+
+/// Computes `res[i] = scalars[i] * generator` on an affine short Weierstrass curve.
+///
+/// A handle with the single generator is an MSM of length one per output, so all the
+/// products are computed by one backend call.
+#[cfg(any(feature = "bls12_381", feature = "bn254", feature = "grumpkin"))]
+fn batch_scalar_mul_sw<C>(res: &mut [Affine<C>], generator: &Affine<C>, scalars: &[C::ScalarField])
+where
+    C: SwCurveConfig + Clone,
+    C::ScalarField: ScalarSerialize,
+{
+    check_lengths(res.len(), scalars.len());
+    if res.is_empty() {
+        return;
+    }
+    let handle = MsmHandle::<ElementP2<C>>::new_with_affine(std::slice::from_ref(generator));
+    let scalars = serialize_scalars(scalars);
+    handle.affine_msm(res, C::ScalarField::NUM_BYTES as u32, &scalars);
+}
+
+/// Computes the scalar multiplications `res[i] = scalars[i] * generator`
+/// for a single fixed `generator` in the Ristretto group.
+///
+/// Unlike an MSM, the products are not summed: each scalar gives its own output. The
+/// products are computed by a single backend call, using a handle created with the
+/// one generator.
+///
+/// # Panics
+///
+/// If `res.len()` is different from `scalars.len()`.
+pub fn batch_scalar_mul_curve25519(
+    res: &mut [CompressedRistretto],
+    generator: &RistrettoPoint,
+    scalars: &[Scalar],
+) {
+    check_lengths(res.len(), scalars.len());
+    if res.is_empty() {
+        return;
+    }
+    let handle = MsmHandle::new(std::slice::from_ref(generator));
+    let scalars = serialize_scalars(scalars);
+    handle.compressed_msm(res, Scalar::NUM_BYTES as u32, &scalars);
+}
+
+/// Computes the scalar multiplications `res[i] = scalars[i] * generator`
+/// for a single fixed `generator` in the bls12-381 G1 group.
+///
+/// See [batch_scalar_mul_curve25519].
+///
+/// # Panics
+///
+/// If `res.len()` is different from `scalars.len()`.
+#[cfg(feature = "bls12_381")]
+pub fn batch_scalar_mul_bls12_381_g1(
+    res: &mut [ark_bls12_381::G1Affine],
+    generator: &ark_bls12_381::G1Affine,
+    scalars: &[ark_bls12_381::Fr],
+) {
+    batch_scalar_mul_sw(res, generator, scalars);
+}
+
+/// Computes the scalar multiplications `res[i] = scalars[i] * generator`
+/// for a single fixed `generator` in the bn254 G1 group.
+///
+/// See [batch_scalar_mul_curve25519].
+///
+/// # Panics
+///
+/// If `res.len()` is different from `scalars.len()`.
+#[cfg(feature = "bn254")]
+pub fn batch_scalar_mul_bn254_g1(
+    res: &mut [ark_bn254::G1Affine],
+    generator: &ark_bn254::G1Affine,
+    scalars: &[ark_bn254::Fr],
+) {
+    batch_scalar_mul_sw(res, generator, scalars);
+}
+
+/// Computes the scalar multiplications `res[i] = scalars[i] * generator`
+/// for a single fixed `generator` in the Grumpkin group.
+///
+/// See [batch_scalar_mul_curve25519].
+///
+/// # Panics
+///
+/// If `res.len()` is different from `scalars.len()`.
+#[cfg(feature = "grumpkin")]
+pub fn batch_scalar_mul_grumpkin(
+    res: &mut [ark_grumpkin::Affine],
+    generator: &ark_grumpkin::Affine,
+    scalars: &[ark_grumpkin::Fr],
+) {
+    batch_scalar_mul_sw(res, generator, scalars);
+}
@@ -0,0 +1,111 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+#[cfg(feature = "bn254")]
+use ark_ec::AffineRepr;
+#[cfg(any(feature = "bls12_381", feature = "bn254", feature = "grumpkin"))]
+use ark_std::UniformRand;
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+};
+use rand_core::OsRng;
+
+#[test]
+fn we_can_compute_a_batch_of_curve25519_scalar_multiplications() {
+    let mut rng = OsRng;
+    let generator = RistrettoPoint::random(&mut rng);
+    let scalars: Vec<Scalar> = (0..10).map(|_| Scalar::random(&mut rng)).collect();
+
+    let mut res = vec![CompressedRistretto::default(); scalars.len()];
+    batch_scalar_mul_curve25519(&mut res, &generator, &scalars);
+
+    let expected: Vec<CompressedRistretto> =
+        scalars.iter().map(|s| (s * generator).compress()).collect();
+    assert_eq!(res, expected);
+}
+
+#[test]
+#[cfg(feature = "bls12_381")]
+fn we_can_compute_a_batch_of_bls12_381_g1_scalar_multiplications() {
+    let mut rng = OsRng;
+    let generator = ark_bls12_381::G1Affine::rand(&mut rng);
+    let scalars: Vec<ark_bls12_381::Fr> =
+        (0..10).map(|_| ark_bls12_381::Fr::rand(&mut rng)).collect();
+
+    let mut res = vec![ark_bls12_381::G1Affine::default(); scalars.len()];
+    batch_scalar_mul_bls12_381_g1(&mut res, &generator, &scalars);
+
+    let expected: Vec<ark_bls12_381::G1Affine> =
+        scalars.iter().map(|s| (generator * s).into()).collect();
+    assert_eq!(res, expected);
+}
+
+#[test]
+#[cfg(feature = "bn254")]
+fn we_can_compute_a_batch_of_bn254_g1_scalar_multiplications() {
+    let mut rng = OsRng;
+    let generator = ark_bn254::G1Affine::rand(&mut rng);
+    let scalars: Vec<ark_bn254::Fr> = (0..10).map(|_| ark_bn254::Fr::rand(&mut rng)).collect();
+
+    let mut res = vec![ark_bn254::G1Affine::default(); scalars.len()];
+    batch_scalar_mul_bn254_g1(&mut res, &generator, &scalars);
+
+    let expected: Vec<ark_bn254::G1Affine> =
+        scalars.iter().map(|s| (generator * s).into()).collect();
+    assert_eq!(res, expected);
+}
+
+#[test]
+#[cfg(feature = "grumpkin")]
+fn we_can_compute_a_batch_of_grumpkin_scalar_multiplications() {
+    let mut rng = OsRng;
+    let generator = ark_grumpkin::Affine::rand(&mut rng);
+    let scalars: Vec<ark_grumpkin::Fr> =
+        (0..10).map(|_| ark_grumpkin::Fr::rand(&mut rng)).collect();
+
+    let mut res = vec![ark_grumpkin::Affine::default(); scalars.len()];
+    batch_scalar_mul_grumpkin(&mut res, &generator, &scalars);
+
+    let expected: Vec<ark_grumpkin::Affine> =
+        scalars.iter().map(|s| (generator * s).into()).collect();
+    assert_eq!(res, expected);
+}
+
+#[test]
+#[cfg(feature = "bn254")]
+fn we_can_compute_a_batch_of_zero_and_one_scalar_multiplications() {
+    let generator = ark_bn254::G1Affine::generator();
+    let scalars = [ark_bn254::Fr::from(0_u64), ark_bn254::Fr::from(1_u64)];
+
+    let mut res = vec![ark_bn254::G1Affine::default(); 2];
+    batch_scalar_mul_bn254_g1(&mut res, &generator, &scalars);
+    assert_eq!(res, [ark_bn254::G1Affine::zero(), generator]);
+}
+
+#[test]
+fn we_can_compute_an_empty_batch_of_scalar_multiplications() {
+    let generator = RistrettoPoint::default();
+    let mut res: Vec<CompressedRistretto> = vec![];
+    batch_scalar_mul_curve25519(&mut res, &generator, &[]);
+    assert!(res.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "res must hold one element for each scalar")]
+fn we_cannot_compute_a_batch_of_scalar_multiplications_with_mismatched_lengths() {
+    let generator = RistrettoPoint::default();
+    let mut res = vec![CompressedRistretto::default(); 2];
+    batch_scalar_mul_curve25519(&mut res, &generator, &[Scalar::ONE]);
+}
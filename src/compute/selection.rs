@@ -0,0 +1,122 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::{
+    commitments::{compute_curve25519_commitments, compute_curve25519_commitments_with_generators},
+    delta_update::get_row_generators,
+};
+use crate::sequence::Sequence;
+use curve25519_dalek::ristretto::CompressedRistretto;
+use rayon::prelude::*;
+
+fn is_selected(bitmap: &[u8], i: usize) -> bool {
+    (bitmap[i / 8] >> (i % 8)) & 1 == 1
+}
+
+/// Returns `selection` restricted to the first `len` rows and to the valid rows of
+/// `sequence`.
+fn selected_rows_bitmap(sequence: &Sequence, selection: &[u8]) -> Vec<u8> {
+    let len = sequence.len();
+    let mut bitmap = selection[..len.div_ceil(8)].to_vec();
+    if let Some(validity) = sequence.validity() {
+        bitmap.iter_mut().zip(validity).for_each(|(b, v)| *b &= v);
+    }
+    if len % 8 != 0 {
+        bitmap[len / 8] &= (1_u8 << (len % 8)) - 1;
+    }
+    bitmap
+}
+
+/// Computes the `curve25519` commitments of the elementwise products of `data` and a
+/// selection bitmap, without materializing the products.
+///
+/// Bit `i % 8` of byte `i / 8` of `selection` is `1` if row `i` is selected, so that
+///
+/// ```text
+/// commitments[j] = sum_i data[j][i] * selection[i] * g[offset_generators + i]
+/// ```
+///
+/// Rows that are null in `data[j]` are not selected. When at least half of the rows
+/// are selected, the selection is passed to the backend as a validity bitmap and the
+/// unselected rows are subtracted, as for nulls. Otherwise only the selected elements
+/// are gathered and committed with the generators of their rows, so a sparse selection
+/// only costs as much as the rows it selects.
+///
+/// # Panics
+///
+/// If `commitments.len()` is different from `data.len()`, or if `selection` has fewer
+/// than `ceil(len / 8)` bytes for the longest sequence of `data`.
+pub fn compute_curve25519_commitments_with_selection(
+    commitments: &mut [CompressedRistretto],
+    data: &[Sequence],
+    selection: &[u8],
+    offset_generators: u64,
+) {
+    assert_eq!(
+        commitments.len(),
+        data.len(),
+        "commitments must have the same length as data"
+    );
+    let num_rows = data.iter().map(Sequence::len).max().unwrap_or(0);
+    assert!(
+        selection.len() >= num_rows.div_ceil(8),
+        "selection must have a bit for every row"
+    );
+    let bitmaps: Vec<Vec<u8>> = data
+        .par_iter()
+        .map(|sequence| selected_rows_bitmap(sequence, selection))
+        .collect();
+
+    let rows: Vec<u64> = (0..num_rows)
+        .filter(|i| is_selected(selection, *i))
+        .map(|i| i as u64)
+        .collect();
+    if 2 * rows.len() >= num_rows {
+        let data: Vec<Sequence> = data
+            .iter()
+            .zip(&bitmaps)
+            .map(|(sequence, bitmap)| sequence.with_validity(bitmap))
+            .collect();
+        compute_curve25519_commitments(commitments, &data, offset_generators);
+        return;
+    }
+
+    let gathered: Vec<Vec<u8>> = data
+        .par_iter()
+        .zip(&bitmaps)
+        .map(|(sequence, bitmap)| {
+            let element_size = sequence.element_size();
+            let mut values = vec![0_u8; rows.len() * element_size];
+            rows.iter()
+                .zip(values.chunks_exact_mut(element_size))
+                .filter(|(i, _)| (**i as usize) < sequence.len())
+                .filter(|(i, _)| is_selected(bitmap, **i as usize))
+                .for_each(|(i, value)| {
+                    let i = *i as usize;
+                    value.copy_from_slice(
+                        &sequence.data_slice()[i * element_size..(i + 1) * element_size],
+                    );
+                });
+            values
+        })
+        .collect();
+    let gathered: Vec<Sequence> = gathered
+        .iter()
+        .zip(data)
+        .map(|(values, sequence)| {
+            Sequence::from_byte_slice(values, sequence.element_size(), sequence.is_signed())
+        })
+        .collect();
+    let generators = get_row_generators(&rows, offset_generators);
+    compute_curve25519_commitments_with_generators(commitments, &gathered, &generators);
+}
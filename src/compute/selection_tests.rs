@@ -0,0 +1,105 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+use crate::{compute::compute_curve25519_commitments, sequence::Sequence};
+use curve25519_dalek::{ristretto::CompressedRistretto, traits::Identity};
+
+/// Commits to the materialized products `data[i] * selection[i]`.
+fn commit_products(data: &[i64], selection: &[u8], offset: u64) -> CompressedRistretto {
+    let products: Vec<i64> = data
+        .iter()
+        .enumerate()
+        .map(|(i, x)| x * ((selection[i / 8] >> (i % 8)) & 1) as i64)
+        .collect();
+    let mut commitments = [CompressedRistretto::default()];
+    compute_curve25519_commitments(&mut commitments, &[(&products).into()], offset);
+    commitments[0]
+}
+
+#[test]
+fn we_can_commit_to_a_dense_selection_of_a_sequence() {
+    let data: Vec<i64> = vec![1, -2, 3, 4, -5, 6, 7, 8, 9, -10];
+    let selection = [0b1101_1111_u8, 0b10];
+
+    let mut commitments = [CompressedRistretto::default()];
+    compute_curve25519_commitments_with_selection(
+        &mut commitments,
+        &[(&data).into()],
+        &selection,
+        3,
+    );
+    assert_eq!(commitments[0], commit_products(&data, &selection, 3));
+}
+
+#[test]
+fn we_can_commit_to_a_sparse_selection_of_a_sequence() {
+    let data: Vec<i64> = vec![1, -2, 3, 4, -5, 6, 7, 8, 9, -10];
+    let selection = [0b0100_0010_u8, 0b10];
+
+    let mut commitments = [CompressedRistretto::default()];
+    compute_curve25519_commitments_with_selection(
+        &mut commitments,
+        &[(&data).into()],
+        &selection,
+        3,
+    );
+    assert_eq!(commitments[0], commit_products(&data, &selection, 3));
+}
+
+#[test]
+fn we_can_commit_to_a_selection_of_sequences_of_different_lengths() {
+    let data_a: Vec<i64> = vec![1, 2, 3, 4, 5, 6, 7, 8, 9];
+    let data_b: Vec<i64> = vec![-1, -2, -3];
+    let data_c: Vec<i64> = vec![];
+    for selection in [[0b1000_0100_u8, 0b1], [0b1111_0111_u8, 0b0]] {
+        let mut commitments = [CompressedRistretto::default(); 3];
+        compute_curve25519_commitments_with_selection(
+            &mut commitments,
+            &[(&data_a).into(), (&data_b).into(), (&data_c).into()],
+            &selection,
+            0,
+        );
+        assert_eq!(commitments[0], commit_products(&data_a, &selection, 0));
+        assert_eq!(commitments[1], commit_products(&data_b, &selection, 0));
+        assert_eq!(commitments[2], CompressedRistretto::identity());
+    }
+}
+
+#[test]
+fn null_rows_are_not_selected() {
+    let data: Vec<i64> = vec![1, 2, 3, 4];
+    let validity = [0b1101_u8];
+    let expected_data: Vec<i64> = vec![1, 0, 3, 4];
+    for selection in [[0b0011_u8], [0b1111_u8]] {
+        let mut commitments = [CompressedRistretto::default()];
+        compute_curve25519_commitments_with_selection(
+            &mut commitments,
+            &[Sequence::from(&data).with_validity(&validity)],
+            &selection,
+            0,
+        );
+        assert_eq!(
+            commitments[0],
+            commit_products(&expected_data, &selection, 0)
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "selection must have a bit for every row")]
+fn we_cannot_commit_with_a_selection_that_is_too_short() {
+    let data: Vec<i64> = vec![0; 9];
+    let mut commitments = [CompressedRistretto::default()];
+    compute_curve25519_commitments_with_selection(&mut commitments, &[(&data).into()], &[0xff], 0);
+}
@@ -0,0 +1,87 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+use crate::sequence::{OwnedSequence, Sequence};
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+use rand_core::OsRng;
+use static_assertions::assert_impl_all;
+use std::sync::Arc;
+
+assert_impl_all!(MsmHandle<RistrettoPoint>: Send, Sync, Clone);
+#[cfg(feature = "bls12_381")]
+assert_impl_all!(MsmHandle<ElementP2<ark_bls12_381::g1::Config>>: Send, Sync, Clone);
+#[cfg(feature = "bn254")]
+assert_impl_all!(MsmHandle<ElementP2<ark_bn254::g1::Config>>: Send, Sync, Clone);
+#[cfg(feature = "grumpkin")]
+assert_impl_all!(MsmHandle<ElementP2<ark_grumpkin::GrumpkinConfig>>: Send, Sync, Clone);
+#[cfg(feature = "bls12_381")]
+assert_impl_all!(DoryCommitmentEngine: Send, Sync, Clone);
+assert_impl_all!(CommitmentCache: Send, Sync);
+assert_impl_all!(ScalarMatrix: Send, Sync);
+assert_impl_all!(CancellationToken: Send, Sync, Clone);
+assert_impl_all!(BackendInfo: Send, Sync);
+assert_impl_all!(Sequence<'static>: Send, Sync);
+assert_impl_all!(OwnedSequence<'static>: Send, Sync);
+assert_impl_all!(crate::error::Error: Send, Sync);
+
+#[test]
+fn we_can_share_a_handle_between_many_threads() {
+    let generators: Vec<RistrettoPoint> = (0..64)
+        .map(|_| RistrettoPoint::random(&mut OsRng))
+        .collect();
+    let handle = Arc::new(MsmHandle::new(&generators));
+
+    let threads: Vec<_> = (0..4_u64)
+        .map(|t| {
+            let handle = handle.clone();
+            let generators = generators.clone();
+            std::thread::spawn(move || {
+                for i in 0..4_u64 {
+                    let scalars: Vec<u64> = (0..64).map(|j| t * 1000 + i * 64 + j).collect();
+                    let bytes: Vec<u8> = scalars.iter().flat_map(|s| s.to_le_bytes()).collect();
+                    let mut res = [RistrettoPoint::default()];
+                    handle.msm(&mut res, 8, &bytes);
+                    let expected: RistrettoPoint = scalars
+                        .iter()
+                        .zip(&generators)
+                        .map(|(s, g)| Scalar::from(*s) * g)
+                        .sum();
+                    assert_eq!(res[0], expected);
+                }
+            })
+        })
+        .collect();
+    threads.into_iter().for_each(|t| t.join().unwrap());
+}
+
+#[test]
+fn we_can_compute_commitments_from_many_threads_at_once() {
+    let data: Vec<u32> = (0..100).collect();
+    let mut expected = [Default::default()];
+    compute_curve25519_commitments(&mut expected, &[(&data).into()], 0);
+
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let data = data.clone();
+            std::thread::spawn(move || {
+                for _ in 0..4 {
+                    let mut commitments = [Default::default()];
+                    compute_curve25519_commitments(&mut commitments, &[(&data).into()], 0);
+                    assert_eq!(commitments, expected);
+                }
+            })
+        })
+        .collect();
+    threads.into_iter().for_each(|t| t.join().unwrap());
+}
@@ -0,0 +1,90 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::sequence::Sequence;
+use rayon::prelude::*;
+
+/// Values hidden by the validity bitmaps of a set of sequences.
+///
+/// `rows` is the sorted union of the null rows of every sequence, and `values[j]` holds,
+/// for each of those rows, the element of `data[j]` if it is null there and zero
+/// otherwise. Committing `values[j]` with the generators of `rows` gives the
+/// contribution of the nulls of `data[j]`, which is subtracted from the commitment of
+/// its raw data.
+pub(crate) struct NullCorrection {
+    pub(crate) rows: Vec<u64>,
+    values: Vec<Vec<u8>>,
+    element_sizes: Vec<usize>,
+    signed: Vec<bool>,
+}
+
+impl NullCorrection {
+    /// Returns `None` if no sequence of `data` has a null element.
+    pub(crate) fn new(data: &[Sequence]) -> Option<Self> {
+        let null_indices: Vec<Vec<usize>> = data.par_iter().map(Sequence::null_indices).collect();
+        let mut rows: Vec<u64> = null_indices.iter().flatten().map(|i| *i as u64).collect();
+        if rows.is_empty() {
+            return None;
+        }
+        rows.sort_unstable();
+        rows.dedup();
+
+        let values = data
+            .par_iter()
+            .zip(&null_indices)
+            .map(|(sequence, null_indices)| {
+                if null_indices.is_empty() {
+                    return Vec::new();
+                }
+                let element_size = sequence.element_size();
+                let mut values = vec![0_u8; rows.len() * element_size];
+                for i in null_indices {
+                    let k = rows
+                        .binary_search(&(*i as u64))
+                        .expect("row was collected above");
+                    values[k * element_size..(k + 1) * element_size].copy_from_slice(
+                        &sequence.data_slice()[i * element_size..(i + 1) * element_size],
+                    );
+                }
+                values
+            })
+            .collect();
+        Some(Self {
+            rows,
+            values,
+            element_sizes: data.iter().map(Sequence::element_size).collect(),
+            signed: data.iter().map(Sequence::is_signed).collect(),
+        })
+    }
+
+    /// Returns one sequence per input sequence holding its null values, to be committed
+    /// with the generators of `rows`.
+    pub(crate) fn sequences(&self) -> Vec<Sequence<'_>> {
+        self.values
+            .iter()
+            .zip(&self.element_sizes)
+            .zip(&self.signed)
+            .map(|((values, element_size), is_signed)| {
+                Sequence::from_byte_slice(values, *element_size, *is_signed)
+            })
+            .collect()
+    }
+
+    /// Returns the generators of `rows`.
+    pub(crate) fn gather<T: Copy>(&self, generators: &[T]) -> Vec<T> {
+        self.rows
+            .iter()
+            .map(|row| generators[*row as usize])
+            .collect()
+    }
+}
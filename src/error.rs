@@ -0,0 +1,276 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! crate level error type
+//!
+//! [Error](enum@Error) groups the failures of the compute and proof modules in a few
+//! categories, so that downstream services can map them to status codes without matching
+//! on every module's error type. The module specific errors convert into it with `?`.
+//!
+//! The commitment functions return [Error](enum@Error) through their `try_` variants,
+//! `compute::try_compute_curve25519_commitments` and the
+//! `compute::try_compute_*_with_generators` functions of every curve. The chunked
+//! `curve25519` commitments also return it, and the `compute::MsmError` of
+//! `compute::MsmHandle::try_msm` converts into it.
+//! The other public APIs still panic on invalid inputs, in particular:
+//!
+//! - the non-`try_` commitment functions and the `*_with_handle` commitment functions
+//! - the `compute::MsmHandle` methods, except `try_msm`
+//! - the inner product proof functions of [proof](crate::proof)
+
+#[cfg(feature = "ffi")]
+use crate::compute::{BackendError, HandleFileError, MsmError};
+use crate::{
+    proof::ProofError,
+    sequence::{Sequence, SequenceError},
+};
+use thiserror::Error;
+
+/// Error of the fallible APIs of this crate
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// This error occurs when the backend cannot be initialized or fails.
+    #[cfg(feature = "ffi")]
+    #[error("Backend error: {0}")]
+    Backend(#[from] BackendError),
+
+    /// This error occurs when the inputs of a call are invalid.
+    #[error("Invalid input: {0}")]
+    InvalidInput(#[from] InvalidInputError),
+
+    /// This error occurs when a file cannot be read or written.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// This error occurs when a proof fails to verify.
+    #[error("Verification error: {0}")]
+    Verification(#[from] ProofError),
+
+    /// This error occurs when a computation is stopped through its cancellation token.
+    #[error("The computation was cancelled")]
+    Cancelled,
+}
+
+/// InvalidInputError related to the inputs of a call
+#[derive(Error, Debug)]
+pub enum InvalidInputError {
+    /// This error occurs when a sequence is not a valid descriptor.
+    #[error(transparent)]
+    Sequence(#[from] SequenceError),
+
+    /// This error occurs when an argument does not have the expected length.
+    #[error("{name} has length {actual} instead of {expected}")]
+    LengthMismatch {
+        /// The name of the argument
+        name: &'static str,
+        /// The expected length
+        expected: usize,
+        /// The actual length
+        actual: usize,
+    },
+
+    /// This error occurs when there are fewer generators than elements in a sequence.
+    #[error("sequence {index} has {sequence_len} elements but only {num_generators} generators are available")]
+    NotEnoughGenerators {
+        /// The index of the first sequence that is too long
+        index: usize,
+        /// The length of that sequence
+        sequence_len: usize,
+        /// The number of generators
+        num_generators: usize,
+    },
+
+    /// This error occurs when an argument has more elements than the backend accepts in
+    /// one call.
+    #[error("{name} has length {len}, more than the {max} the backend accepts")]
+    TooLong {
+        /// The name of the argument
+        name: &'static str,
+        /// The length of the argument
+        len: usize,
+        /// The largest length accepted by the backend
+        max: usize,
+    },
+
+    /// This error occurs when a chunked computation is given chunks of zero elements.
+    #[error("Chunk size must be at least 1 element")]
+    ZeroChunkSize,
+
+    /// This error occurs when a handle file is not valid for the handle being loaded.
+    #[cfg(feature = "ffi")]
+    #[error(transparent)]
+    HandleFile(HandleFileError),
+
+    /// This error occurs when the scalars of an MSM do not match the handle or outputs.
+    #[cfg(feature = "ffi")]
+    #[error(transparent)]
+    Msm(#[from] MsmError),
+}
+
+impl From<SequenceError> for Error {
+    fn from(err: SequenceError) -> Self {
+        Error::InvalidInput(err.into())
+    }
+}
+
+#[cfg(feature = "ffi")]
+impl From<HandleFileError> for Error {
+    fn from(err: HandleFileError) -> Self {
+        match err {
+            HandleFileError::Io(err) => Error::Io(err),
+            err => Error::InvalidInput(InvalidInputError::HandleFile(err)),
+        }
+    }
+}
+
+#[cfg(feature = "ffi")]
+impl From<MsmError> for Error {
+    fn from(err: MsmError) -> Self {
+        match err {
+            MsmError::Backend(err) => Error::Backend(err),
+            err => Error::InvalidInput(err.into()),
+        }
+    }
+}
+
+/// Returns an error if an argument named `name` does not have length `expected`.
+#[cfg_attr(not(feature = "ffi"), allow(dead_code))]
+pub(crate) fn check_length(
+    name: &'static str,
+    expected: usize,
+    actual: usize,
+) -> Result<(), InvalidInputError> {
+    if expected != actual {
+        return Err(InvalidInputError::LengthMismatch {
+            name,
+            expected,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// Returns `len` as the `u32` taken by the backend, or an error if an argument named
+/// `name` is too long for it.
+#[cfg_attr(not(feature = "ffi"), allow(dead_code))]
+pub(crate) fn try_backend_len(name: &'static str, len: usize) -> Result<u32, InvalidInputError> {
+    u32::try_from(len).map_err(|_| InvalidInputError::TooLong {
+        name,
+        len,
+        max: u32::MAX as usize,
+    })
+}
+
+/// Returns `len` as the `u32` taken by the backend.
+///
+/// # Panics
+///
+/// If an argument named `name` is too long for the backend, instead of silently
+/// truncating its length.
+#[cfg_attr(not(feature = "ffi"), allow(dead_code))]
+pub(crate) fn backend_len(name: &'static str, len: usize) -> u32 {
+    match try_backend_len(name, len) {
+        Ok(len) => len,
+        Err(err) => panic!("{err}"),
+    }
+}
+
+/// Returns an error if a sequence of `data` has more than `num_generators` elements.
+#[cfg_attr(not(feature = "ffi"), allow(dead_code))]
+pub(crate) fn check_generators(
+    data: &[Sequence],
+    num_generators: usize,
+) -> Result<(), InvalidInputError> {
+    match data.iter().position(|s| s.len() > num_generators) {
+        Some(index) => Err(InvalidInputError::NotEnoughGenerators {
+            index,
+            sequence_len: data[index].len(),
+            num_generators,
+        }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn we_can_only_pass_lengths_that_fit_in_a_u32_to_the_backend() {
+        assert_eq!(try_backend_len("data", 3).unwrap(), 3);
+        assert_eq!(
+            try_backend_len("data", u32::MAX as usize).unwrap(),
+            u32::MAX
+        );
+        let err = try_backend_len("data", u32::MAX as usize + 1).unwrap_err();
+        assert!(matches!(
+            err,
+            InvalidInputError::TooLong {
+                name: "data",
+                len,
+                max,
+            } if len == max + 1
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "data has length 4294967296")]
+    fn we_cannot_truncate_lengths_passed_to_the_backend() {
+        backend_len("data", u32::MAX as usize + 1);
+    }
+
+    #[test]
+    fn module_errors_convert_into_their_category() {
+        let err: Error = SequenceError::ZeroElementSize.into();
+        assert!(matches!(
+            err,
+            Error::InvalidInput(InvalidInputError::Sequence(SequenceError::ZeroElementSize))
+        ));
+
+        #[cfg(feature = "ffi")]
+        {
+            let err: Error = HandleFileError::InvalidHeader.into();
+            assert!(matches!(
+                err,
+                Error::InvalidInput(InvalidInputError::HandleFile(
+                    HandleFileError::InvalidHeader
+                ))
+            ));
+
+            let err: Error = HandleFileError::Io(std::io::ErrorKind::NotFound.into()).into();
+            assert!(matches!(err, Error::Io(_)));
+
+            let err: Error = MsmError::TooManyOutputs(3).into();
+            assert!(matches!(
+                err,
+                Error::InvalidInput(InvalidInputError::Msm(MsmError::TooManyOutputs(3)))
+            ));
+
+            let err: Error = MsmError::Backend(BackendError::MemoryLimitExceeded {
+                required: 2,
+                limit: 1,
+            })
+            .into();
+            assert!(matches!(
+                err,
+                Error::Backend(BackendError::MemoryLimitExceeded { .. })
+            ));
+        }
+
+        let err: Error = ProofError::VerificationError.into();
+        assert!(matches!(err, Error::Verification(_)));
+        assert_eq!(err.to_string(), "Verification error: Verification error");
+    }
+}
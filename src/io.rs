@@ -0,0 +1,446 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! reading columns of CSV and Parquet files into sequences
+//!
+//! A column is read with arrow and converted into an [OwnedSequence] with the following
+//! type mapping:
+//!
+//! | Arrow type                                 | Element size | Signed |
+//! |--------------------------------------------|--------------|--------|
+//! | `Boolean` (0 or 1)                         | 1            | no     |
+//! | `UInt8`, `UInt16`, `UInt32`, `UInt64`      | 1, 2, 4, 8   | no     |
+//! | `Int8`, `Int16`, `Int32`, `Int64`          | 1, 2, 4, 8   | yes    |
+//! | `Date32`                                   | 4            | yes    |
+//! | `Date64`, `Timestamp` (in its unit)        | 8            | yes    |
+//! | `Decimal128` (the unscaled integer)        | 16           | yes    |
+//!
+//! Other types, and columns holding null values, are rejected. The CSV schema is
+//! inferred from the whole file, which must start with a header row.
+
+use crate::sequence::{OwnedSequence, SequenceData, SequenceError};
+use arrow_array::{
+    cast::AsArray,
+    types::{
+        Date32Type, Date64Type, Decimal128Type, Int16Type, Int32Type, Int64Type, Int8Type,
+        TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
+        TimestampSecondType, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+    },
+    Array, RecordBatch,
+};
+use arrow_schema::{ArrowError, DataType, TimeUnit};
+use parquet::{
+    arrow::{arrow_reader::ParquetRecordBatchReaderBuilder, ProjectionMask},
+    errors::ParquetError,
+};
+use std::{
+    fs::File,
+    io::{Seek, SeekFrom},
+    path::Path,
+    sync::Arc,
+};
+use thiserror::Error;
+
+/// Errors of reading a column into a sequence
+#[derive(Error, Debug)]
+pub enum ColumnError {
+    /// This error occurs when the file cannot be opened or read.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// This error occurs when a CSV file cannot be parsed.
+    #[error("Arrow error: {0}")]
+    Arrow(#[from] ArrowError),
+
+    /// This error occurs when a Parquet file cannot be parsed.
+    #[error("Parquet error: {0}")]
+    Parquet(#[from] ParquetError),
+
+    /// This error occurs when the file has no column with the requested name.
+    #[error("the file has no column named {name}")]
+    ColumnNotFound {
+        /// The name of the column
+        name: String,
+    },
+
+    /// This error occurs when the type of the column cannot be committed.
+    #[error("columns of type {data_type} are not supported")]
+    UnsupportedType {
+        /// The type of the column
+        data_type: DataType,
+    },
+
+    /// This error occurs when the column holds a null value.
+    #[error("the column holds a null value at row {index}")]
+    NullValue {
+        /// The index of the first null row
+        index: usize,
+    },
+
+    /// This error occurs when the bytes of the column are not a valid sequence.
+    #[error(transparent)]
+    Sequence(#[from] SequenceError),
+
+    /// This error occurs when the commitment of the column cannot be computed.
+    #[cfg(feature = "ffi")]
+    #[error(transparent)]
+    Commitment(#[from] crate::error::Error),
+}
+
+/// Format of a file holding columns
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileFormat {
+    /// Comma separated values with a header row
+    Csv,
+    /// Apache Parquet
+    Parquet,
+}
+
+/// Bytes of a column read so far, with the element size and signedness of its type.
+struct ColumnBytes {
+    data: Vec<u8>,
+    element_size: usize,
+    is_signed: bool,
+    num_rows: usize,
+}
+
+macro_rules! append_primitive {
+    ($bytes:expr, $array:expr, $t:ty) => {
+        $bytes.extend(
+            $array
+                .as_primitive::<$t>()
+                .values()
+                .iter()
+                .flat_map(|x| x.to_le_bytes()),
+        )
+    };
+}
+
+impl ColumnBytes {
+    fn new(data_type: &DataType) -> Result<Self, ColumnError> {
+        let (element_size, is_signed) = match data_type {
+            DataType::Boolean | DataType::UInt8 => (1, false),
+            DataType::UInt16 => (2, false),
+            DataType::UInt32 => (4, false),
+            DataType::UInt64 => (8, false),
+            DataType::Int8 => (1, true),
+            DataType::Int16 => (2, true),
+            DataType::Int32 | DataType::Date32 => (4, true),
+            DataType::Int64 | DataType::Date64 | DataType::Timestamp(_, _) => (8, true),
+            DataType::Decimal128(_, _) => (16, true),
+            data_type => {
+                return Err(ColumnError::UnsupportedType {
+                    data_type: data_type.clone(),
+                })
+            }
+        };
+        Ok(ColumnBytes {
+            data: Vec::new(),
+            element_size,
+            is_signed,
+            num_rows: 0,
+        })
+    }
+
+    fn append(&mut self, array: &dyn Array) -> Result<(), ColumnError> {
+        if let Some(index) = (0..array.len()).find(|i| array.is_null(*i)) {
+            return Err(ColumnError::NullValue {
+                index: self.num_rows + index,
+            });
+        }
+        let bytes = &mut self.data;
+        match array.data_type() {
+            DataType::Boolean => bytes.extend(array.as_boolean().values().iter().map(u8::from)),
+            DataType::UInt8 => append_primitive!(bytes, array, UInt8Type),
+            DataType::UInt16 => append_primitive!(bytes, array, UInt16Type),
+            DataType::UInt32 => append_primitive!(bytes, array, UInt32Type),
+            DataType::UInt64 => append_primitive!(bytes, array, UInt64Type),
+            DataType::Int8 => append_primitive!(bytes, array, Int8Type),
+            DataType::Int16 => append_primitive!(bytes, array, Int16Type),
+            DataType::Int32 => append_primitive!(bytes, array, Int32Type),
+            DataType::Int64 => append_primitive!(bytes, array, Int64Type),
+            DataType::Date32 => append_primitive!(bytes, array, Date32Type),
+            DataType::Date64 => append_primitive!(bytes, array, Date64Type),
+            DataType::Timestamp(TimeUnit::Second, _) => {
+                append_primitive!(bytes, array, TimestampSecondType)
+            }
+            DataType::Timestamp(TimeUnit::Millisecond, _) => {
+                append_primitive!(bytes, array, TimestampMillisecondType)
+            }
+            DataType::Timestamp(TimeUnit::Microsecond, _) => {
+                append_primitive!(bytes, array, TimestampMicrosecondType)
+            }
+            DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+                append_primitive!(bytes, array, TimestampNanosecondType)
+            }
+            DataType::Decimal128(_, _) => append_primitive!(bytes, array, Decimal128Type),
+            data_type => {
+                return Err(ColumnError::UnsupportedType {
+                    data_type: data_type.clone(),
+                })
+            }
+        }
+        self.num_rows += array.len();
+        Ok(())
+    }
+
+    fn into_sequence(self) -> Result<OwnedSequence<'static>, ColumnError> {
+        Ok(OwnedSequence::try_new(
+            SequenceData::Owned(self.data),
+            self.element_size,
+            self.is_signed,
+        )?)
+    }
+}
+
+/// Converts the first column of each batch into a sequence.
+fn read_batches(
+    data_type: &DataType,
+    batches: impl Iterator<Item = Result<RecordBatch, ArrowError>>,
+) -> Result<OwnedSequence<'static>, ColumnError> {
+    let mut bytes = ColumnBytes::new(data_type)?;
+    for batch in batches {
+        bytes.append(batch?.column(0).as_ref())?;
+    }
+    bytes.into_sequence()
+}
+
+/// Reads the column named `column` of a CSV file with a header row into a sequence.
+///
+/// See the [module documentation](self) for the supported types.
+pub fn read_csv_column<P: AsRef<Path>>(
+    path: P,
+    column: &str,
+) -> Result<OwnedSequence<'static>, ColumnError> {
+    let mut file = File::open(path)?;
+    let format = arrow_csv::reader::Format::default().with_header(true);
+    let (schema, _) = format.infer_schema(&mut file, None)?;
+    let (index, field) =
+        schema
+            .column_with_name(column)
+            .ok_or_else(|| ColumnError::ColumnNotFound {
+                name: column.to_string(),
+            })?;
+    let data_type = field.data_type().clone();
+    file.seek(SeekFrom::Start(0))?;
+    let reader = arrow_csv::ReaderBuilder::new(Arc::new(schema))
+        .with_format(format)
+        .with_projection(vec![index])
+        .build(file)?;
+    read_batches(&data_type, reader)
+}
+
+/// Reads the column named `column` of a Parquet file into a sequence.
+///
+/// See the [module documentation](self) for the supported types.
+pub fn read_parquet_column<P: AsRef<Path>>(
+    path: P,
+    column: &str,
+) -> Result<OwnedSequence<'static>, ColumnError> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(path)?)?;
+    let (index, field) =
+        builder
+            .schema()
+            .column_with_name(column)
+            .ok_or_else(|| ColumnError::ColumnNotFound {
+                name: column.to_string(),
+            })?;
+    let data_type = field.data_type().clone();
+    let mask = ProjectionMask::roots(builder.parquet_schema(), [index]);
+    let reader = builder.with_projection(mask).build()?;
+    read_batches(&data_type, reader)
+}
+
+/// Reads the column named `column` of a file in the given `format` into a sequence.
+///
+/// See [read_csv_column] and [read_parquet_column].
+pub fn read_column<P: AsRef<Path>>(
+    path: P,
+    column: &str,
+    format: FileFormat,
+) -> Result<OwnedSequence<'static>, ColumnError> {
+    match format {
+        FileFormat::Csv => read_csv_column(path, column),
+        FileFormat::Parquet => read_parquet_column(path, column),
+    }
+}
+
+/// Computes the `curve25519` commitment of the column named `column` of a file.
+///
+/// The column is read with [read_column] and committed with
+/// [crate::compute::try_compute_curve25519_commitments], starting at the generator
+/// `offset_generators`.
+#[cfg(feature = "ffi")]
+pub fn compute_curve25519_commitment_from_column<P: AsRef<Path>>(
+    path: P,
+    column: &str,
+    format: FileFormat,
+    offset_generators: u64,
+) -> Result<curve25519_dalek::ristretto::CompressedRistretto, ColumnError> {
+    let sequence = read_column(path, column, format)?;
+    let mut commitment = [Default::default()];
+    crate::compute::try_compute_curve25519_commitments(
+        &mut commitment,
+        &[sequence.as_sequence()],
+        offset_generators,
+    )?;
+    Ok(commitment[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{BooleanArray, Decimal128Array, Int16Array, UInt32Array};
+    use arrow_schema::{Field, Schema};
+    use parquet::arrow::ArrowWriter;
+    use tempfile::TempDir;
+
+    fn to_bytes(sequence: &OwnedSequence) -> Vec<u8> {
+        sequence.data().to_vec()
+    }
+
+    #[test]
+    fn we_can_read_csv_columns_into_sequences() {
+        let tmp_dir = TempDir::new().unwrap();
+        let path = tmp_dir.path().join("t.csv");
+        std::fs::write(&path, "a,b,c\n1,-2,true\n3,4,false\n").unwrap();
+
+        let a = read_csv_column(&path, "a").unwrap();
+        let expected: Vec<u8> = [1_i64, 3].iter().flat_map(|x| x.to_le_bytes()).collect();
+        assert_eq!(to_bytes(&a), expected);
+        assert!(a.as_sequence().is_signed());
+
+        let b = read_column(&path, "b", FileFormat::Csv).unwrap();
+        let expected: Vec<u8> = [-2_i64, 4].iter().flat_map(|x| x.to_le_bytes()).collect();
+        assert_eq!(to_bytes(&b), expected);
+
+        let c = read_csv_column(&path, "c").unwrap();
+        assert_eq!(to_bytes(&c), [1, 0]);
+        assert!(!c.as_sequence().is_signed());
+    }
+
+    #[test]
+    fn we_can_read_parquet_columns_into_sequences() {
+        let tmp_dir = TempDir::new().unwrap();
+        let path = tmp_dir.path().join("t.parquet");
+        let batch = RecordBatch::try_from_iter([
+            (
+                "a",
+                Arc::new(UInt32Array::from(vec![1, 2, 3])) as Arc<dyn Array>,
+            ),
+            ("b", Arc::new(Int16Array::from(vec![-1, 0, 1])) as _),
+            (
+                "c",
+                Arc::new(BooleanArray::from(vec![true, false, true])) as _,
+            ),
+            (
+                "d",
+                Arc::new(
+                    Decimal128Array::from(vec![-5, 10, 15])
+                        .with_precision_and_scale(10, 2)
+                        .unwrap(),
+                ) as _,
+            ),
+        ])
+        .unwrap();
+        let mut writer =
+            ArrowWriter::try_new(File::create(&path).unwrap(), batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let a = read_parquet_column(&path, "a").unwrap();
+        let expected: Vec<u8> = [1_u32, 2, 3].iter().flat_map(|x| x.to_le_bytes()).collect();
+        assert_eq!(to_bytes(&a), expected);
+        assert!(!a.as_sequence().is_signed());
+
+        let b = read_parquet_column(&path, "b").unwrap();
+        let expected: Vec<u8> = [-1_i16, 0, 1]
+            .iter()
+            .flat_map(|x| x.to_le_bytes())
+            .collect();
+        assert_eq!(to_bytes(&b), expected);
+        assert!(b.as_sequence().is_signed());
+
+        let c = read_column(&path, "c", FileFormat::Parquet).unwrap();
+        assert_eq!(to_bytes(&c), [1, 0, 1]);
+
+        let d = read_parquet_column(&path, "d").unwrap();
+        let expected: Vec<u8> = [-5_i128, 10, 15]
+            .iter()
+            .flat_map(|x| x.to_le_bytes())
+            .collect();
+        assert_eq!(to_bytes(&d), expected);
+    }
+
+    #[test]
+    fn we_cannot_read_a_missing_column() {
+        let tmp_dir = TempDir::new().unwrap();
+        let path = tmp_dir.path().join("t.csv");
+        std::fs::write(&path, "a\n1\n").unwrap();
+        assert!(matches!(
+            read_csv_column(&path, "b"),
+            Err(ColumnError::ColumnNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn we_cannot_read_columns_with_nulls_or_unsupported_types() {
+        let tmp_dir = TempDir::new().unwrap();
+        let path = tmp_dir.path().join("t.csv");
+        std::fs::write(&path, "a,b\n1,x\n,y\n").unwrap();
+        assert!(matches!(
+            read_csv_column(&path, "a"),
+            Err(ColumnError::NullValue { index: 1 })
+        ));
+        assert!(matches!(
+            read_csv_column(&path, "b"),
+            Err(ColumnError::UnsupportedType {
+                data_type: DataType::Utf8
+            })
+        ));
+
+        let path = tmp_dir.path().join("t.parquet");
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::UInt32, true)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(UInt32Array::from(vec![Some(1), None]))],
+        )
+        .unwrap();
+        let mut writer = ArrowWriter::try_new(File::create(&path).unwrap(), schema, None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        assert!(matches!(
+            read_parquet_column(&path, "a"),
+            Err(ColumnError::NullValue { index: 1 })
+        ));
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn we_can_commit_to_a_column_of_a_file() {
+        use crate::compute::compute_curve25519_commitments;
+        use curve25519_dalek::ristretto::CompressedRistretto;
+
+        let tmp_dir = TempDir::new().unwrap();
+        let path = tmp_dir.path().join("t.csv");
+        std::fs::write(&path, "a\n1\n-2\n3\n").unwrap();
+
+        let commitment =
+            compute_curve25519_commitment_from_column(&path, "a", FileFormat::Csv, 4).unwrap();
+        let data: Vec<i64> = vec![1, -2, 3];
+        let mut expected = [CompressedRistretto::default()];
+        compute_curve25519_commitments(&mut expected, &[(&data).into()], 4);
+        assert_eq!(commitment, expected[0]);
+    }
+}
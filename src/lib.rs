@@ -73,6 +73,25 @@
 //! cargo run --features <cpu|gpu> --example <example_name>
 //!```
 //!
+//! ## WebAssembly
+//!
+//! The `ffi` feature, enabled by both `cpu` and `gpu`, links the `blitzar-sys` backend and
+//! provides the [compute] module. Without it, the crate builds for targets such as
+//! `wasm32-unknown-unknown`, keeping the [sequence] descriptors, the [reference]
+//! implementations and the serialization of the [proof] types:
+//!```text
+//! cargo build --lib --no-default-features --target wasm32-unknown-unknown
+//!```
+//!
+//! ## Curves
+//!
+//! `curve25519` is always available. The other curves are behind the `bls12_381`,
+//! `bn254` and `grumpkin` features, all enabled by default through `all-curves`, so that
+//! users of a single curve do not compile the arkworks crates of the others:
+//!```text
+//! blitzar = { version = "...", default-features = false, features = ["gpu", "bn254"] }
+//!```
+//!
 //! ## Tests
 //! ```text
 //!  cargo test
@@ -91,7 +110,37 @@
 pub mod sequence;
 
 // Responsible for all computations (commitments and generator computation)
+#[cfg(feature = "ffi")]
 pub mod compute;
 
+// Responsible for the crate level error type of the fallible APIs
+pub mod error;
+
 // Responsible for all proof primitives
 pub mod proof;
+
+// Reading columns of CSV and Parquet files into sequences
+#[cfg(feature = "io")]
+pub mod io;
+
+// Pure-Rust implementations used to cross-check the backend
+pub mod reference;
+
+// protobuf messages for proofs and commitments
+#[cfg(feature = "proto")]
+pub mod proto;
+
+// Stable C interface for embedding the crate in other languages
+#[cfg(feature = "capi")]
+pub mod capi;
+
+// Python bindings of the compute APIs
+#[cfg(feature = "python")]
+pub mod python;
+
+#[cfg(feature = "bench")]
+pub mod bench;
+
+// Conformance checks for downstream crates
+#[cfg(feature = "testing")]
+pub mod testing;
@@ -0,0 +1,161 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! arkworks serialization of the proof types
+//!
+//! Points and scalars are written in their 32 byte encodings and vectors are prefixed
+//! with their length as a `u64`, as arkworks does. The encoding is the same in the
+//! compressed and uncompressed modes.
+
+use super::InnerProductProof;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+use curve25519_dalek::{ristretto::CompressedRistretto, scalar::Scalar};
+
+fn read_bytes<R: Read>(mut reader: R) -> Result<[u8; 32], SerializationError> {
+    let mut bytes = [0_u8; 32];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn serialize_points<W: Write>(
+    points: &[CompressedRistretto],
+    mut writer: W,
+    compress: Compress,
+) -> Result<(), SerializationError> {
+    points.len().serialize_with_mode(&mut writer, compress)?;
+    points
+        .iter()
+        .try_for_each(|point| writer.write_all(point.as_bytes()))?;
+    Ok(())
+}
+
+fn deserialize_points<R: Read>(
+    mut reader: R,
+    compress: Compress,
+    validate: Validate,
+) -> Result<Vec<CompressedRistretto>, SerializationError> {
+    let len = usize::deserialize_with_mode(&mut reader, compress, validate)?;
+    // the length is not trusted, so the vector is not preallocated
+    let mut points = Vec::new();
+    for _ in 0..len {
+        points.push(CompressedRistretto(read_bytes(&mut reader)?));
+    }
+    Ok(points)
+}
+
+impl Valid for InnerProductProof {
+    /// Checks that every point of the proof can be decompressed.
+    fn check(&self) -> Result<(), SerializationError> {
+        self.l_vector
+            .iter()
+            .chain(&self.r_vector)
+            .try_for_each(|point| match point.decompress() {
+                Some(_) => Ok(()),
+                None => Err(SerializationError::InvalidData),
+            })
+    }
+}
+
+impl CanonicalSerialize for InnerProductProof {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        serialize_points(&self.l_vector, &mut writer, compress)?;
+        serialize_points(&self.r_vector, &mut writer, compress)?;
+        writer.write_all(self.ap_value.as_bytes())?;
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        2 * 0_usize.serialized_size(compress) + 32 * (self.l_vector.len() + self.r_vector.len() + 1)
+    }
+}
+
+impl CanonicalDeserialize for InnerProductProof {
+    /// Reads a proof. Scalars must always be canonical, while points are only checked
+    /// with [Validate::Yes].
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let l_vector = deserialize_points(&mut reader, compress, validate)?;
+        let r_vector = deserialize_points(&mut reader, compress, validate)?;
+        let ap_value = Option::from(Scalar::from_canonical_bytes(read_bytes(&mut reader)?))
+            .ok_or(SerializationError::InvalidData)?;
+        let proof = InnerProductProof {
+            l_vector,
+            r_vector,
+            ap_value,
+        };
+        if validate == Validate::Yes {
+            proof.check()?;
+        }
+        Ok(proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use rand_core::OsRng;
+
+    fn random_proof(n: usize) -> InnerProductProof {
+        let mut points = (0..2 * n).map(|_| RistrettoPoint::random(&mut OsRng).compress());
+        InnerProductProof {
+            l_vector: points.by_ref().take(n).collect(),
+            r_vector: points.collect(),
+            ap_value: Scalar::random(&mut OsRng),
+        }
+    }
+
+    #[test]
+    fn we_can_serialize_and_deserialize_an_inner_product_proof() {
+        for n in [0, 1, 5] {
+            let proof = random_proof(n);
+            let mut bytes = Vec::new();
+            proof.serialize_compressed(&mut bytes).unwrap();
+            assert_eq!(bytes.len(), proof.compressed_size());
+            assert_eq!(bytes.len(), 16 + 32 * (2 * n + 1));
+            let res = InnerProductProof::deserialize_compressed(&bytes[..]).unwrap();
+            assert_eq!(res.l_vector, proof.l_vector);
+            assert_eq!(res.r_vector, proof.r_vector);
+            assert_eq!(res.ap_value, proof.ap_value);
+        }
+    }
+
+    #[test]
+    fn we_cannot_deserialize_an_invalid_inner_product_proof() {
+        let proof = random_proof(2);
+        let mut bytes = Vec::new();
+        proof.serialize_compressed(&mut bytes).unwrap();
+
+        let mut invalid_point = bytes.clone();
+        invalid_point[8..40].copy_from_slice(&[0xff; 32]);
+        assert!(InnerProductProof::deserialize_compressed(&invalid_point[..]).is_err());
+        assert!(InnerProductProof::deserialize_compressed_unchecked(&invalid_point[..]).is_ok());
+
+        let mut invalid_scalar = bytes.clone();
+        let n = invalid_scalar.len();
+        invalid_scalar[n - 32..].copy_from_slice(&[0xff; 32]);
+        assert!(InnerProductProof::deserialize_compressed_unchecked(&invalid_scalar[..]).is_err());
+
+        assert!(InnerProductProof::deserialize_compressed(&bytes[..n - 1]).is_err());
+    }
+}
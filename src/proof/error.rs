@@ -19,4 +19,11 @@ pub enum ProofError {
     /// This error occurs when a proof failed to verify.
     #[error("Verification error")]
     VerificationError,
+
+    /// This error occurs when a proof of a batch failed to verify.
+    #[error("Verification error of proof {index} of the batch")]
+    BatchVerificationError {
+        /// The index of the first proof of the batch that failed to verify
+        index: usize,
+    },
 }
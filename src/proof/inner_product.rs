@@ -11,16 +11,18 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
-use super::error::ProofError;
-use crate::compute::init_backend;
-use curve25519_dalek::{
-    ristretto::{CompressedRistretto, RistrettoPoint},
-    scalar::Scalar,
-};
-use merlin::Transcript;
+use curve25519_dalek::{ristretto::CompressedRistretto, scalar::Scalar};
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "ffi")]
+use {
+    super::error::ProofError, crate::compute::init_backend,
+    curve25519_dalek::ristretto::RistrettoPoint, merlin::Transcript, rayon::prelude::*,
+};
 
 /// InnerProductProof construct
+///
+/// Creating and verifying proofs requires the `ffi` feature, while the proofs can be
+/// (de)serialized on any target.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct InnerProductProof {
     pub(crate) l_vector: Vec<CompressedRistretto>,
@@ -28,6 +30,7 @@ pub struct InnerProductProof {
     pub(crate) ap_value: Scalar,
 }
 
+#[cfg(feature = "ffi")]
 impl InnerProductProof {
     /// Creates an inner product proof.
     ///
@@ -227,4 +230,75 @@ impl InnerProductProof {
 
         Err(ProofError::VerificationError)
     }
+
+    /// Creates a batch of inner product proofs.
+    ///
+    /// This is equivalent to calling `InnerProductProof::create(&mut transcripts[i], a, b, generators_offset)`
+    /// for each `(a, b, generators_offset)` tuple of `inputs`, with the proofs being created
+    /// concurrently so that the backend is kept busy when many small proofs are needed.
+    ///
+    /// # Arguments:
+    ///
+    /// - `transcripts` (in/out): one strobe-based transcript per proof
+    /// - `inputs` (in): one `(a, b, generators_offset)` tuple per proof, with the same
+    ///   requirements as the arguments of `InnerProductProof::create`
+    pub fn create_batch(
+        transcripts: &mut [Transcript],
+        inputs: &[(&[Scalar], &[Scalar], u64)],
+    ) -> Vec<InnerProductProof> {
+        assert_eq!(transcripts.len(), inputs.len());
+
+        init_backend();
+
+        transcripts
+            .par_iter_mut()
+            .zip(inputs)
+            .map(|(transcript, (a, b, generators_offset))| {
+                InnerProductProof::create(transcript, a, b, *generators_offset)
+            })
+            .collect()
+    }
+
+    /// Verifies a batch of inner product proofs.
+    ///
+    /// This is equivalent to calling
+    /// `proofs[i].verify(&mut transcripts[i], a_commit, product, b, generators_offset)`
+    /// for each `(a_commit, product, b, generators_offset)` tuple of `inputs`, with the
+    /// proofs being verified concurrently.
+    ///
+    /// If any of the proofs fails to verify, [ProofError::BatchVerificationError] is
+    /// returned with the index of the first failing proof.
+    ///
+    /// # Arguments:
+    ///
+    /// - `proofs` (in): the proofs to verify
+    /// - `transcripts` (in/out): one strobe-based transcript per proof
+    /// - `inputs` (in): one `(a_commit, product, b, generators_offset)` tuple per proof, with the
+    ///   same requirements as the arguments of `InnerProductProof::verify`
+    pub fn verify_batch(
+        proofs: &[InnerProductProof],
+        transcripts: &mut [Transcript],
+        inputs: &[(&RistrettoPoint, &Scalar, &[Scalar], u64)],
+    ) -> Result<(), ProofError> {
+        assert_eq!(proofs.len(), inputs.len());
+        assert_eq!(transcripts.len(), inputs.len());
+
+        init_backend();
+
+        let failed = proofs
+            .par_iter()
+            .zip(transcripts.par_iter_mut())
+            .zip(inputs)
+            .position_first(
+                |((proof, transcript), (a_commit, product, b, generators_offset))| {
+                    proof
+                        .verify(transcript, a_commit, product, b, *generators_offset)
+                        .is_err()
+                },
+            );
+        match failed {
+            Some(index) => Err(ProofError::BatchVerificationError { index }),
+            None => Ok(()),
+        }
+    }
 }
@@ -187,3 +187,71 @@ fn test_prove_and_verify_random_proofs_of_varying_size() {
         test_prove_and_verify_with_given_n_and_generators_offset(i, i);
     }
 }
+
+#[test]
+fn we_can_create_and_verify_a_batch_of_proofs() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(123);
+
+    let lengths = [1_usize, 2, 5, 8];
+    let a: Vec<Vec<_>> = lengths
+        .iter()
+        .map(|n| (0..*n).map(|_| Scalar::random(&mut rng)).collect())
+        .collect();
+    let b: Vec<Vec<_>> = lengths
+        .iter()
+        .map(|n| (0..*n).map(|_| Scalar::random(&mut rng)).collect())
+        .collect();
+    let generators_offsets: Vec<u64> = (0..lengths.len() as u64).collect();
+
+    let inputs: Vec<_> = a
+        .iter()
+        .zip(&b)
+        .zip(&generators_offsets)
+        .map(|((a, b), offset)| (a.as_slice(), b.as_slice(), *offset))
+        .collect();
+    let mut transcripts = vec![Transcript::new(b"innerproducttest"); lengths.len()];
+    let proofs = InnerProductProof::create_batch(&mut transcripts, &inputs);
+
+    let products: Vec<Scalar> = a
+        .iter()
+        .zip(&b)
+        .map(|(a, b)| a.iter().zip(b).map(|(a_i, b_i)| a_i * b_i).sum())
+        .collect();
+    let a_commits: Vec<RistrettoPoint> = a
+        .iter()
+        .zip(&generators_offsets)
+        .map(|(a, offset)| {
+            let mut g = vec![RistrettoPoint::default(); a.len()];
+            get_curve25519_generators(&mut g, *offset);
+            a.iter().zip(&g).map(|(a_i, g_i)| a_i * g_i).sum()
+        })
+        .collect();
+
+    // We can verify the proofs as a batch
+    let mut inputs: Vec<_> = a_commits
+        .iter()
+        .zip(&products)
+        .zip(&b)
+        .zip(&generators_offsets)
+        .map(|(((a_commit, product), b), offset)| (a_commit, product, b.as_slice(), *offset))
+        .collect();
+    let mut transcripts = vec![Transcript::new(b"innerproducttest"); lengths.len()];
+    assert!(InnerProductProof::verify_batch(&proofs, &mut transcripts, &inputs).is_ok());
+
+    // Each proof of the batch is also valid on its own
+    for (proof, (a_commit, product, b, offset)) in proofs.iter().zip(&inputs) {
+        let mut transcript = Transcript::new(b"innerproducttest");
+        assert!(proof
+            .verify(&mut transcript, a_commit, product, b, *offset)
+            .is_ok());
+    }
+
+    // We cannot verify the batch if any of the proofs is invalid
+    let invalid_product = products[2] + Scalar::from(123_u64);
+    inputs[2].1 = &invalid_product;
+    let mut transcripts = vec![Transcript::new(b"innerproducttest"); lengths.len()];
+    assert!(matches!(
+        InnerProductProof::verify_batch(&proofs, &mut transcripts, &inputs),
+        Err(ProofError::BatchVerificationError { index: 2 })
+    ));
+}
@@ -20,5 +20,8 @@ pub use error::ProofError;
 mod inner_product;
 pub use inner_product::InnerProductProof;
 
-#[cfg(test)]
+#[cfg(feature = "arkworks")]
+mod canonical;
+
+#[cfg(all(test, feature = "ffi"))]
 mod inner_product_tests;
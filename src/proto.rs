@@ -0,0 +1,243 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! protobuf messages for proofs and commitments
+//!
+//! The messages are defined in `proto/blitzar.proto` and derived with `prost`, so that
+//! services written in other languages can share the same wire format. The `From` and
+//! `TryFrom` conversions check the lengths and encodings of the bytes received.
+
+use crate::proof;
+use curve25519_dalek::{ristretto::CompressedRistretto, scalar::Scalar};
+use thiserror::Error;
+
+/// An inner product proof over curve25519, see [proof::InnerProductProof].
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct InnerProductProof {
+    /// The compressed ristretto points L of each round, 32 bytes each.
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    pub l_vector: Vec<Vec<u8>>,
+    /// The compressed ristretto points R of each round, 32 bytes each.
+    #[prost(bytes = "vec", repeated, tag = "2")]
+    pub r_vector: Vec<Vec<u8>>,
+    /// The canonical little-endian encoding of the final scalar, 32 bytes.
+    #[prost(bytes = "vec", tag = "3")]
+    pub ap_value: Vec<u8>,
+}
+
+/// A list of commitments in their compressed encoding.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Commitments {
+    /// The encoded commitments, e.g. 32 bytes per ristretto point or 48 bytes per
+    /// bls12-381 `G1` point.
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    pub commitments: Vec<Vec<u8>>,
+}
+
+/// ProtoError related to the conversion of protobuf messages
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ProtoError {
+    /// This error occurs when a field does not have the expected number of bytes.
+    #[error("{field} has {actual} bytes instead of {expected}")]
+    InvalidLength {
+        /// The name of the field
+        field: &'static str,
+        /// The expected number of bytes
+        expected: usize,
+        /// The actual number of bytes
+        actual: usize,
+    },
+
+    /// This error occurs when a field is not a canonical encoding.
+    #[error("{0} is not a canonical encoding")]
+    NonCanonical(&'static str),
+
+    /// This error occurs when the `l_vector` and `r_vector` of a proof have different lengths.
+    #[error("l_vector has {l_len} points but r_vector has {r_len}")]
+    MismatchedRounds {
+        /// The length of `l_vector`
+        l_len: usize,
+        /// The length of `r_vector`
+        r_len: usize,
+    },
+}
+
+fn to_array<const N: usize>(field: &'static str, bytes: &[u8]) -> Result<[u8; N], ProtoError> {
+    bytes.try_into().map_err(|_| ProtoError::InvalidLength {
+        field,
+        expected: N,
+        actual: bytes.len(),
+    })
+}
+
+fn to_points(
+    field: &'static str,
+    points: &[Vec<u8>],
+) -> Result<Vec<CompressedRistretto>, ProtoError> {
+    points
+        .iter()
+        .map(|bytes| to_array(field, bytes).map(CompressedRistretto))
+        .collect()
+}
+
+impl From<&proof::InnerProductProof> for InnerProductProof {
+    fn from(proof: &proof::InnerProductProof) -> Self {
+        InnerProductProof {
+            l_vector: proof.l_vector.iter().map(|p| p.0.to_vec()).collect(),
+            r_vector: proof.r_vector.iter().map(|p| p.0.to_vec()).collect(),
+            ap_value: proof.ap_value.to_bytes().to_vec(),
+        }
+    }
+}
+
+impl TryFrom<&InnerProductProof> for proof::InnerProductProof {
+    type Error = ProtoError;
+
+    /// Decodes the proof, checking that `ap_value` is a canonical scalar.
+    ///
+    /// The points are not decompressed, so an invalid point is only detected when the
+    /// proof is verified.
+    fn try_from(proof: &InnerProductProof) -> Result<Self, ProtoError> {
+        if proof.l_vector.len() != proof.r_vector.len() {
+            return Err(ProtoError::MismatchedRounds {
+                l_len: proof.l_vector.len(),
+                r_len: proof.r_vector.len(),
+            });
+        }
+        let ap_value = Option::from(Scalar::from_canonical_bytes(to_array(
+            "ap_value",
+            &proof.ap_value,
+        )?))
+        .ok_or(ProtoError::NonCanonical("ap_value"))?;
+        Ok(proof::InnerProductProof {
+            l_vector: to_points("l_vector", &proof.l_vector)?,
+            r_vector: to_points("r_vector", &proof.r_vector)?,
+            ap_value,
+        })
+    }
+}
+
+impl From<&[CompressedRistretto]> for Commitments {
+    fn from(commitments: &[CompressedRistretto]) -> Self {
+        Commitments {
+            commitments: commitments.iter().map(|c| c.0.to_vec()).collect(),
+        }
+    }
+}
+
+impl TryFrom<&Commitments> for Vec<CompressedRistretto> {
+    type Error = ProtoError;
+
+    fn try_from(commitments: &Commitments) -> Result<Self, ProtoError> {
+        to_points("commitments", &commitments.commitments)
+    }
+}
+
+impl From<&[[u8; 48]]> for Commitments {
+    /// Encodes compressed bls12-381 `G1` commitments.
+    fn from(commitments: &[[u8; 48]]) -> Self {
+        Commitments {
+            commitments: commitments.iter().map(|c| c.to_vec()).collect(),
+        }
+    }
+}
+
+impl TryFrom<&Commitments> for Vec<[u8; 48]> {
+    type Error = ProtoError;
+
+    /// Decodes compressed bls12-381 `G1` commitments, checking only their length.
+    fn try_from(commitments: &Commitments) -> Result<Self, ProtoError> {
+        commitments
+            .commitments
+            .iter()
+            .map(|bytes| to_array("commitments", bytes))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use prost::Message;
+
+    #[test]
+    fn we_can_round_trip_an_inner_product_proof_through_protobuf() {
+        let mut rng = rand_core::OsRng;
+        let proof = proof::InnerProductProof {
+            l_vector: (0..3)
+                .map(|_| RistrettoPoint::random(&mut rng).compress())
+                .collect(),
+            r_vector: (0..3)
+                .map(|_| RistrettoPoint::random(&mut rng).compress())
+                .collect(),
+            ap_value: Scalar::random(&mut rng),
+        };
+
+        let bytes = InnerProductProof::from(&proof).encode_to_vec();
+        let decoded = InnerProductProof::decode(&bytes[..]).unwrap();
+        let decoded = proof::InnerProductProof::try_from(&decoded).unwrap();
+        assert_eq!(decoded.l_vector, proof.l_vector);
+        assert_eq!(decoded.r_vector, proof.r_vector);
+        assert_eq!(decoded.ap_value, proof.ap_value);
+    }
+
+    #[test]
+    fn we_cannot_decode_an_invalid_inner_product_proof() {
+        let mut message = InnerProductProof {
+            l_vector: vec![vec![0; 32]],
+            r_vector: vec![vec![0; 31]],
+            ap_value: vec![0; 32],
+        };
+        assert_eq!(
+            proof::InnerProductProof::try_from(&message).err(),
+            Some(ProtoError::InvalidLength {
+                field: "r_vector",
+                expected: 32,
+                actual: 31
+            })
+        );
+
+        message.r_vector = vec![];
+        assert_eq!(
+            proof::InnerProductProof::try_from(&message).err(),
+            Some(ProtoError::MismatchedRounds { l_len: 1, r_len: 0 })
+        );
+
+        message.r_vector = vec![vec![0; 32]];
+        message.ap_value = vec![0xff; 32];
+        assert_eq!(
+            proof::InnerProductProof::try_from(&message).err(),
+            Some(ProtoError::NonCanonical("ap_value"))
+        );
+    }
+
+    #[test]
+    fn we_can_round_trip_commitments_through_protobuf() {
+        let commitments: Vec<CompressedRistretto> = (0..2)
+            .map(|_| RistrettoPoint::random(&mut rand_core::OsRng).compress())
+            .collect();
+        let bytes = Commitments::from(&commitments[..]).encode_to_vec();
+        let decoded = Commitments::decode(&bytes[..]).unwrap();
+        assert_eq!(
+            Vec::<CompressedRistretto>::try_from(&decoded),
+            Ok(commitments)
+        );
+
+        let commitments = vec![[7_u8; 48]];
+        let message = Commitments::from(&commitments[..]);
+        assert_eq!(Vec::<[u8; 48]>::try_from(&message), Ok(commitments));
+        assert!(Vec::<CompressedRistretto>::try_from(&message).is_err());
+    }
+}
@@ -0,0 +1,197 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Python bindings of the compute APIs
+//!
+//! The `python` feature builds a `blitzar` Python extension module with
+//! [`maturin`](https://www.maturin.rs):
+//!
+//! ```text
+//! maturin develop --release --features python,pyo3/extension-module
+//! ```
+//!
+//! ```text
+//! import blitzar, numpy as np
+//!
+//! column = blitzar.Sequence(np.array([1, 2, 3], dtype=np.int32))
+//! [commitment] = blitzar.compute_curve25519_commitments([column])
+//! ```
+//!
+//! Sequences copy the data of the numpy arrays they are created from, so the arrays can
+//! be modified afterwards. Points are exchanged as 32-byte compressed `bytes`, and the
+//! GIL is released while the backend computes.
+
+use crate::{
+    compute::{try_compute_curve25519_commitments, MsmHandle, ScalarWidth},
+    sequence::Sequence,
+};
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use numpy::PyReadonlyArray1;
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+    types::{PyBytes, PyModule},
+};
+
+/// Returns the compressed encodings of `points` as Python `bytes`.
+fn to_py_bytes<'py>(py: Python<'py>, points: &[CompressedRistretto]) -> Vec<Bound<'py, PyBytes>> {
+    points
+        .iter()
+        .map(|p| PyBytes::new(py, p.as_bytes()))
+        .collect()
+}
+
+/// Column of integers to commit to, created from a one-dimensional numpy array
+#[pyclass(name = "Sequence", module = "blitzar", frozen)]
+pub struct PySequence {
+    data: Vec<u8>,
+    element_size: usize,
+    is_signed: bool,
+}
+
+impl PySequence {
+    fn as_sequence(&self) -> Sequence<'_> {
+        Sequence::from_byte_slice(&self.data, self.element_size, self.is_signed)
+    }
+}
+
+macro_rules! try_from_array {
+    ($array:expr, $(($t:ty, $is_signed:expr)),*) => {
+        $(
+            if let Ok(array) = $array.extract::<PyReadonlyArray1<$t>>() {
+                return Ok(PySequence {
+                    data: array.as_array().iter().flat_map(|x| x.to_le_bytes()).collect(),
+                    element_size: std::mem::size_of::<$t>(),
+                    is_signed: $is_signed,
+                });
+            }
+        )*
+    };
+}
+
+#[pymethods]
+impl PySequence {
+    /// Creates a sequence from a one-dimensional numpy array of 8, 16, 32 or 64-bit
+    /// signed or unsigned integers.
+    #[new]
+    fn new(array: &Bound<'_, PyAny>) -> PyResult<Self> {
+        try_from_array!(
+            array,
+            (u8, false),
+            (u16, false),
+            (u32, false),
+            (u64, false),
+            (i8, true),
+            (i16, true),
+            (i32, true),
+            (i64, true)
+        );
+        Err(PyValueError::new_err(
+            "expected a one-dimensional numpy array of integers",
+        ))
+    }
+
+    /// Creates a sequence from little-endian elements of `element_size` bytes each,
+    /// e.g. 32-byte scalars.
+    #[staticmethod]
+    #[pyo3(signature = (data, element_size, is_signed=false))]
+    fn from_bytes(data: &[u8], element_size: usize, is_signed: bool) -> PyResult<Self> {
+        Sequence::try_from_byte_slice_with_size(data, element_size, is_signed)
+            .map_err(|err| PyValueError::new_err(err.to_string()))?;
+        Ok(PySequence {
+            data: data.to_vec(),
+            element_size,
+            is_signed,
+        })
+    }
+
+    fn __len__(&self) -> usize {
+        self.data.len() / self.element_size
+    }
+}
+
+/// Computes the curve25519 commitments of `sequences` using the backend's generators
+/// starting at `offset_generators`.
+///
+/// See [crate::compute::compute_curve25519_commitments].
+#[pyfunction]
+#[pyo3(signature = (sequences, offset_generators=0))]
+fn compute_curve25519_commitments<'py>(
+    py: Python<'py>,
+    sequences: Vec<PyRef<'py, PySequence>>,
+    offset_generators: u64,
+) -> PyResult<Vec<Bound<'py, PyBytes>>> {
+    let data: Vec<Sequence> = sequences.iter().map(|s| s.as_sequence()).collect();
+    let mut commitments = vec![CompressedRistretto::default(); data.len()];
+    py.detach(|| try_compute_curve25519_commitments(&mut commitments, &data, offset_generators))
+        .map_err(|err| PyValueError::new_err(err.to_string()))?;
+    Ok(to_py_bytes(py, &commitments))
+}
+
+/// Handle to compute curve25519 MSMs with pre-specified generators
+///
+/// See [MsmHandle].
+#[pyclass(name = "MsmHandle", module = "blitzar", frozen)]
+pub struct PyMsmHandle(MsmHandle<RistrettoPoint>);
+
+#[pymethods]
+impl PyMsmHandle {
+    /// Creates a handle from generators given as 32-byte compressed points.
+    #[new]
+    fn new(py: Python<'_>, generators: Vec<Vec<u8>>) -> PyResult<Self> {
+        let generators = generators
+            .iter()
+            .map(|bytes| {
+                CompressedRistretto::from_slice(bytes)
+                    .ok()
+                    .and_then(|point| point.decompress())
+                    .ok_or_else(|| PyValueError::new_err("invalid generator"))
+            })
+            .collect::<PyResult<Vec<RistrettoPoint>>>()?;
+        Ok(PyMsmHandle(py.detach(|| MsmHandle::new(&generators))))
+    }
+
+    /// The number of generators the handle was created with.
+    #[getter]
+    fn num_generators(&self) -> usize {
+        self.0.num_generators()
+    }
+
+    /// Computes `num_outputs` MSMs of scalars of `element_num_bytes` bytes each, laid
+    /// out as in [MsmHandle::msm].
+    fn msm<'py>(
+        &self,
+        py: Python<'py>,
+        num_outputs: usize,
+        element_num_bytes: u32,
+        scalars: &[u8],
+    ) -> PyResult<Vec<Bound<'py, PyBytes>>> {
+        let width = ScalarWidth::try_from(element_num_bytes)
+            .map_err(|err| PyValueError::new_err(err.to_string()))?;
+        let mut res = vec![RistrettoPoint::default(); num_outputs];
+        py.detach(|| self.0.try_msm(&mut res, width, scalars))
+            .map_err(|err| PyValueError::new_err(err.to_string()))?;
+        let res: Vec<CompressedRistretto> = res.iter().map(RistrettoPoint::compress).collect();
+        Ok(to_py_bytes(py, &res))
+    }
+}
+
+/// The `blitzar` Python module
+#[pymodule]
+fn blitzar(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PySequence>()?;
+    m.add_class::<PyMsmHandle>()?;
+    m.add_function(wrap_pyfunction!(compute_curve25519_commitments, m)?)?;
+    Ok(())
+}
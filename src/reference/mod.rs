@@ -0,0 +1,237 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! pure-Rust reference implementations of the commitment and MSM computations
+//!
+//! The functions of this module compute the same results as their counterparts in
+//! [crate::compute] without calling the backend, using `curve25519-dalek` and
+//! `arkworks` instead. They are slow and intended for differential testing and fuzzing
+//! on small inputs.
+
+use crate::sequence::Sequence;
+#[cfg(feature = "bls12_381")]
+use ark_bls12_381::G1Affine;
+#[cfg(feature = "bn254")]
+use ark_bn254::G1Affine as bn254_g1_affine;
+use ark_ec::{
+    short_weierstrass::{Affine, Projective, SWCurveConfig},
+    CurveGroup, VariableBaseMSM,
+};
+use ark_ff::PrimeField;
+#[cfg(feature = "grumpkin")]
+use ark_grumpkin::Affine as grumpkin_affine;
+#[cfg(feature = "bls12_381")]
+use ark_serialize::CanonicalSerialize;
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+    traits::{Identity, VartimeMultiscalarMul},
+};
+use std::ops::Neg;
+
+fn scalar_from_le_bytes(bytes: &[u8]) -> Scalar {
+    let mut buf = [0_u8; 32];
+    buf[..bytes.len()].copy_from_slice(bytes);
+    Scalar::from_bytes_mod_order(buf)
+}
+
+/// Converts the elements of `data` to scalars, reading signed elements as two's
+/// complement integers and null elements as zero.
+fn to_scalars<F: Neg<Output = F>>(data: &Sequence, from_le_bytes: impl Fn(&[u8]) -> F) -> Vec<F> {
+    let element_size = data.element_size();
+    data.data_slice()
+        .chunks_exact(element_size)
+        .enumerate()
+        .map(|(i, element)| {
+            if !data.is_valid(i) {
+                return from_le_bytes(&[0]);
+            }
+            if !data.is_signed() || element[element_size - 1] >> 7 == 0 {
+                return from_le_bytes(element);
+            }
+            // negate the two's complement encoding to obtain the magnitude
+            let mut magnitude: Vec<u8> = element.iter().map(|byte| !byte).collect();
+            for byte in magnitude.iter_mut() {
+                let (sum, carry) = byte.overflowing_add(1);
+                *byte = sum;
+                if !carry {
+                    break;
+                }
+            }
+            -from_le_bytes(&magnitude)
+        })
+        .collect()
+}
+
+#[cfg(any(feature = "bls12_381", feature = "bn254", feature = "grumpkin"))]
+fn sw_commitments<C: SWCurveConfig>(data: &[Sequence], generators: &[Affine<C>]) -> Vec<Affine<C>> {
+    data.iter()
+        .map(|sequence| {
+            let scalars = to_scalars(sequence, C::ScalarField::from_le_bytes_mod_order);
+            Projective::<C>::msm(&generators[..scalars.len()], &scalars)
+                .expect("generators and scalars have the same length")
+                .into_affine()
+        })
+        .collect()
+}
+
+/// Reference implementation of
+/// [crate::compute::compute_curve25519_commitments_with_generators].
+///
+/// # Panics
+///
+/// If a sequence is longer than `generators`.
+pub fn compute_curve25519_commitments_with_generators(
+    commitments: &mut [CompressedRistretto],
+    data: &[Sequence],
+    generators: &[RistrettoPoint],
+) {
+    assert_eq!(commitments.len(), data.len());
+    commitments
+        .iter_mut()
+        .zip(data)
+        .for_each(|(commitment, sequence)| {
+            let scalars = to_scalars(sequence, scalar_from_le_bytes);
+            *commitment =
+                RistrettoPoint::vartime_multiscalar_mul(&scalars, &generators[..scalars.len()])
+                    .compress();
+        });
+}
+
+/// Reference implementation of
+/// [crate::compute::compute_bls12_381_g1_commitments_with_generators].
+///
+/// # Panics
+///
+/// If a sequence is longer than `generators`.
+#[cfg(feature = "bls12_381")]
+pub fn compute_bls12_381_g1_commitments_with_generators(
+    commitments: &mut [[u8; 48]],
+    data: &[Sequence],
+    generators: &[G1Affine],
+) {
+    assert_eq!(commitments.len(), data.len());
+    commitments
+        .iter_mut()
+        .zip(sw_commitments(data, generators))
+        .for_each(|(commitment, res)| {
+            res.serialize_compressed(&mut commitment[..])
+                .expect("a compressed bls12-381 G1 point is 48 bytes")
+        });
+}
+
+/// Reference implementation of
+/// [crate::compute::compute_bn254_g1_uncompressed_commitments_with_generators].
+///
+/// # Panics
+///
+/// If a sequence is longer than `generators`.
+#[cfg(feature = "bn254")]
+pub fn compute_bn254_g1_uncompressed_commitments_with_generators(
+    commitments: &mut [bn254_g1_affine],
+    data: &[Sequence],
+    generators: &[bn254_g1_affine],
+) {
+    assert_eq!(commitments.len(), data.len());
+    commitments.copy_from_slice(&sw_commitments(data, generators));
+}
+
+/// Reference implementation of
+/// [crate::compute::compute_grumpkin_uncompressed_commitments_with_generators].
+///
+/// # Panics
+///
+/// If a sequence is longer than `generators`.
+#[cfg(feature = "grumpkin")]
+pub fn compute_grumpkin_uncompressed_commitments_with_generators(
+    commitments: &mut [grumpkin_affine],
+    data: &[Sequence],
+    generators: &[grumpkin_affine],
+) {
+    assert_eq!(commitments.len(), data.len());
+    commitments.copy_from_slice(&sw_commitments(data, generators));
+}
+
+/// Reads the scalars of output `j` from the `msm` layout
+/// `[s_11, s_21, ..., s_m1, s_12, ..., s_mn]`.
+fn msm_scalars<F>(
+    num_outputs: usize,
+    element_num_bytes: u32,
+    scalars: &[u8],
+    j: usize,
+    from_le_bytes: impl Fn(&[u8]) -> F,
+) -> Vec<F> {
+    let element_num_bytes = element_num_bytes as usize;
+    scalars
+        .chunks_exact(num_outputs * element_num_bytes)
+        .map(|row| from_le_bytes(&row[j * element_num_bytes..(j + 1) * element_num_bytes]))
+        .collect()
+}
+
+/// Reference implementation of [crate::compute::MsmHandle::msm] for `curve25519`.
+///
+/// # Panics
+///
+/// If `scalars` holds more rows than `generators`.
+pub fn curve25519_msm(
+    res: &mut [RistrettoPoint],
+    element_num_bytes: u32,
+    scalars: &[u8],
+    generators: &[RistrettoPoint],
+) {
+    let num_outputs = res.len();
+    res.iter_mut().enumerate().for_each(|(j, resj)| {
+        let scalars = msm_scalars(
+            num_outputs,
+            element_num_bytes,
+            scalars,
+            j,
+            scalar_from_le_bytes,
+        );
+        *resj = if scalars.is_empty() {
+            RistrettoPoint::identity()
+        } else {
+            RistrettoPoint::vartime_multiscalar_mul(&scalars, &generators[..scalars.len()])
+        };
+    });
+}
+
+/// Reference implementation of [crate::compute::SwMsmHandle::affine_msm] for short
+/// Weierstrass curves.
+///
+/// # Panics
+///
+/// If `scalars` holds more rows than `generators`.
+pub fn sw_msm<C: SWCurveConfig>(
+    res: &mut [Affine<C>],
+    element_num_bytes: u32,
+    scalars: &[u8],
+    generators: &[Affine<C>],
+) {
+    let num_outputs = res.len();
+    res.iter_mut().enumerate().for_each(|(j, resj)| {
+        let scalars = msm_scalars(
+            num_outputs,
+            element_num_bytes,
+            scalars,
+            j,
+            C::ScalarField::from_le_bytes_mod_order,
+        );
+        *resj = Projective::<C>::msm(&generators[..scalars.len()], &scalars)
+            .expect("generators and scalars have the same length")
+            .into_affine();
+    });
+}
+
+#[cfg(all(test, feature = "ffi"))]
+mod test;
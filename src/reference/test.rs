@@ -0,0 +1,123 @@
+use super::*;
+use crate::compute;
+#[cfg(feature = "bls12_381")]
+use crate::compute::{MsmHandle, SwMsmHandle};
+#[cfg(any(feature = "bls12_381", feature = "bn254", feature = "grumpkin"))]
+use ark_std::UniformRand;
+use rand_core::OsRng;
+
+#[test]
+fn reference_curve25519_commitments_match_the_backend() {
+    let data_a: Vec<u64> = vec![2, 3, 1, 5, 4, 7, 6, 8, 9, 10];
+    let data_b: Vec<i32> = vec![-3, 1, i32::MIN, 7];
+    let data_c: Vec<i128> = vec![i128::MIN, -1, i128::MAX];
+    let data_d: Vec<Scalar> = vec![Scalar::from(5_u64), -Scalar::from(5_u64)];
+    let data: Vec<Sequence> = vec![
+        (&data_a).into(),
+        (&data_b).into(),
+        (&data_c).into(),
+        (&data_d).into(),
+    ];
+
+    let mut rng = OsRng;
+    let generators: Vec<RistrettoPoint> =
+        (0..10).map(|_| RistrettoPoint::random(&mut rng)).collect();
+
+    let mut commitments = vec![CompressedRistretto::default(); data.len()];
+    compute_curve25519_commitments_with_generators(&mut commitments, &data, &generators);
+
+    let mut expected = vec![CompressedRistretto::default(); data.len()];
+    compute::compute_curve25519_commitments_with_generators(&mut expected, &data, &generators);
+
+    assert_eq!(commitments, expected);
+}
+
+#[test]
+#[cfg(feature = "bls12_381")]
+fn reference_bls12_381_g1_commitments_match_the_backend() {
+    let data_a: Vec<u64> = vec![2, 3, 1, 5, 4, 7, 6, 8, 9, 10];
+    let data_b: Vec<i16> = vec![-3, 1, i16::MIN, 7];
+    let data: Vec<Sequence> = vec![(&data_a).into(), (&data_b).into()];
+
+    let mut rng = ark_std::test_rng();
+    let generators: Vec<G1Affine> = (0..10).map(|_| G1Affine::rand(&mut rng)).collect();
+
+    let mut commitments = vec![[0_u8; 48]; data.len()];
+    compute_bls12_381_g1_commitments_with_generators(&mut commitments, &data, &generators);
+
+    let mut expected = vec![[0_u8; 48]; data.len()];
+    compute::compute_bls12_381_g1_commitments_with_generators(&mut expected, &data, &generators);
+
+    assert_eq!(commitments, expected);
+}
+
+#[test]
+#[cfg(feature = "bn254")]
+fn reference_bn254_g1_commitments_match_the_backend() {
+    let data_a: Vec<u64> = vec![2, 3, 1, 5, 4, 7, 6, 8, 9, 10];
+    let data_b: Vec<i64> = vec![-3, 1, i64::MIN, 7];
+    let data: Vec<Sequence> = vec![(&data_a).into(), (&data_b).into()];
+
+    let mut rng = ark_std::test_rng();
+    let generators: Vec<bn254_g1_affine> =
+        (0..10).map(|_| bn254_g1_affine::rand(&mut rng)).collect();
+
+    let mut commitments = vec![bn254_g1_affine::default(); data.len()];
+    compute_bn254_g1_uncompressed_commitments_with_generators(&mut commitments, &data, &generators);
+
+    let mut expected = vec![bn254_g1_affine::default(); data.len()];
+    compute::compute_bn254_g1_uncompressed_commitments_with_generators(
+        &mut expected,
+        &data,
+        &generators,
+    );
+
+    assert_eq!(commitments, expected);
+}
+
+#[test]
+#[cfg(feature = "grumpkin")]
+fn reference_grumpkin_commitments_match_the_backend() {
+    let data_a: Vec<u64> = vec![2, 3, 1, 5, 4, 7, 6, 8, 9, 10];
+    let data_b: Vec<i8> = vec![-3, 1, i8::MIN, 7];
+    let data: Vec<Sequence> = vec![(&data_a).into(), (&data_b).into()];
+
+    let mut rng = ark_std::test_rng();
+    let generators: Vec<grumpkin_affine> =
+        (0..10).map(|_| grumpkin_affine::rand(&mut rng)).collect();
+
+    let mut commitments = vec![grumpkin_affine::default(); data.len()];
+    compute_grumpkin_uncompressed_commitments_with_generators(&mut commitments, &data, &generators);
+
+    let mut expected = vec![grumpkin_affine::default(); data.len()];
+    compute::compute_grumpkin_uncompressed_commitments_with_generators(
+        &mut expected,
+        &data,
+        &generators,
+    );
+
+    assert_eq!(commitments, expected);
+}
+
+#[test]
+#[cfg(feature = "bls12_381")]
+fn reference_msms_match_the_backend() {
+    let scalars: Vec<u8> = (0..24).collect();
+
+    let mut rng = OsRng;
+    let generators: Vec<RistrettoPoint> =
+        (0..4).map(|_| RistrettoPoint::random(&mut rng)).collect();
+    let mut res = vec![RistrettoPoint::default(); 3];
+    curve25519_msm(&mut res, 2, &scalars, &generators);
+    let mut expected = vec![RistrettoPoint::default(); 3];
+    MsmHandle::new(&generators).msm(&mut expected, 2, &scalars);
+    assert_eq!(res, expected);
+
+    let mut rng = ark_std::test_rng();
+    let generators: Vec<G1Affine> = (0..4).map(|_| G1Affine::rand(&mut rng)).collect();
+    let mut res = vec![G1Affine::default(); 3];
+    sw_msm(&mut res, 2, &scalars, &generators);
+    let mut expected = vec![G1Affine::default(); 3];
+    MsmHandle::new_with_affine(&generators).affine_msm(&mut expected, 2, &scalars);
+    assert_eq!(res, expected);
+}
@@ -0,0 +1,62 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::{SequenceError, MAX_UNSIGNED_ELEMENT_SIZE};
+use num_bigint::BigUint;
+
+/// Converts `values` into fixed width little-endian elements of `N` bytes, zero padded.
+///
+/// The result can be converted to an unsigned [super::Sequence] with `From`, e.g.
+///
+/// ```
+/// use blitzar::sequence::{biguints_to_fixed_bytes, Sequence};
+/// use num_bigint::BigUint;
+///
+/// let values = vec![BigUint::from(3_u8), BigUint::from(u128::MAX) + 1_u8];
+/// let elements = biguints_to_fixed_bytes::<32>(&values).unwrap();
+/// let sequence = Sequence::from(&elements);
+/// assert_eq!(sequence.len(), 2);
+/// ```
+///
+/// `N` is checked at compile time to be between `1` and `32`.
+///
+/// # Errors
+///
+/// If a value needs more than `8 * N` bits.
+pub fn biguints_to_fixed_bytes<const N: usize>(
+    values: &[BigUint],
+) -> Result<Vec<[u8; N]>, SequenceError> {
+    const {
+        assert!(
+            N > 0 && N <= MAX_UNSIGNED_ELEMENT_SIZE,
+            "element size must be between 1 and 32 bytes"
+        )
+    };
+    values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            if value.bits() > 8 * N as u64 {
+                return Err(SequenceError::ValueTooLarge {
+                    index,
+                    num_bits: value.bits(),
+                    max_bits: 8 * N,
+                });
+            }
+            let bytes = value.to_bytes_le();
+            let mut element = [0_u8; N];
+            element[..bytes.len()].copy_from_slice(&bytes);
+            Ok(element)
+        })
+        .collect()
+}
@@ -0,0 +1,75 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use thiserror::Error;
+
+/// SequenceError related to invalid sequence descriptors
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SequenceError {
+    /// This error occurs when the element size is zero.
+    #[error("Element size must be at least 1 byte")]
+    ZeroElementSize,
+
+    /// This error occurs when the element size exceeds the maximum supported by the backend.
+    #[error(
+        "Element size of {element_size} bytes exceeds the maximum of {max_element_size} bytes"
+    )]
+    ElementSizeTooLarge {
+        /// The element size of the sequence
+        element_size: usize,
+        /// The maximum element size, which is 16 for signed and 32 for unsigned sequences
+        max_element_size: usize,
+    },
+
+    /// This error occurs when the data length is not a multiple of the element size.
+    #[error("Data length of {num_bytes} bytes is not a multiple of the element size of {element_size} bytes")]
+    LengthNotMultipleOfElementSize {
+        /// The number of bytes of the data
+        num_bytes: usize,
+        /// The element size of the sequence
+        element_size: usize,
+    },
+
+    /// This error occurs when the element size does not evenly split the elements of a typed slice.
+    #[error(
+        "Element size of {element_size} bytes does not divide the type size of {type_size} bytes"
+    )]
+    ElementSizeMismatch {
+        /// The element size of the sequence
+        element_size: usize,
+        /// The size of the type of the slice elements
+        type_size: usize,
+    },
+
+    /// This error occurs when a value does not fit in the element size.
+    #[error(
+        "Value at index {index} needs {num_bits} bits, more than the {max_bits} bits of an element"
+    )]
+    ValueTooLarge {
+        /// The index of the value
+        index: usize,
+        /// The number of bits of the value
+        num_bits: u64,
+        /// The number of bits of an element
+        max_bits: usize,
+    },
+
+    /// This error occurs when the validity bitmap does not have a bit for every element.
+    #[error("Validity bitmap of {num_bytes} bytes is shorter than the {required} bytes required")]
+    ValidityTooShort {
+        /// The number of bytes of the validity bitmap
+        num_bytes: usize,
+        /// The number of bytes needed to hold one bit per element
+        required: usize,
+    },
+}
@@ -0,0 +1,88 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use ark_ff::{BigInt, PrimeField};
+use curve25519_dalek::scalar::Scalar;
+use rayon::prelude::*;
+
+/// Reduces scalars stored as `N` little-endian 64-bit limbs into the scalar field `F`.
+///
+/// Sequences are limited to 32 byte elements, so wider values (e.g. `[u64; 6]`
+/// BLS12-381 scalars kept with headroom) must be reduced before being committed. Each
+/// value is read as an unsigned `64 * N` bit integer `v` and replaced by the canonical
+/// representative of `v mod r`, where `r` is the modulus of `F`. The result can be
+/// converted to a [super::Sequence] with `From`.
+///
+/// This is the same reduction the backend applies implicitly to 32 byte values larger
+/// than `r`, since `v * G = (v mod r) * G` for any generator `G` of order `r`, so
+/// commitments are unchanged by reducing beforehand.
+pub fn reduce_limbs<F, const N: usize>(data: &[[u64; N]]) -> Vec<[u64; 4]>
+where
+    F: PrimeField<BigInt = BigInt<4>>,
+{
+    data.par_iter()
+        .map(|limbs| {
+            let bytes: Vec<u8> = limbs.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+            F::from_le_bytes_mod_order(&bytes).into_bigint().0
+        })
+        .collect()
+}
+
+/// Reduces scalars stored as `N` little-endian 64-bit limbs modulo the order of the
+/// ristretto255 group.
+///
+/// Each value is read as an unsigned `64 * N` bit integer `v` and replaced by the
+/// canonical representative of `v mod l`, where `l = 2^252 + 27742317777372353535851937790883648493`.
+///
+/// # Panics
+///
+/// If `N` is larger than `8`, i.e. the values are wider than 512 bits.
+pub fn reduce_curve25519_limbs<const N: usize>(data: &[[u64; N]]) -> Vec<Scalar> {
+    assert!(N <= 8, "curve25519 limbs can be at most 512 bits wide");
+    data.par_iter()
+        .map(|limbs| {
+            let mut bytes = [0_u8; 64];
+            bytes
+                .chunks_exact_mut(8)
+                .zip(limbs)
+                .for_each(|(out, limb)| out.copy_from_slice(&limb.to_le_bytes()));
+            Scalar::from_bytes_mod_order_wide(&bytes)
+        })
+        .collect()
+}
+
+/// Reduces unsigned little-endian elements of `element_size` bytes into the canonical
+/// 32 byte encodings of their residues in the scalar field `F`.
+pub(super) fn reduce_wide_bytes<F>(data: &[u8], element_size: usize) -> Vec<u8>
+where
+    F: PrimeField<BigInt = BigInt<4>>,
+{
+    data.par_chunks_exact(element_size)
+        .flat_map_iter(|bytes| {
+            let limbs = F::from_le_bytes_mod_order(bytes).into_bigint().0;
+            limbs.into_iter().flat_map(u64::to_le_bytes)
+        })
+        .collect()
+}
+
+/// Reduces unsigned little-endian elements of at most 64 bytes into the canonical
+/// 32 byte encodings of their residues modulo the order of the ristretto255 group.
+pub(super) fn reduce_curve25519_wide_bytes(data: &[u8], element_size: usize) -> Vec<u8> {
+    data.par_chunks_exact(element_size)
+        .flat_map_iter(|bytes| {
+            let mut wide = [0_u8; 64];
+            wide[..bytes.len()].copy_from_slice(bytes);
+            Scalar::from_bytes_mod_order_wide(&wide).to_bytes()
+        })
+        .collect()
+}
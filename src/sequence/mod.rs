@@ -14,7 +14,101 @@
 
 //! data and scalar field elements for data table
 
+#[cfg(feature = "ffi")]
 use blitzar_sys::sxt_sequence_descriptor;
+use rayon::prelude::*;
+
+mod error;
+pub use error::SequenceError;
+
+#[cfg(feature = "num-bigint")]
+mod biguint;
+#[cfg(feature = "num-bigint")]
+pub use biguint::biguints_to_fixed_bytes;
+
+mod limbs;
+pub use limbs::{reduce_curve25519_limbs, reduce_limbs};
+
+mod owned;
+pub use owned::{Endianness, OwnedSequence, SequenceData};
+
+/// Maximum number of bytes of the elements of a signed sequence.
+const MAX_SIGNED_ELEMENT_SIZE: usize = 16;
+
+/// Maximum number of bytes of the elements of an unsigned sequence.
+const MAX_UNSIGNED_ELEMENT_SIZE: usize = 32;
+
+/// Checks that `num_bytes` of data can be read as elements of `element_size` bytes.
+fn check_parts(
+    num_bytes: usize,
+    element_size: usize,
+    is_signed: bool,
+) -> Result<(), SequenceError> {
+    if element_size == 0 {
+        return Err(SequenceError::ZeroElementSize);
+    }
+    let max_element_size = if is_signed {
+        MAX_SIGNED_ELEMENT_SIZE
+    } else {
+        MAX_UNSIGNED_ELEMENT_SIZE
+    };
+    if element_size > max_element_size {
+        return Err(SequenceError::ElementSizeTooLarge {
+            element_size,
+            max_element_size,
+        });
+    }
+    if num_bytes % element_size != 0 {
+        return Err(SequenceError::LengthNotMultipleOfElementSize {
+            num_bytes,
+            element_size,
+        });
+    }
+    Ok(())
+}
+
+/// Number of bytes of each element of a [Sequence].
+///
+/// Element sizes are always counted in bytes. Use [ElementSize::from_bits] when
+/// starting from a bit width, so that the unit conversion is explicit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ElementSize(usize);
+
+impl ElementSize {
+    /// Creates an element size of `num_bytes` bytes.
+    pub const fn from_bytes(num_bytes: usize) -> Self {
+        Self(num_bytes)
+    }
+
+    /// Creates an element size of `num_bits` bits.
+    ///
+    /// # Panics
+    ///
+    /// If `num_bits` is not a multiple of 8. In a `const` context, this is a compile
+    /// time error.
+    pub const fn from_bits(num_bits: usize) -> Self {
+        assert!(
+            num_bits % 8 == 0,
+            "element size in bits must be a multiple of 8"
+        );
+        Self(num_bits / 8)
+    }
+
+    /// Returns the element size of values of type `T`.
+    pub const fn of<T>() -> Self {
+        Self(core::mem::size_of::<T>())
+    }
+
+    /// Returns the number of bytes of each element.
+    pub const fn bytes(self) -> usize {
+        self.0
+    }
+
+    /// Returns the number of bits of each element.
+    pub const fn bits(self) -> usize {
+        8 * self.0
+    }
+}
 
 /// Stores the slice view of a contiguous column data table.
 ///
@@ -61,6 +155,12 @@ pub struct Sequence<'a> {
     /// Represents whether the data slice should be interpreted
     /// as a sequence of signed or unsigned values.
     is_signed: bool,
+
+    /// Optional validity bitmap, where bit `i % 8` of byte `i / 8`
+    /// is `0` if element `i` is null.
+    ///
+    /// Null elements are committed as zero.
+    validity: Option<&'a [u8]>,
 }
 
 impl<'a> Sequence<'a> {
@@ -74,6 +174,111 @@ impl<'a> Sequence<'a> {
         self.len() == 0
     }
 
+    /// Returns the number of bytes of each element.
+    pub(crate) fn element_size(&self) -> usize {
+        self.element_size
+    }
+
+    /// Returns whether the elements are interpreted as signed values.
+    pub(crate) fn is_signed(&self) -> bool {
+        self.is_signed
+    }
+
+    /// Returns the raw bytes of the sequence.
+    pub(crate) fn data_slice(&self) -> &'a [u8] {
+        self.data_slice
+    }
+
+    /// Attaches a validity bitmap to the sequence, e.g. the one of an Arrow array.
+    ///
+    /// Bit `i % 8` of byte `i / 8` of `validity` is `0` if element `i` is null. Null
+    /// elements are committed as if they were zero, without copying the data: the
+    /// commitment functions subtract the contribution of the null elements instead,
+    /// so this is most efficient when nulls are rare.
+    ///
+    /// # Panics
+    ///
+    /// If `validity` has fewer than `ceil(len / 8)` bytes.
+    pub fn with_validity(self, validity: &'a [u8]) -> Self {
+        let sequence = Sequence {
+            validity: Some(validity),
+            ..self
+        };
+        sequence.validate().unwrap_or_else(|err| panic!("{err}"));
+        sequence
+    }
+
+    /// Returns the validity bitmap of the sequence, if any.
+    pub fn validity(&self) -> Option<&'a [u8]> {
+        self.validity
+    }
+
+    /// Returns `false` if element `i` is null.
+    pub(crate) fn is_valid(&self, i: usize) -> bool {
+        match self.validity {
+            Some(validity) => (validity[i / 8] >> (i % 8)) & 1 == 1,
+            None => true,
+        }
+    }
+
+    /// Returns the indices of the null elements.
+    #[cfg_attr(not(feature = "ffi"), allow(dead_code))]
+    pub(crate) fn null_indices(&self) -> Vec<usize> {
+        match self.validity {
+            Some(_) => (0..self.len()).filter(|i| !self.is_valid(*i)).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the elements `start..end` of the sequence, clamped to its length.
+    ///
+    /// `start` must be a multiple of `8` so that the validity bitmap stays aligned.
+    #[cfg_attr(not(feature = "ffi"), allow(dead_code))]
+    pub(crate) fn rows(&self, start: usize, end: usize) -> Sequence<'a> {
+        debug_assert_eq!(start % 8, 0);
+        let end = end.min(self.len());
+        let start = start.min(end);
+        Sequence {
+            data_slice: &self.data_slice[start * self.element_size..end * self.element_size],
+            validity: self.validity.map(|validity| &validity[start / 8..]),
+            ..*self
+        }
+    }
+
+    /// Returns the smallest number of bits that can represent every element of the
+    /// sequence when read as an unsigned little-endian integer.
+    ///
+    /// Note that signed sequences holding negative values always require the full
+    /// width of `8 * element_size` bits, since their two's complement encoding has the
+    /// top bit set.
+    pub fn bit_width(&self) -> u32 {
+        let element_size = self.element_size;
+        let bytes = self
+            .data_slice
+            .par_chunks(element_size * 1024)
+            .map(|chunk| {
+                let mut bytes = [0_u8; 32];
+                chunk.chunks_exact(element_size).for_each(|element| {
+                    bytes
+                        .iter_mut()
+                        .zip(element)
+                        .for_each(|(acc, byte)| *acc |= byte)
+                });
+                bytes
+            })
+            .reduce(
+                || [0_u8; 32],
+                |mut lhs, rhs| {
+                    lhs.iter_mut().zip(rhs).for_each(|(acc, byte)| *acc |= byte);
+                    lhs
+                },
+            );
+        bytes
+            .iter()
+            .rposition(|byte| *byte != 0)
+            .map_or(0, |i| 8 * i as u32 + 8 - bytes[i].leading_zeros())
+    }
+
     /// Converts a slice of any type to a Sequence by calling `from_raw_parts` on it.
     /// The `is_signed` parameter is used to determine whether the data is interpreted as a signed value or not.
     /// Several types are also supported via the `From` trait, which is preferred over this method.
@@ -82,39 +287,201 @@ impl<'a> Sequence<'a> {
     /// and between `1` and `32` bytes (inclusive) if `is_signed` is `false`.
     pub fn from_raw_parts<T>(slice: &'a [T], is_signed: bool) -> Self {
         let element_size = core::mem::size_of::<T>();
-        Self::from_raw_parts_with_size(slice, element_size, is_signed)
+        Self::try_from_raw_parts_with_size(slice, element_size, is_signed)
+            .unwrap_or_else(|err| panic!("{err}"))
     }
 
     /// Converts a slice of any type to a Sequence by calling `from_raw_parts` on it.
     ///
+    /// Prefer the typed constructors such as [Sequence::from_u64s],
+    /// [Sequence::from_scalars] or [Sequence::from_fixed_bytes] for common cases, since
+    /// they cannot get the element size wrong.
+    ///
     /// The `is_signed` parameter is used to determine whether the data is interpreted as a signed value or not.
-    /// The `element_size` parameter specifies the size of each element in bytes.
+    /// The `element_size` parameter specifies the size of each element in bytes. Unless `T`
+    /// is a single byte, it must divide `size_of::<T>()`, so that no element straddles two
+    /// values of the slice. Use [Sequence::from_byte_slice] for raw bytes.
+    ///
+    /// # Panics
+    ///
+    /// In the same cases as [Sequence::try_from_raw_parts_with_size] returns an error.
+    #[deprecated(note = "use from_u64s/from_fixed_bytes/try_from_raw_parts_with_size")]
     pub fn from_raw_parts_with_size<T>(
         slice: &'a [T],
         element_size: usize,
         is_signed: bool,
     ) -> Self {
-        assert!(element_size > 0);
-        if is_signed {
-            assert!(element_size <= 16);
-        } else {
-            assert!(element_size <= 32);
-        }
+        Self::try_from_raw_parts_with_size(slice, element_size, is_signed)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Fallible version of [Sequence::from_raw_parts_with_size].
+    ///
+    /// Returns an error instead of panicking if `element_size` is out of bounds, if it
+    /// does not divide `size_of::<T>()` while `T` is larger than a byte, or if the number
+    /// of bytes of `slice` is not a multiple of `element_size`.
+    pub fn try_from_raw_parts_with_size<T>(
+        slice: &'a [T],
+        element_size: usize,
+        is_signed: bool,
+    ) -> Result<Self, SequenceError> {
         let len = std::mem::size_of_val(slice);
-        assert_eq!(
-            len % element_size,
-            0,
-            "raw data length should be a multiple of element size"
-        );
+        check_parts(len, element_size, is_signed)?;
+        let type_size = std::mem::size_of::<T>();
+        if type_size > 1 && type_size % element_size != 0 {
+            return Err(SequenceError::ElementSizeMismatch {
+                element_size,
+                type_size,
+            });
+        }
         let data_slice = unsafe { core::slice::from_raw_parts(slice.as_ptr() as *const u8, len) };
-        Sequence {
-            data_slice,
+        Self::try_from_byte_slice_with_size(data_slice, element_size, is_signed)
+    }
+
+    /// Creates an unsigned Sequence from a slice of `u64` values.
+    pub fn from_u64s(data: &'a [u64]) -> Self {
+        Self::from_raw_parts(data, false)
+    }
+
+    /// Creates an unsigned Sequence from a slice of `u128` values.
+    pub fn from_u128s(data: &'a [u128]) -> Self {
+        Self::from_raw_parts(data, false)
+    }
+
+    /// Creates a Sequence from a slice of curve25519 scalars, each read as a 32 byte
+    /// unsigned value.
+    pub fn from_scalars(data: &'a [curve25519_dalek::scalar::Scalar]) -> Self {
+        Self::from_raw_parts(data, false)
+    }
+
+    /// Creates an unsigned Sequence from raw bytes holding elements of `N` bytes each.
+    ///
+    /// `N` is checked at compile time to be between `1` and `32`.
+    ///
+    /// # Panics
+    ///
+    /// If `data.len()` is not a multiple of `N`.
+    pub fn from_fixed_bytes<const N: usize>(data: &'a [u8]) -> Self {
+        const {
+            assert!(
+                N > 0 && N <= MAX_UNSIGNED_ELEMENT_SIZE,
+                "element size must be between 1 and 32 bytes"
+            )
+        };
+        Self::from_byte_slice(data, N, false)
+    }
+
+    /// Creates a Sequence from raw bytes holding elements of `element_size` each.
+    ///
+    /// Returns an error in the same cases as [Sequence::try_from_byte_slice_with_size].
+    pub fn try_from_bytes(
+        data: &'a [u8],
+        element_size: ElementSize,
+        is_signed: bool,
+    ) -> Result<Self, SequenceError> {
+        Self::try_from_byte_slice_with_size(data, element_size.bytes(), is_signed)
+    }
+
+    /// Creates a Sequence from raw bytes holding elements of `element_size` bytes each.
+    ///
+    /// Unlike [Sequence::try_from_raw_parts_with_size], the elements may have any size
+    /// supported by the backend, since a byte slice has no element boundaries of its own.
+    ///
+    /// Returns an error if `element_size` is out of bounds or if `data.len()` is not a
+    /// multiple of `element_size`.
+    pub fn try_from_byte_slice_with_size(
+        data: &'a [u8],
+        element_size: usize,
+        is_signed: bool,
+    ) -> Result<Self, SequenceError> {
+        check_parts(data.len(), element_size, is_signed)?;
+        Ok(Sequence {
+            data_slice: data,
             element_size,
             is_signed,
+            validity: None,
+        })
+    }
+
+    /// Creates a Sequence from raw bytes holding elements of `element_size` bytes each.
+    ///
+    /// This is the safe counterpart of [Sequence::from_raw_pointer] for callers that
+    /// already hold the data as a byte slice.
+    ///
+    /// # Panics
+    ///
+    /// In the same cases as [Sequence::try_from_byte_slice_with_size] returns an error.
+    pub fn from_byte_slice(data: &'a [u8], element_size: usize, is_signed: bool) -> Self {
+        Self::try_from_byte_slice_with_size(data, element_size, is_signed)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Creates a Sequence from a pointer to `num_elements` elements of `element_size`
+    /// bytes each, e.g. a buffer handed over through FFI.
+    ///
+    /// A null `ptr` is accepted when `num_elements` is zero.
+    ///
+    /// # Safety
+    ///
+    /// Unless `num_elements` is zero, `ptr` must be non-null and valid for reads of
+    /// `num_elements * element_size` bytes, and the memory must not be mutated or freed
+    /// for the lifetime `'a` of the returned Sequence.
+    ///
+    /// # Panics
+    ///
+    /// If `num_elements * element_size` overflows, or in the same cases as
+    /// [Sequence::from_byte_slice].
+    pub unsafe fn from_raw_pointer(
+        ptr: *const u8,
+        num_elements: usize,
+        element_size: usize,
+        is_signed: bool,
+    ) -> Self {
+        let num_bytes = num_elements
+            .checked_mul(element_size)
+            .expect("sequence size in bytes overflows usize");
+        let data: &'a [u8] = if num_bytes == 0 {
+            &[]
+        } else {
+            core::slice::from_raw_parts(ptr, num_bytes)
+        };
+        Self::from_byte_slice(data, element_size, is_signed)
+    }
+
+    /// Checks that the sequence is a valid descriptor for the backend.
+    pub fn validate(&self) -> Result<(), SequenceError> {
+        check_parts(self.data_slice.len(), self.element_size, self.is_signed)?;
+        if let Some(validity) = self.validity {
+            let required = self.len().div_ceil(8);
+            if validity.len() < required {
+                return Err(SequenceError::ValidityTooShort {
+                    num_bytes: validity.len(),
+                    required,
+                });
+            }
         }
+        Ok(())
     }
 }
 
+/// Checks that every sequence of `data` is a valid descriptor for the backend.
+///
+/// This performs the same checks as the `Sequence` constructors without calling the
+/// backend, so it can be shared by fuzzers and production code.
+pub fn validate(data: &[Sequence]) -> Result<(), SequenceError> {
+    data.iter().try_for_each(Sequence::validate)
+}
+
+/// Returns the bit width of each sequence, as computed by [Sequence::bit_width].
+///
+/// The result can be used as a tight `output_bit_table` for the packed MSM functions
+/// of `crate::compute::MsmHandle`, so that narrow columns are committed using only the
+/// bits they need.
+pub fn analyze_bit_widths(data: &[Sequence]) -> Vec<u32> {
+    data.par_iter().map(Sequence::bit_width).collect()
+}
+
+#[cfg(feature = "ffi")]
 impl From<&Sequence<'_>> for sxt_sequence_descriptor {
     fn from(other: &Sequence<'_>) -> Self {
         sxt_sequence_descriptor {
@@ -0,0 +1,323 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::{
+    check_parts,
+    limbs::{reduce_curve25519_wide_bytes, reduce_wide_bytes},
+    Sequence, SequenceError,
+};
+use ark_ff::{BigInt, PrimeField};
+use core::ops::Deref;
+use rayon::prelude::*;
+
+/// Maximum number of bytes of the elements reduced by [OwnedSequence::try_reduce_wide].
+const MAX_WIDE_ELEMENT_SIZE: usize = 64;
+
+/// Checks that `num_bytes` of data can be read as wide elements of `element_size` bytes.
+fn check_wide_parts(num_bytes: usize, element_size: usize) -> Result<(), SequenceError> {
+    if element_size == 0 {
+        return Err(SequenceError::ZeroElementSize);
+    }
+    if element_size > MAX_WIDE_ELEMENT_SIZE {
+        return Err(SequenceError::ElementSizeTooLarge {
+            element_size,
+            max_element_size: MAX_WIDE_ELEMENT_SIZE,
+        });
+    }
+    if num_bytes % element_size != 0 {
+        return Err(SequenceError::LengthNotMultipleOfElementSize {
+            num_bytes,
+            element_size,
+        });
+    }
+    Ok(())
+}
+
+/// Byte order of the elements of raw sequence data.
+///
+/// The backend reads little-endian elements, so big-endian data is byte-swapped on the
+/// host by [OwnedSequence::try_new_with_endianness].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Endianness {
+    /// The least significant byte of each element comes first.
+    #[default]
+    Little,
+    /// The most significant byte of each element comes first, e.g. network byte order.
+    Big,
+}
+
+/// Bytes backing an [OwnedSequence], either borrowed or owned, much like a `Cow<[u8]>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SequenceData<'a> {
+    /// Bytes borrowed from the caller.
+    Borrowed(&'a [u8]),
+    /// Bytes owned by the sequence.
+    Owned(Vec<u8>),
+}
+
+impl SequenceData<'_> {
+    /// Copies borrowed bytes, so that the data no longer borrows from the caller.
+    fn into_owned(self) -> SequenceData<'static> {
+        match self {
+            SequenceData::Borrowed(data) => SequenceData::Owned(data.to_vec()),
+            SequenceData::Owned(data) => SequenceData::Owned(data),
+        }
+    }
+}
+
+impl Deref for SequenceData<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            SequenceData::Borrowed(data) => data,
+            SequenceData::Owned(data) => data,
+        }
+    }
+}
+
+/// A [Sequence] that can either borrow or own its data.
+///
+/// Some conversions cannot hand out a view of the caller's memory, e.g. field elements
+/// stored in Montgomery form must first be re-encoded into their canonical bytes. An
+/// `OwnedSequence` keeps such bytes alive for as long as it is needed, which also makes it
+/// suitable to be moved into async tasks once converted with [OwnedSequence::into_owned].
+///
+/// Use [OwnedSequence::as_sequence] to obtain the `Sequence` view passed to the commitment
+/// functions.
+#[derive(Clone, Debug)]
+pub struct OwnedSequence<'a> {
+    data: SequenceData<'a>,
+    element_size: usize,
+    is_signed: bool,
+    validity: Option<SequenceData<'a>>,
+}
+
+impl<'a> OwnedSequence<'a> {
+    /// Creates a sequence from `data` holding elements of `element_size` bytes each.
+    ///
+    /// # Errors
+    ///
+    /// In the same cases as [Sequence::try_from_byte_slice_with_size].
+    pub fn try_new(
+        data: SequenceData<'a>,
+        element_size: usize,
+        is_signed: bool,
+    ) -> Result<Self, SequenceError> {
+        check_parts(data.len(), element_size, is_signed)?;
+        Ok(OwnedSequence {
+            data,
+            element_size,
+            is_signed,
+            validity: None,
+        })
+    }
+
+    /// Creates a sequence from `data` holding elements of `element_size` bytes each,
+    /// encoded with the given `endianness`.
+    ///
+    /// Little-endian data is kept as is. Big-endian data is byte-swapped element by
+    /// element in parallel, in place if it is owned and into a new buffer otherwise.
+    ///
+    /// # Errors
+    ///
+    /// In the same cases as [OwnedSequence::try_new].
+    pub fn try_new_with_endianness(
+        data: SequenceData<'a>,
+        element_size: usize,
+        is_signed: bool,
+        endianness: Endianness,
+    ) -> Result<Self, SequenceError> {
+        check_parts(data.len(), element_size, is_signed)?;
+        let data = match (endianness, data) {
+            (Endianness::Little, data) => data,
+            (Endianness::Big, SequenceData::Borrowed(data)) => {
+                let mut swapped = data.to_vec();
+                swap_elements(&mut swapped, element_size);
+                SequenceData::Owned(swapped)
+            }
+            (Endianness::Big, SequenceData::Owned(mut data)) => {
+                swap_elements(&mut data, element_size);
+                SequenceData::Owned(data)
+            }
+        };
+        Ok(OwnedSequence {
+            data,
+            element_size,
+            is_signed,
+            validity: None,
+        })
+    }
+
+    /// Returns the number of elements in the sequence.
+    pub fn len(&self) -> usize {
+        self.data.len() / self.element_size
+    }
+
+    /// Returns true if the sequence has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if the sequence owns its data.
+    pub fn is_owned(&self) -> bool {
+        matches!(self.data, SequenceData::Owned(_))
+    }
+
+    /// Returns the bytes backing the sequence.
+    pub fn data(&self) -> &SequenceData<'a> {
+        &self.data
+    }
+
+    /// Returns the validity bitmap of the sequence, if any.
+    ///
+    /// Bit `i % 8` of byte `i / 8` is `0` if element `i` is null, as in
+    /// [Sequence::with_validity].
+    pub fn validity(&self) -> Option<&[u8]> {
+        self.validity.as_deref()
+    }
+
+    /// Returns a `Sequence` view of the data.
+    pub fn as_sequence(&self) -> Sequence<'_> {
+        let sequence = Sequence::from_byte_slice(&self.data, self.element_size, self.is_signed);
+        match &self.validity {
+            Some(validity) => sequence.with_validity(validity),
+            None => sequence,
+        }
+    }
+
+    /// Copies borrowed data and validity bitmap, so that the sequence no longer borrows
+    /// from the caller.
+    pub fn into_owned(self) -> OwnedSequence<'static> {
+        OwnedSequence {
+            data: self.data.into_owned(),
+            element_size: self.element_size,
+            is_signed: self.is_signed,
+            validity: self.validity.map(SequenceData::into_owned),
+        }
+    }
+}
+
+/// Reverses the bytes of each element of `element_size` bytes of `data`.
+fn swap_elements(data: &mut [u8], element_size: usize) {
+    data.par_chunks_exact_mut(element_size)
+        .for_each(|element| element.reverse());
+}
+
+impl OwnedSequence<'static> {
+    /// Reduces unsigned little-endian elements of up to 64 bytes, e.g. hash outputs,
+    /// into the scalar field `F` of a short Weierstrass curve.
+    ///
+    /// The backend only reads elements of up to 32 bytes, so wider elements are reduced
+    /// on the host: each element is read as an unsigned integer `v` and replaced by the
+    /// canonical 32 byte encoding of `v mod r`, where `r` is the modulus of `F`, e.g.
+    /// `ark_bn254::Fr` or `ark_bls12_381::Fr`. Commitments are unchanged by the reduction,
+    /// since `v * G = (v mod r) * G` for any generator `G` of order `r`.
+    ///
+    /// # Errors
+    ///
+    /// If `element_size` is zero or larger than `64`, or if `data.len()` is not a multiple
+    /// of `element_size`.
+    pub fn try_reduce_wide<F>(data: &[u8], element_size: usize) -> Result<Self, SequenceError>
+    where
+        F: PrimeField<BigInt = BigInt<4>>,
+    {
+        check_wide_parts(data.len(), element_size)?;
+        let data = reduce_wide_bytes::<F>(data, element_size);
+        OwnedSequence::try_new(SequenceData::Owned(data), 32, false)
+    }
+
+    /// Reduces unsigned little-endian elements of up to 64 bytes modulo the order `l` of
+    /// the ristretto255 group.
+    ///
+    /// This is the same as [OwnedSequence::try_reduce_wide] for the curve25519 commitments,
+    /// with `v` replaced by the canonical encoding of `v mod l`, as computed by
+    /// `Scalar::from_bytes_mod_order_wide`.
+    ///
+    /// # Errors
+    ///
+    /// In the same cases as [OwnedSequence::try_reduce_wide].
+    pub fn try_reduce_wide_curve25519(
+        data: &[u8],
+        element_size: usize,
+    ) -> Result<Self, SequenceError> {
+        check_wide_parts(data.len(), element_size)?;
+        let data = reduce_curve25519_wide_bytes(data, element_size);
+        OwnedSequence::try_new(SequenceData::Owned(data), 32, false)
+    }
+}
+
+impl<'a> From<&'a OwnedSequence<'_>> for Sequence<'a> {
+    fn from(other: &'a OwnedSequence<'_>) -> Self {
+        other.as_sequence()
+    }
+}
+
+impl<'a> From<Sequence<'a>> for OwnedSequence<'a> {
+    /// Borrows the data and the validity bitmap of `other`.
+    fn from(other: Sequence<'a>) -> Self {
+        OwnedSequence {
+            data: SequenceData::Borrowed(other.data_slice()),
+            element_size: other.element_size(),
+            is_signed: other.is_signed(),
+            validity: other.validity().map(SequenceData::Borrowed),
+        }
+    }
+}
+
+impl<'a, T> From<&'a [T]> for OwnedSequence<'a>
+where
+    Sequence<'a>: From<&'a [T]>,
+{
+    fn from(other: &'a [T]) -> Self {
+        Sequence::from(other).into()
+    }
+}
+
+impl<'a, T> From<&'a Vec<T>> for OwnedSequence<'a>
+where
+    Sequence<'a>: From<&'a [T]>,
+{
+    fn from(other: &'a Vec<T>) -> Self {
+        other.as_slice().into()
+    }
+}
+
+impl<T> From<Vec<T>> for OwnedSequence<'static>
+where
+    for<'b> Sequence<'b>: From<&'b [T]>,
+{
+    /// Takes over the elements of `other`, copying them into a byte buffer.
+    fn from(other: Vec<T>) -> Self {
+        OwnedSequence::from(other.as_slice()).into_owned()
+    }
+}
+
+#[cfg(feature = "arkworks")]
+impl<P: ark_ff::FpConfig<N>, const N: usize> From<&[ark_ff::Fp<P, N>]> for OwnedSequence<'static> {
+    /// Re-encodes the field elements from Montgomery form into their canonical
+    /// little-endian bytes.
+    ///
+    /// # Panics
+    ///
+    /// If the field elements are wider than 32 bytes.
+    fn from(other: &[ark_ff::Fp<P, N>]) -> Self {
+        use ark_ff::{BigInteger, PrimeField};
+        let data = other
+            .iter()
+            .flat_map(|x| x.into_bigint().to_bytes_le())
+            .collect();
+        OwnedSequence::try_new(SequenceData::Owned(data), 8 * N, false)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+}
@@ -1,4 +1,17 @@
-use super::Sequence;
+#[cfg(feature = "bls12_381")]
+use super::reduce_limbs;
+use super::{
+    analyze_bit_widths, reduce_curve25519_limbs, validate, ElementSize, Endianness, OwnedSequence,
+    Sequence, SequenceData, SequenceError,
+};
+#[cfg(any(
+    feature = "bls12_381",
+    feature = "bn254",
+    all(feature = "arkworks", feature = "grumpkin")
+))]
+use ark_ff::PrimeField;
+#[cfg(feature = "bls12_381")]
+use ark_ff::{BigInteger, Field};
 use curve25519_dalek::scalar::Scalar;
 
 #[test]
@@ -199,7 +212,7 @@ fn we_can_convert_a_slice_of_fixed_size_binary_to_a_sequence_with_correct_data()
         [0x09u8, 0x0Au8, 0x0Bu8, 0x0Cu8],
     ];
 
-    let d = Sequence::from_raw_parts_with_size(&s[..], element_size, false);
+    let d = Sequence::from_fixed_bytes::<4>(s.as_flattened());
 
     assert_eq!(d.element_size, element_size);
     assert_eq!(d.len(), 3);
@@ -233,3 +246,496 @@ fn we_can_convert_a_slice_of_arkworks_bigint_to_the_same_values_as_scalars() {
     assert_eq!(a_seq.len(), b_seq.len());
     assert_eq!(a_seq.data_slice, b_seq.data_slice);
 }
+
+#[test]
+fn we_can_compute_the_bit_width_of_sequences() {
+    let empty = Vec::<u64>::new();
+    let zeros = vec![0_u32; 10];
+    let small = vec![1_u64, 5, 3];
+    let large = vec![1_u16, 0x100, 0x7FFF];
+    let negative = vec![3_i32, -1];
+    let nonnegative = vec![3_i32, 64];
+    let scalars = vec![Scalar::from(1_u32), Scalar::from(u64::MAX)];
+    let data = [
+        Sequence::from(&empty),
+        Sequence::from(&zeros),
+        Sequence::from(&small),
+        Sequence::from(&large),
+        Sequence::from(&negative),
+        Sequence::from(&nonnegative),
+        Sequence::from(&scalars),
+    ];
+
+    assert_eq!(analyze_bit_widths(&data), vec![0, 0, 3, 15, 32, 7, 64]);
+}
+
+#[test]
+fn we_can_compute_the_bit_width_of_a_long_sequence() {
+    let mut data = vec![1_u32; 5000];
+    data[4321] = 1 << 20;
+    assert_eq!(Sequence::from(&data).bit_width(), 21);
+}
+
+#[test]
+fn we_can_validate_sequences() {
+    let a: Vec<u64> = vec![1, 2, 3];
+    let b: Vec<i128> = vec![-1];
+    let c: Vec<[u64; 4]> = vec![[1, 2, 3, 4]];
+    assert_eq!(validate(&[(&a).into(), (&b).into(), (&c).into()]), Ok(()));
+    assert_eq!(validate(&[]), Ok(()));
+}
+
+#[test]
+fn we_cannot_create_sequences_with_invalid_element_sizes() {
+    let s: Vec<u8> = vec![0; 64];
+    assert_eq!(
+        Sequence::try_from_raw_parts_with_size(&s[..], 0, false).err(),
+        Some(SequenceError::ZeroElementSize)
+    );
+    assert_eq!(
+        Sequence::try_from_raw_parts_with_size(&s[..], 32, true).err(),
+        Some(SequenceError::ElementSizeTooLarge {
+            element_size: 32,
+            max_element_size: 16
+        })
+    );
+    assert_eq!(
+        Sequence::try_from_raw_parts_with_size(&s[..], 64, false).err(),
+        Some(SequenceError::ElementSizeTooLarge {
+            element_size: 64,
+            max_element_size: 32
+        })
+    );
+    assert!(Sequence::try_from_raw_parts_with_size(&s[..], 32, false).is_ok());
+}
+
+#[test]
+fn we_cannot_create_sequences_with_a_partial_element() {
+    let s: Vec<u8> = vec![0; 10];
+    assert_eq!(
+        Sequence::try_from_raw_parts_with_size(&s[..], 4, false).err(),
+        Some(SequenceError::LengthNotMultipleOfElementSize {
+            num_bytes: 10,
+            element_size: 4
+        })
+    );
+}
+
+#[test]
+fn we_cannot_create_sequences_with_elements_straddling_typed_values() {
+    let s: Vec<[u8; 4]> = vec![[1, 2, 3, 4]; 3];
+    assert_eq!(
+        Sequence::try_from_raw_parts_with_size(&s[..], 3, false).err(),
+        Some(SequenceError::ElementSizeMismatch {
+            element_size: 3,
+            type_size: 4
+        })
+    );
+    let s: Vec<u64> = vec![1, 2, 3, 4];
+    assert_eq!(
+        Sequence::try_from_raw_parts_with_size(&s[..], 16, false).err(),
+        Some(SequenceError::ElementSizeMismatch {
+            element_size: 16,
+            type_size: 8
+        })
+    );
+
+    let s: Vec<[u8; 4]> = vec![[1, 2, 3, 4]; 3];
+    let d = Sequence::try_from_raw_parts_with_size(&s[..], 2, false).unwrap();
+    assert_eq!(d.len(), 6);
+    let s: Vec<u8> = vec![0; 12];
+    let d = Sequence::try_from_raw_parts_with_size(&s[..], 3, false).unwrap();
+    assert_eq!(d.len(), 4);
+}
+
+#[test]
+fn we_can_create_sequences_from_byte_slices_of_any_element_size() {
+    let s: Vec<u8> = (0..12).collect();
+    let d = Sequence::try_from_byte_slice_with_size(&s, 3, false).unwrap();
+    assert_eq!(d.element_size, 3);
+    assert_eq!(d.len(), 4);
+    assert_eq!(d.data_slice, &s[..]);
+    assert_eq!(
+        Sequence::try_from_byte_slice_with_size(&s, 5, false).err(),
+        Some(SequenceError::LengthNotMultipleOfElementSize {
+            num_bytes: 12,
+            element_size: 5
+        })
+    );
+}
+
+#[test]
+#[should_panic(expected = "Element size must be at least 1 byte")]
+#[allow(deprecated)]
+fn from_raw_parts_with_size_panics_on_invalid_parts() {
+    let s: Vec<u8> = vec![0; 10];
+    Sequence::from_raw_parts_with_size(&s[..], 0, false);
+}
+
+#[test]
+fn we_can_create_a_sequence_from_a_byte_slice() {
+    let s: Vec<u8> = vec![1, 0, 2, 0, 0xff, 0xff];
+    let d = Sequence::from_byte_slice(&s, 2, true);
+    assert_eq!(d.len(), 3);
+    assert_eq!(d.element_size, 2);
+    assert!(d.is_signed);
+    assert_eq!(d.data_slice, &s[..]);
+}
+
+#[test]
+fn we_can_create_a_sequence_from_a_raw_pointer() {
+    let s: Vec<u32> = vec![1, 2, 3];
+    let d = unsafe { Sequence::from_raw_pointer(s.as_ptr() as *const u8, s.len(), 4, false) };
+    let expected = Sequence::from(&s[..]);
+    assert_eq!(d.len(), 3);
+    assert_eq!(d.element_size, expected.element_size);
+    assert_eq!(d.is_signed, expected.is_signed);
+    assert_eq!(d.data_slice, expected.data_slice);
+}
+
+#[test]
+fn we_can_create_an_empty_sequence_from_a_null_pointer() {
+    let d = unsafe { Sequence::from_raw_pointer(std::ptr::null(), 0, 8, false) };
+    assert!(d.is_empty());
+    assert_eq!(d.element_size, 8);
+}
+
+#[test]
+fn we_can_convert_element_sizes_between_bits_and_bytes() {
+    const SIZE: ElementSize = ElementSize::from_bits(64);
+    assert_eq!(SIZE, ElementSize::from_bytes(8));
+    assert_eq!(SIZE, ElementSize::of::<u64>());
+    assert_eq!(SIZE.bytes(), 8);
+    assert_eq!(SIZE.bits(), 64);
+}
+
+#[test]
+#[should_panic(expected = "element size in bits must be a multiple of 8")]
+fn we_cannot_create_an_element_size_from_a_partial_number_of_bytes() {
+    ElementSize::from_bits(12);
+}
+
+#[test]
+fn we_can_create_sequences_with_typed_constructors() {
+    let a = [1_u64, 2, 3];
+    let d = Sequence::from_u64s(&a);
+    assert_eq!((d.element_size, d.len(), d.is_signed), (8, 3, false));
+
+    let b = [1_u128, 2];
+    let d = Sequence::from_u128s(&b);
+    assert_eq!((d.element_size, d.len(), d.is_signed), (16, 2, false));
+
+    let c = [Scalar::from(1_u8), Scalar::from(2_u8)];
+    let d = Sequence::from_scalars(&c);
+    assert_eq!((d.element_size, d.len(), d.is_signed), (32, 2, false));
+
+    let bytes = [1_u8, 2, 3, 4, 5, 6];
+    let d = Sequence::from_fixed_bytes::<3>(&bytes);
+    assert_eq!((d.element_size, d.len(), d.is_signed), (3, 2, false));
+    assert_eq!(d.data_slice, bytes);
+
+    let d = Sequence::try_from_bytes(&bytes, ElementSize::from_bits(16), true).unwrap();
+    assert_eq!((d.element_size, d.len(), d.is_signed), (2, 3, true));
+    assert_eq!(
+        Sequence::try_from_bytes(&bytes, ElementSize::from_bytes(4), false).err(),
+        Some(SequenceError::LengthNotMultipleOfElementSize {
+            num_bytes: 6,
+            element_size: 4
+        })
+    );
+}
+
+#[test]
+#[should_panic]
+fn we_cannot_create_a_sequence_of_fixed_bytes_from_a_partial_element() {
+    Sequence::from_fixed_bytes::<4>(&[1, 2, 3, 4, 5]);
+}
+
+#[test]
+#[cfg(feature = "bls12_381")]
+fn we_can_reduce_wide_limbs_into_a_scalar_field() {
+    type Fr = ark_bls12_381::Fr;
+    let modulus = Fr::MODULUS.0;
+    let mut modulus_plus_5 = Fr::MODULUS;
+    modulus_plus_5.add_with_carry(&5_u64.into());
+    let data = [
+        [7, 0, 0, 0, 0, 0],
+        [modulus[0], modulus[1], modulus[2], modulus[3], 0, 0],
+        [
+            modulus_plus_5.0[0],
+            modulus_plus_5.0[1],
+            modulus_plus_5.0[2],
+            modulus_plus_5.0[3],
+            0,
+            0,
+        ],
+        [0, 0, 0, 0, 1, 0],
+    ];
+    let reduced = reduce_limbs::<Fr, 6>(&data);
+    let two_to_256 = Fr::from(2_u8).pow([256]);
+    assert_eq!(
+        reduced,
+        vec![
+            [7, 0, 0, 0],
+            [0; 4],
+            [5, 0, 0, 0],
+            two_to_256.into_bigint().0
+        ]
+    );
+    let d = Sequence::from(&reduced);
+    assert_eq!((d.element_size, d.len()), (32, 4));
+}
+
+#[test]
+fn we_can_reduce_wide_limbs_modulo_the_ristretto_group_order() {
+    let data = [[3_u64, 0, 0], [0, 0, 1]];
+    let reduced = reduce_curve25519_limbs(&data);
+    let two_to_128 = Scalar::from(u128::MAX) + Scalar::ONE;
+    assert_eq!(reduced, vec![Scalar::from(3_u8), two_to_128]);
+}
+
+#[test]
+#[cfg(feature = "bn254")]
+fn we_can_reduce_wide_elements_into_owned_sequences() {
+    let hashes: Vec<[u8; 64]> = (0..3_u8).map(|i| [i.wrapping_mul(97) ^ 0x5a; 64]).collect();
+    let data: Vec<u8> = hashes.concat();
+
+    let s = OwnedSequence::try_reduce_wide::<ark_bn254::Fr>(&data, 64).unwrap();
+    let expected: Vec<_> = hashes
+        .iter()
+        .map(|h| ark_bn254::Fr::from_le_bytes_mod_order(h).into_bigint().0)
+        .collect();
+    assert_eq!(s.len(), 3);
+    assert_eq!(
+        s.as_sequence().data_slice,
+        Sequence::from(&expected).data_slice
+    );
+
+    let s = OwnedSequence::try_reduce_wide_curve25519(&data, 64).unwrap();
+    let expected: Vec<_> = hashes
+        .iter()
+        .map(Scalar::from_bytes_mod_order_wide)
+        .collect();
+    assert_eq!(
+        s.as_sequence().data_slice,
+        Sequence::from(&expected).data_slice
+    );
+
+    let s = OwnedSequence::try_reduce_wide_curve25519(&[5, 0, 0], 3).unwrap();
+    assert_eq!(s.as_sequence().data_slice[..4], [5, 0, 0, 0]);
+    assert_eq!(s.as_sequence().element_size, 32);
+}
+
+#[test]
+#[cfg(feature = "bn254")]
+fn we_cannot_reduce_elements_wider_than_64_bytes() {
+    assert_eq!(
+        OwnedSequence::try_reduce_wide_curve25519(&[0; 65], 65).unwrap_err(),
+        SequenceError::ElementSizeTooLarge {
+            element_size: 65,
+            max_element_size: 64
+        }
+    );
+    assert_eq!(
+        OwnedSequence::try_reduce_wide::<ark_bn254::Fr>(&[0; 65], 64).unwrap_err(),
+        SequenceError::LengthNotMultipleOfElementSize {
+            num_bytes: 65,
+            element_size: 64
+        }
+    );
+    assert_eq!(
+        OwnedSequence::try_reduce_wide::<ark_bn254::Fr>(&[], 0).unwrap_err(),
+        SequenceError::ZeroElementSize
+    );
+}
+
+#[cfg(feature = "num-bigint")]
+#[test]
+fn we_can_convert_biguints_to_fixed_width_elements() {
+    use super::biguints_to_fixed_bytes;
+    use num_bigint::BigUint;
+
+    let values = [
+        BigUint::from(0x0102_u16),
+        BigUint::from(0_u8),
+        BigUint::from(u32::MAX),
+    ];
+    let elements = biguints_to_fixed_bytes::<4>(&values).unwrap();
+    assert_eq!(elements, vec![[2, 1, 0, 0], [0; 4], [0xff; 4]]);
+    let d = Sequence::from(&elements);
+    assert_eq!((d.element_size, d.len(), d.is_signed), (4, 3, false));
+
+    let too_large = [BigUint::from(1_u8), BigUint::from(u32::MAX) + 1_u8];
+    assert_eq!(
+        biguints_to_fixed_bytes::<4>(&too_large),
+        Err(SequenceError::ValueTooLarge {
+            index: 1,
+            num_bits: 33,
+            max_bits: 32
+        })
+    );
+}
+
+#[test]
+fn we_can_attach_a_validity_bitmap_to_a_sequence() {
+    let data: Vec<u32> = (0..10).collect();
+    let validity = [0b1111_0110_u8, 0b01];
+    let d = Sequence::from(&data).with_validity(&validity);
+    assert_eq!(d.validity(), Some(&validity[..]));
+    assert_eq!(d.null_indices(), vec![0, 3, 9]);
+    assert!(d.is_valid(1) && !d.is_valid(9));
+    assert!(Sequence::from(&data).validity().is_none());
+}
+
+#[test]
+fn we_cannot_attach_a_validity_bitmap_that_is_too_short() {
+    let data: Vec<u32> = (0..9).collect();
+    let mut d = Sequence::from(&data);
+    d.validity = Some(&[0xff]);
+    assert_eq!(
+        d.validate(),
+        Err(SequenceError::ValidityTooShort {
+            num_bytes: 1,
+            required: 2
+        })
+    );
+}
+
+#[test]
+#[should_panic(expected = "Validity bitmap of 1 bytes is shorter than the 2 bytes required")]
+fn with_validity_panics_on_a_bitmap_that_is_too_short() {
+    let data: Vec<u32> = (0..9).collect();
+    Sequence::from(&data).with_validity(&[0xff]);
+}
+
+#[test]
+fn we_can_borrow_or_own_the_data_of_an_owned_sequence() {
+    let data: Vec<i32> = vec![-1, 2, 3];
+    let borrowed = OwnedSequence::from(&data);
+    assert!(!borrowed.is_owned());
+    assert_eq!(borrowed.len(), 3);
+    let owned = borrowed.clone().into_owned();
+    assert!(owned.is_owned());
+    let expected = Sequence::from(&data);
+    for s in [borrowed.as_sequence(), Sequence::from(&owned)] {
+        assert_eq!(s.data_slice, expected.data_slice);
+        assert_eq!(s.element_size, expected.element_size);
+        assert!(s.is_signed);
+    }
+
+    let owned = OwnedSequence::from(vec![1_u16, 2]);
+    assert!(owned.is_owned());
+    assert_eq!(owned.data()[..], [1, 0, 2, 0]);
+}
+
+#[test]
+fn owned_sequences_keep_the_validity_bitmap_of_a_sequence() {
+    let data: Vec<u32> = vec![1, 2, 3];
+    let validity = [0b101_u8];
+    let borrowed = OwnedSequence::from(Sequence::from(&data).with_validity(&validity));
+    assert_eq!(borrowed.validity(), Some(&validity[..]));
+    let owned = borrowed.into_owned();
+    assert_eq!(owned.validity(), Some(&validity[..]));
+    assert_eq!(owned.as_sequence().validity(), Some(&validity[..]));
+    assert_eq!(OwnedSequence::from(&data).validity(), None);
+}
+
+#[test]
+fn we_can_create_owned_sequences_from_big_endian_data() {
+    let values: Vec<i32> = vec![-2, 3, 0x01020304];
+    let be: Vec<u8> = values.iter().flat_map(|v| v.to_be_bytes()).collect();
+    let expected = Sequence::from(&values);
+
+    let borrowed = OwnedSequence::try_new_with_endianness(
+        SequenceData::Borrowed(&be),
+        4,
+        true,
+        Endianness::Big,
+    )
+    .unwrap();
+    let owned = OwnedSequence::try_new_with_endianness(
+        SequenceData::Owned(be.clone()),
+        4,
+        true,
+        Endianness::Big,
+    )
+    .unwrap();
+    for s in [&borrowed, &owned] {
+        assert!(s.is_owned());
+        assert_eq!(s.as_sequence().data_slice, expected.data_slice);
+    }
+
+    let le = OwnedSequence::try_new_with_endianness(
+        SequenceData::Borrowed(expected.data_slice),
+        4,
+        true,
+        Endianness::Little,
+    )
+    .unwrap();
+    assert!(!le.is_owned());
+    assert_eq!(
+        OwnedSequence::try_new_with_endianness(
+            SequenceData::Borrowed(&be[..5]),
+            4,
+            true,
+            Endianness::Big
+        )
+        .unwrap_err(),
+        SequenceError::LengthNotMultipleOfElementSize {
+            num_bytes: 5,
+            element_size: 4
+        }
+    );
+}
+
+#[test]
+fn we_cannot_create_an_owned_sequence_from_invalid_parts() {
+    assert_eq!(
+        OwnedSequence::try_new(SequenceData::Owned(vec![0; 5]), 2, false).unwrap_err(),
+        SequenceError::LengthNotMultipleOfElementSize {
+            num_bytes: 5,
+            element_size: 2
+        }
+    );
+    assert_eq!(
+        OwnedSequence::try_new(SequenceData::Borrowed(&[]), 0, false).unwrap_err(),
+        SequenceError::ZeroElementSize
+    );
+}
+
+#[test]
+#[cfg(all(feature = "arkworks", feature = "bn254"))]
+fn we_can_convert_arkworks_field_elements_to_an_owned_sequence_of_canonical_bytes() {
+    let a = [ark_bn254::Fr::from(123u32), -ark_bn254::Fr::from(1u32)];
+    let s = OwnedSequence::from(&a[..]);
+    assert!(s.is_owned());
+    let expected: Vec<_> = a.iter().map(|x| x.into_bigint()).collect();
+    assert_eq!(
+        s.as_sequence().data_slice,
+        Sequence::from(&expected).data_slice
+    );
+}
+
+#[test]
+#[cfg(all(feature = "arkworks", feature = "grumpkin"))]
+fn we_can_convert_grumpkin_field_elements_to_an_owned_sequence_of_canonical_bytes() {
+    let fr = [ark_grumpkin::Fr::from(7u32), -ark_grumpkin::Fr::from(2u32)];
+    let fq = [ark_grumpkin::Fq::from(7u32), -ark_grumpkin::Fq::from(2u32)];
+    let fr_sequence = OwnedSequence::from(&fr[..]);
+    let fq_sequence = OwnedSequence::from(&fq[..]);
+
+    let fr_expected: Vec<_> = fr.iter().map(|x| x.into_bigint()).collect();
+    let fq_expected: Vec<_> = fq.iter().map(|x| x.into_bigint()).collect();
+    assert_eq!(
+        fr_sequence.as_sequence().data_slice,
+        Sequence::from(&fr_expected).data_slice
+    );
+    assert_eq!(
+        fq_sequence.as_sequence().data_slice,
+        Sequence::from(&fq_expected).data_slice
+    );
+    assert_eq!(
+        fr_sequence.as_sequence().data_slice[..8],
+        7_u64.to_le_bytes()
+    );
+}
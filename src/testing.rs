@@ -0,0 +1,244 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! reusable conformance checks of the commitment computation
+//!
+//! This module is only available with the `testing` feature. It builds random workloads
+//! and compares the results of [crate::compute] with the pure-Rust implementations of
+//! [crate::reference], so that downstream crates can check the backend they link against
+//! without duplicating the correctness tests of this crate:
+//!
+//! ```no_run
+//! #[test]
+//! fn blitzar_commitments_are_correct() {
+//!     blitzar::testing::check_all_curves(42);
+//! }
+//! ```
+
+use crate::{
+    compute::{self, get_curve25519_generators, MsmHandle},
+    reference,
+    sequence::{OwnedSequence, Sequence, SequenceData},
+};
+use ark_bls12_381::G1Affine;
+use ark_bn254::G1Affine as bn254_g1_affine;
+use ark_ec::short_weierstrass::{Affine, SWCurveConfig};
+use ark_grumpkin::Affine as grumpkin_affine;
+use ark_std::{
+    rand::{rngs::StdRng, Rng, RngCore, SeedableRng},
+    UniformRand,
+};
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+
+/// Element sizes and signedness of the sequences built by [random_sequences].
+const ELEMENT_KINDS: [(usize, bool); 7] = [
+    (1, false),
+    (2, true),
+    (4, false),
+    (8, true),
+    (16, true),
+    (16, false),
+    (32, false),
+];
+
+/// Builds `num_sequences` sequences of random bytes with up to `max_length` elements each.
+///
+/// The element size and signedness of each sequence are picked among the ones supported
+/// by the backend, so that a workload covers both signed and unsigned data.
+pub fn random_sequences<R: RngCore>(
+    rng: &mut R,
+    num_sequences: usize,
+    max_length: usize,
+) -> Vec<OwnedSequence<'static>> {
+    (0..num_sequences)
+        .map(|_| {
+            let (element_size, is_signed) = ELEMENT_KINDS[rng.gen_range(0..ELEMENT_KINDS.len())];
+            let mut data = vec![0_u8; element_size * rng.gen_range(0..=max_length)];
+            rng.fill_bytes(&mut data);
+            OwnedSequence::try_new(SequenceData::Owned(data), element_size, is_signed)
+                .expect("the element kinds are valid")
+        })
+        .collect()
+}
+
+/// Returns the length of the longest sequence of `data`.
+fn longest_sequence(data: &[Sequence]) -> usize {
+    data.iter().map(Sequence::len).max().unwrap_or(0)
+}
+
+/// Asserts that every `curve25519` commitment function agrees with the reference.
+///
+/// The generators are the ones of [compute::compute_curve25519_commitments] starting at
+/// `offset_generators`, and are also passed explicitly and through a handle.
+pub fn assert_curve25519_commitments_match(data: &[Sequence], offset_generators: u64) {
+    let mut generators = vec![RistrettoPoint::default(); longest_sequence(data)];
+    get_curve25519_generators(&mut generators, offset_generators);
+    let mut expected = vec![CompressedRistretto::default(); data.len()];
+    reference::compute_curve25519_commitments_with_generators(&mut expected, data, &generators);
+
+    let mut commitments = vec![CompressedRistretto::default(); data.len()];
+    compute::compute_curve25519_commitments(&mut commitments, data, offset_generators);
+    assert_eq!(commitments, expected, "compute_curve25519_commitments");
+    compute::compute_curve25519_commitments_with_generators(&mut commitments, data, &generators);
+    assert_eq!(
+        commitments, expected,
+        "compute_curve25519_commitments_with_generators"
+    );
+    if !generators.is_empty() {
+        let handle = MsmHandle::new(&generators);
+        compute::compute_curve25519_commitments_with_handle(&mut commitments, data, &handle);
+        assert_eq!(
+            commitments, expected,
+            "compute_curve25519_commitments_with_handle"
+        );
+    }
+}
+
+/// Asserts that the `bls12-381` `G1` commitment functions agree with the reference.
+pub fn assert_bls12_381_g1_commitments_match(data: &[Sequence], generators: &[G1Affine]) {
+    let mut expected = vec![[0_u8; 48]; data.len()];
+    reference::compute_bls12_381_g1_commitments_with_generators(&mut expected, data, generators);
+
+    let mut commitments = vec![[0_u8; 48]; data.len()];
+    compute::compute_bls12_381_g1_commitments_with_generators(&mut commitments, data, generators);
+    assert_eq!(
+        commitments, expected,
+        "compute_bls12_381_g1_commitments_with_generators"
+    );
+    if !generators.is_empty() {
+        let handle = MsmHandle::new(&generators.iter().map(Into::into).collect::<Vec<_>>());
+        compute::compute_bls12_381_g1_commitments_with_handle(&mut commitments, data, &handle);
+        assert_eq!(
+            commitments, expected,
+            "compute_bls12_381_g1_commitments_with_handle"
+        );
+    }
+}
+
+/// Asserts that the `bn254` `G1` commitment functions agree with the reference.
+pub fn assert_bn254_g1_commitments_match(data: &[Sequence], generators: &[bn254_g1_affine]) {
+    let mut expected = vec![bn254_g1_affine::default(); data.len()];
+    reference::compute_bn254_g1_uncompressed_commitments_with_generators(
+        &mut expected,
+        data,
+        generators,
+    );
+
+    let mut commitments = vec![bn254_g1_affine::default(); data.len()];
+    compute::compute_bn254_g1_uncompressed_commitments_with_generators(
+        &mut commitments,
+        data,
+        generators,
+    );
+    assert_eq!(
+        commitments, expected,
+        "compute_bn254_g1_uncompressed_commitments_with_generators"
+    );
+    if !generators.is_empty() {
+        let handle = MsmHandle::new(&generators.iter().map(Into::into).collect::<Vec<_>>());
+        compute::compute_bn254_g1_uncompressed_commitments_with_handle(
+            &mut commitments,
+            data,
+            &handle,
+        );
+        assert_eq!(
+            commitments, expected,
+            "compute_bn254_g1_uncompressed_commitments_with_handle"
+        );
+    }
+}
+
+/// Asserts that the `grumpkin` commitment functions agree with the reference.
+pub fn assert_grumpkin_commitments_match(data: &[Sequence], generators: &[grumpkin_affine]) {
+    let mut expected = vec![grumpkin_affine::default(); data.len()];
+    reference::compute_grumpkin_uncompressed_commitments_with_generators(
+        &mut expected,
+        data,
+        generators,
+    );
+
+    let mut commitments = vec![grumpkin_affine::default(); data.len()];
+    compute::compute_grumpkin_uncompressed_commitments_with_generators(
+        &mut commitments,
+        data,
+        generators,
+    );
+    assert_eq!(
+        commitments, expected,
+        "compute_grumpkin_uncompressed_commitments_with_generators"
+    );
+    if !generators.is_empty() {
+        let handle = MsmHandle::new(&generators.iter().map(Into::into).collect::<Vec<_>>());
+        compute::compute_grumpkin_uncompressed_commitments_with_handle(
+            &mut commitments,
+            data,
+            &handle,
+        );
+        assert_eq!(
+            commitments, expected,
+            "compute_grumpkin_uncompressed_commitments_with_handle"
+        );
+    }
+}
+
+fn random_generators<C: SWCurveConfig, R: RngCore>(rng: &mut R, n: usize) -> Vec<Affine<C>> {
+    (0..n).map(|_| Affine::<C>::rand(rng)).collect()
+}
+
+/// Checks every curve against the reference on a few random workloads derived from
+/// `seed`.
+///
+/// # Panics
+///
+/// If any commitment differs from the reference.
+pub fn check_all_curves(seed: u64) {
+    const NUM_WORKLOADS: usize = 4;
+    const NUM_SEQUENCES: usize = 5;
+    const MAX_LENGTH: usize = 16;
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    for _ in 0..NUM_WORKLOADS {
+        let owned = random_sequences(&mut rng, NUM_SEQUENCES, MAX_LENGTH);
+        let data: Vec<Sequence> = owned.iter().map(Into::into).collect();
+        let n = longest_sequence(&data);
+
+        assert_curve25519_commitments_match(&data, rng.gen_range(0..64));
+        assert_bls12_381_g1_commitments_match(&data, &random_generators(&mut rng, n));
+        assert_bn254_g1_commitments_match(&data, &random_generators(&mut rng, n));
+        assert_grumpkin_commitments_match(&data, &random_generators(&mut rng, n));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn we_can_check_every_curve_against_the_reference() {
+        check_all_curves(0);
+        check_all_curves(1);
+    }
+
+    #[test]
+    fn random_sequences_are_valid_and_reproducible() {
+        let a = random_sequences(&mut StdRng::seed_from_u64(3), 10, 4);
+        let b = random_sequences(&mut StdRng::seed_from_u64(3), 10, 4);
+        assert_eq!(a.len(), 10);
+        for (a, b) in a.iter().zip(&b) {
+            assert!(a.len() <= 4);
+            assert!(a.as_sequence().validate().is_ok());
+            assert_eq!(a.data(), b.data());
+        }
+    }
+}